@@ -3,7 +3,7 @@
 //! This program builds a `ProxmoxClient`, checks its initial authentication
 //! state, performs login, and then retrieves the issued session and CSRF tokens.
 
-use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+use leeca_proxmox::{ProxmoxClient, ProxmoxResult, ValidationConfig};
 
 #[tokio::main]
 async fn main() -> ProxmoxResult<()> {
@@ -45,11 +45,15 @@ async fn main() -> ProxmoxResult<()> {
         }
     );
 
+    // Lifetimes the client uses to decide when to refresh; these are the defaults.
+    let config = ValidationConfig::default();
+
     // Retrieve the issued authentication ticket.
     if let Some(token) = client.auth_token().await {
         println!("\n🎟️  Session Token");
         println!("------------------------");
         println!("Value: {}", token.as_str());
+        println!("Expires at: {:?}", token.expires_at(config.ticket_lifetime));
     }
 
     // Retrieve the CSRF prevention token used for state-changing operations.
@@ -57,6 +61,7 @@ async fn main() -> ProxmoxResult<()> {
         println!("\n🛡️  CSRF Token");
         println!("------------------------");
         println!("Value: {}", csrf.as_str());
+        println!("Expires at: {:?}", csrf.expires_at(config.csrf_lifetime));
     }
 
     println!("\nConnection established successfully.\n");
@@ -102,43 +102,34 @@ async fn main() -> ProxmoxResult<()> {
         // Lifecycle operations (disabled by default).
         /*
         println!("\nStopping VM...");
-        let task = client.stop_vm(node, vmid).await?;
-        println!("  Task ID: {}", task);
+        let outcome = client.stop_vm(node, vmid).await?;
+        println!("  Task ID: {:?}", outcome.task_id());
 
         println!("\nStarting VM...");
-        let task = client.start_vm(node, vmid).await?;
-        println!("  Task ID: {}", task);
+        let outcome = client.start_vm(node, vmid).await?;
+        println!("  Task ID: {:?}", outcome.task_id());
         */
     }
 
     // 3. Prepare parameters for VM creation.
     println!("\nCreating a new VM (not executed):");
 
-    let params = CreateVmParams {
-        vmid: 9999,
-        name: "test-vm".to_string(),
-        memory: Some(2048),
-        sockets: Some(1),
-        cores: Some(2),
-        threads: None,
-        cpu: Some("host".to_string()),
-        ostype: Some("l26".to_string()),
-        kvm: Some(1),
-        numa: None,
-        net: Some("virtio,bridge=vmbr0".to_string()),
-        scsihw: Some("virtio-scsi-pci".to_string()),
-        boot: Some("order=scsi0;net0".to_string()),
-        start: Some(0),
-        tags: Some("example".to_string()),
-        description: Some("Created via ProxmoxClient".to_string()),
-        protection: None,
-        tablet: Some(1),
-        vga: Some("virtio".to_string()),
-        bios: None,
-        efidisk: None,
-        tpmstate: None,
-        agent: Some(1),
-    };
+    let params = CreateVmParams::builder(9999, "test-vm")
+        .memory(2048)
+        .cores(2)
+        .cpu("host")
+        .ostype("l26")
+        .kvm(true)
+        .net("virtio,bridge=vmbr0")
+        .scsihw("virtio-scsi-pci")
+        .boot("order=scsi0;net0")
+        .start(false)
+        .tags("example")
+        .description("Created via ProxmoxClient")
+        .tablet(true)
+        .vga("virtio")
+        .agent(true)
+        .build()?;
 
     println!(
         "  Prepared VM creation request for ID {} with name '{}'",
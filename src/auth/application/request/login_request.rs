@@ -1,4 +1,5 @@
 use serde::Serialize;
+use zeroize::Zeroize;
 
 #[derive(Serialize)]
 pub struct LoginRequest {
@@ -6,3 +7,9 @@ pub struct LoginRequest {
     pub password: String,
     pub realm: String,
 }
+
+impl Drop for LoginRequest {
+    fn drop(&mut self) {
+        self.password.zeroize();
+    }
+}
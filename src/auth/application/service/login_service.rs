@@ -8,7 +8,7 @@ use crate::{
 
 use reqwest::{
     Client, StatusCode,
-    header::{ACCEPT, CONTENT_TYPE, HeaderMap},
+    header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue},
 };
 use std::backtrace::Backtrace;
 
@@ -19,43 +19,24 @@ pub struct LoginService {
 impl LoginService {
     pub fn new() -> Self {
         let mut default_headers = HeaderMap::new();
-        default_headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
-        default_headers.insert(ACCEPT, "application/json".parse().unwrap());
-
-        // Add Cloudflare Access headers if environment variables are present
-        if let Ok(client_id) = std::env::var("CF_ACCESS_CLIENT_ID")
-            && !client_id.is_empty()
-        {
-            default_headers.insert(
-                "CF-Access-Client-Id",
-                format!("{}.access", client_id).parse().unwrap(),
-            );
-
-            if let Ok(client_secret) = std::env::var("CF_ACCESS_CLIENT_SECRET")
-                && !client_secret.is_empty()
-            {
-                default_headers.insert("CF-Access-Client-Secret", client_secret.parse().unwrap());
-            }
-        }
+        default_headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        default_headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
 
         Self { default_headers }
     }
 
-    pub async fn execute(&self, connection: &ProxmoxConnection) -> ProxmoxResult<ProxmoxAuth> {
-        println!("Building HTTP client with connection settings");
-        let http_client = Client::builder()
-            .danger_accept_invalid_certs(connection.accept_invalid_certs())
-            .build()
-            .map_err(|e| {
-                println!("Failed to build HTTP client: {}", e);
-                ProxmoxError::Connection(e.to_string())
-            })?;
+    /// Logs in over `http_client`, which should be the client used for API requests so the
+    /// login shares its TLS settings, User-Agent and connection pool.
+    pub async fn execute(
+        &self,
+        http_client: &Client,
+        connection: &ProxmoxConnection,
+    ) -> ProxmoxResult<ProxmoxAuth> {
+        let request = self.build_login_request(connection)?;
 
         let url = self.build_login_url(connection)?;
-        println!("URL built: {}", url);
 
-        let request = self.build_login_request(connection);
-        let response = self.send_request(&http_client, &url, &request).await?;
+        let response = self.send_request(http_client, &url, &request).await?;
 
         match response.status() {
             StatusCode::OK => self.handle_successful_login(response).await,
@@ -89,12 +70,19 @@ impl LoginService {
         Ok(format!("{}/api2/json/access/ticket", base))
     }
 
-    fn build_login_request(&self, connection: &ProxmoxConnection) -> LoginRequest {
-        LoginRequest {
+    fn build_login_request(&self, connection: &ProxmoxConnection) -> ProxmoxResult<LoginRequest> {
+        let password = connection.password().ok_or_else(|| {
+            ProxmoxError::Authentication(
+                "No credentials available to log in again: the client was restored from a session \
+                 without a password; supply credentials or load a fresh session"
+                    .to_string(),
+            )
+        })?;
+        Ok(LoginRequest {
             username: connection.username().as_str().to_string(),
-            password: connection.password().as_str().to_string(),
+            password: password.as_str().to_string(),
             realm: connection.realm().as_str().to_string(),
-        }
+        })
     }
 
     async fn send_request(
@@ -103,34 +91,23 @@ impl LoginService {
         url: &str,
         request: &LoginRequest,
     ) -> ProxmoxResult<reqwest::Response> {
-        println!("Sending request to: {}", url);
-        let response = client
+        client
             .post(url)
             .headers(self.default_headers.clone())
             .json(request)
             .send()
-            .await;
-
-        match response {
-            Ok(r) => {
-                println!("Response status: {}", r.status());
-                println!("Response headers: {:?}", r.headers());
-                Ok(r)
-            }
-            Err(e) => {
-                println!("Request failed: {}", e);
-                Err(ProxmoxError::Connection(e.to_string()))
-            }
-        }
+            .await
+            .map_err(|e| ProxmoxError::http("Login request failed", e))
     }
 
     async fn handle_successful_login(
         &self,
         response: reqwest::Response,
     ) -> ProxmoxResult<ProxmoxAuth> {
-        let login_response = response.json::<LoginResponse>().await.map_err(|e| {
-            ProxmoxError::Connection(format!("Failed to parse login response: {}", e))
-        })?;
+        let login_response = response
+            .json::<LoginResponse>()
+            .await
+            .map_err(|e| ProxmoxError::http("Failed to parse login response", e))?;
 
         // Validate ticket and CSRF token format (optional, but good to catch server errors)
         let ticket_str = login_response.data.ticket;
@@ -201,7 +178,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(result.is_ok());
         let auth = result.unwrap();
         assert_eq!(
@@ -225,7 +202,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(matches!(result, Err(ProxmoxError::Authentication(_))));
     }
 
@@ -240,7 +217,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(matches!(result, Err(ProxmoxError::Validation { .. })));
     }
 
@@ -255,7 +232,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(matches!(result, Err(ProxmoxError::Connection(_))));
     }
 
@@ -270,7 +247,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(matches!(result, Err(ProxmoxError::Connection(_))));
     }
 
@@ -290,7 +267,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(matches!(result, Err(ProxmoxError::Validation { .. })));
     }
 
@@ -310,7 +287,7 @@ mod tests {
 
         let connection = create_test_connection(&mock_server.uri()).await;
         let service = LoginService::new();
-        let result = service.execute(&connection).await;
+        let result = service.execute(&Client::new(), &connection).await;
         assert!(matches!(result, Err(ProxmoxError::Validation { .. })));
     }
 }
@@ -0,0 +1,246 @@
+//! Synchronous facade over [`ProxmoxClient`] for non-async consumers.
+//!
+//! Enabled with the `blocking` feature. [`BlockingProxmoxClient`] owns a single
+//! `current_thread` Tokio runtime and drives every call to completion on it, so it can be
+//! used from plain `fn main()` programs such as CLIs.
+//!
+//! These methods must not be called from within an async context; doing so panics,
+//! as with any nested Tokio runtime.
+//!
+//! # Example
+//! ```no_run
+//! use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+//!
+//! fn main() -> ProxmoxResult<()> {
+//!     let mut client = ProxmoxClient::builder()
+//!         .host("192.168.1.182")
+//!         .port(8006)
+//!         .credentials("leeca", "password", "pam")
+//!         .build_blocking()?;
+//!
+//!     client.login()?;
+//!     for node in client.nodes()? {
+//!         println!("Node: {}", node.node);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    ClusterResource, CreateVmParams, DeleteVmOptions, NodeDnsConfig, NodeListItem, NodeName,
+    NodeStatus, ProxmoxClient, ProxmoxClientBuilder, ProxmoxError, ProxmoxResult, TaskOutcome,
+    UpdateVmConfig, VmConfig, VmId, VmListItem, VmStatusCurrent,
+};
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+/// A blocking wrapper around [`ProxmoxClient`].
+///
+/// Each method mirrors its async counterpart and blocks the calling thread until it completes.
+/// Methods that are not mirrored can still be reached through [`client`](Self::client) and
+/// [`block_on`](Self::block_on).
+#[derive(Debug)]
+pub struct BlockingProxmoxClient {
+    inner: ProxmoxClient,
+    runtime: Runtime,
+}
+
+impl ProxmoxClientBuilder {
+    /// Constructs a [`BlockingProxmoxClient`], running [`build`](Self::build) on its runtime.
+    pub fn build_blocking(self) -> ProxmoxResult<BlockingProxmoxClient> {
+        let runtime = create_runtime()?;
+        let inner = runtime.block_on(self.build())?;
+        Ok(BlockingProxmoxClient { inner, runtime })
+    }
+}
+
+impl BlockingProxmoxClient {
+    /// Wraps an existing async client.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if the internal runtime cannot be created.
+    pub fn from_client(client: ProxmoxClient) -> ProxmoxResult<Self> {
+        Ok(Self {
+            inner: client,
+            runtime: create_runtime()?,
+        })
+    }
+
+    /// Returns the wrapped async client.
+    #[must_use]
+    pub fn client(&self) -> &ProxmoxClient {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the async client.
+    #[must_use]
+    pub fn into_inner(self) -> ProxmoxClient {
+        self.inner
+    }
+
+    /// Runs an arbitrary future to completion on the internal runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Blocking version of [`ProxmoxClient::login`].
+    pub fn login(&mut self) -> ProxmoxResult<()> {
+        self.runtime.block_on(self.inner.login())
+    }
+
+    /// Blocking version of [`ProxmoxClient::logout`].
+    pub fn logout(&self) {
+        self.runtime.block_on(self.inner.logout())
+    }
+
+    /// Blocking version of [`ProxmoxClient::is_authenticated`].
+    pub fn is_authenticated(&self) -> bool {
+        self.runtime.block_on(self.inner.is_authenticated())
+    }
+
+    /// Blocking version of [`ProxmoxClient::cluster_resources`].
+    pub fn cluster_resources(&self) -> ProxmoxResult<Vec<ClusterResource>> {
+        self.runtime.block_on(self.inner.cluster_resources())
+    }
+
+    /// Blocking version of [`ProxmoxClient::nodes`].
+    pub fn nodes(&self) -> ProxmoxResult<Vec<NodeListItem>> {
+        self.runtime.block_on(self.inner.nodes())
+    }
+
+    /// Blocking version of [`ProxmoxClient::node_status`].
+    pub fn node_status(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<NodeStatus> {
+        self.runtime.block_on(self.inner.node_status(node))
+    }
+
+    /// Blocking version of [`ProxmoxClient::node_dns`].
+    pub fn node_dns(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<NodeDnsConfig> {
+        self.runtime.block_on(self.inner.node_dns(node))
+    }
+
+    /// Blocking version of [`ProxmoxClient::vms`].
+    pub fn vms(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<VmListItem>> {
+        self.runtime.block_on(self.inner.vms(node))
+    }
+
+    /// Blocking version of [`ProxmoxClient::vm_status`].
+    pub fn vm_status(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<VmStatusCurrent> {
+        self.runtime.block_on(self.inner.vm_status(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::start_vm`].
+    pub fn start_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        self.runtime.block_on(self.inner.start_vm(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::stop_vm`].
+    pub fn stop_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        self.runtime.block_on(self.inner.stop_vm(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::shutdown_vm`].
+    pub fn shutdown_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        self.runtime.block_on(self.inner.shutdown_vm(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::reboot_vm`].
+    pub fn reboot_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        self.runtime.block_on(self.inner.reboot_vm(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::reset_vm`].
+    pub fn reset_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        self.runtime.block_on(self.inner.reset_vm(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::delete_vm`].
+    pub fn delete_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        options: &DeleteVmOptions,
+    ) -> ProxmoxResult<String> {
+        self.runtime
+            .block_on(self.inner.delete_vm(node, vmid, options))
+    }
+
+    /// Blocking version of [`ProxmoxClient::delete_vm_simple`].
+    pub fn delete_vm_simple(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        purge: bool,
+    ) -> ProxmoxResult<String> {
+        self.runtime
+            .block_on(self.inner.delete_vm_simple(node, vmid, purge))
+    }
+
+    /// Blocking version of [`ProxmoxClient::create_vm`].
+    pub fn create_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &CreateVmParams,
+    ) -> ProxmoxResult<String> {
+        self.runtime.block_on(self.inner.create_vm(node, params))
+    }
+
+    /// Blocking version of [`ProxmoxClient::vm_config`].
+    pub fn vm_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<VmConfig> {
+        self.runtime.block_on(self.inner.vm_config(node, vmid))
+    }
+
+    /// Blocking version of [`ProxmoxClient::update_vm_config`].
+    pub fn update_vm_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &UpdateVmConfig,
+    ) -> ProxmoxResult<TaskOutcome> {
+        self.runtime
+            .block_on(self.inner.update_vm_config(node, vmid, params))
+    }
+}
+
+fn create_runtime() -> ProxmoxResult<Runtime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| ProxmoxError::Unexpected(format!("Failed to build Tokio runtime: {}", e)))
+}
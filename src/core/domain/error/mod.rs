@@ -15,33 +15,114 @@ pub enum ProxmoxError {
     #[error("Connection error: {0}")]
     Connection(String),
 
+    /// The API answered with an unsuccessful HTTP status.
+    ///
+    /// `message` includes the status and the response body, e.g.
+    /// "API error (403 Forbidden): permission check failed".
+    #[error("{message}")]
+    Api { status: u16, message: String },
+
+    /// An HTTP exchange failed in transport or its response body could not be decoded.
+    ///
+    /// [`source`](std::error::Error::source) returns the underlying `reqwest` error.
+    #[error("HTTP error: {message}: {source}")]
+    Http {
+        message: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     /// Validation error with backtrace.
-    #[error("Validation error")]
+    #[error("Validation error: {source}")]
     Validation {
         source: ValidationError,
         backtrace: Backtrace,
     },
 
+    /// An operation did not complete within the allotted time.
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    /// The resource changed since it was read (e.g., a config `digest` no longer matches).
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// A server-side task finished unsuccessfully.
+    #[error("Task failed: {0}")]
+    TaskFailed(String),
+
     /// Session persistence error (I/O, serialization, etc.)
     #[error("Session error: {0}")]
     Session(String),
 
+    /// An encrypted session could not be decrypted (wrong passphrase or tampered file).
+    #[error("Session decryption failed: {0}")]
+    SessionDecryption(String),
+
     /// Other unexpected errors.
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 }
 
 /// Validation-specific errors.
+///
+/// Always reported through [`ProxmoxError::Validation`], which adds a backtrace.
 #[derive(Debug, Error)]
 pub enum ValidationError {
+    /// A required or named input is missing or invalid.
     #[error("Field '{field}' is invalid: {message}")]
     Field { field: String, message: String },
 
+    /// A value does not have the expected syntax.
     #[error("Format error: {0}")]
     Format(String),
 
+    /// A well-formed value breaks a policy rule (e.g., password strength, reserved names).
     #[error("Constraint violation: {0}")]
     ConstraintViolation(String),
+
+    /// Required inputs are missing; lists every one of them.
+    #[error("Missing required fields: {}", .0.join(", "))]
+    MissingFields(Vec<String>),
+}
+
+impl ProxmoxError {
+    /// Returns the HTTP status of an [`Api`](Self::Api) error.
+    ///
+    /// # Example
+    /// ```
+    /// use leeca_proxmox::ProxmoxError;
+    ///
+    /// let err = ProxmoxError::Api {
+    ///     status: 403,
+    ///     message: "API error (403 Forbidden): permission check failed".to_string(),
+    /// };
+    /// assert_eq!(err.status_code(), Some(403));
+    /// assert_eq!(ProxmoxError::Timeout("task".to_string()).status_code(), None);
+    /// ```
+    #[must_use]
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            ProxmoxError::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Wraps a transport or decoding failure, keeping it as the error source.
+    pub(crate) fn http(
+        message: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        ProxmoxError::Http {
+            message: message.into(),
+            source: source.into(),
+        }
+    }
+}
+
+impl From<std::convert::Infallible> for ProxmoxError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
 }
 
 impl From<std::io::Error> for ProxmoxError {
@@ -55,3 +136,37 @@ impl From<serde_json::Error> for ProxmoxError {
         ProxmoxError::Session(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_error_display() {
+        let field = ValidationError::Field {
+            field: "host".to_string(),
+            message: "Host cannot be empty".to_string(),
+        };
+        assert_eq!(
+            field.to_string(),
+            "Field 'host' is invalid: Host cannot be empty"
+        );
+        assert_eq!(
+            ValidationError::Format("Invalid URL".to_string()).to_string(),
+            "Format error: Invalid URL"
+        );
+        assert_eq!(
+            ValidationError::ConstraintViolation("Username is reserved".to_string()).to_string(),
+            "Constraint violation: Username is reserved"
+        );
+
+        let err = ProxmoxError::Validation {
+            source: field,
+            backtrace: Backtrace::capture(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Validation error: Field 'host' is invalid: Host cannot be empty"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Domain models for QEMU guest agent operations.
+//!
+//! This module defines the structures used by the `/nodes/{node}/qemu/{vmid}/agent/*` endpoints.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Response of `/agent/exec`: the PID of the process started inside the guest.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AgentExecPid {
+    /// Guest process identifier, used to query `/agent/exec-status`.
+    pub pid: u64,
+}
+
+/// Raw status of a guest process from `/agent/exec-status`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct AgentExecStatus {
+    /// Whether the process has finished.
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::int_bool::deserialize"
+    )]
+    pub exited: bool,
+    /// Process exit code (present once exited normally).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exitcode: Option<i64>,
+    /// Signal number if the process was killed by a signal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal: Option<i64>,
+    /// Captured standard output (possibly base64-encoded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_data: Option<String>,
+    /// Captured standard error (possibly base64-encoded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub err_data: Option<String>,
+    /// Whether standard output was truncated by the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_truncated: Option<bool>,
+    /// Whether standard error was truncated by the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub err_truncated: Option<bool>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Final result of a guest command run via [`ProxmoxClient::agent_exec_wait`](crate::ProxmoxClient::agent_exec_wait).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AgentExecResult {
+    /// Guest process identifier.
+    pub pid: u64,
+    /// Process exit code (absent if terminated by a signal).
+    pub exitcode: Option<i64>,
+    /// Signal number if the process was killed by a signal.
+    pub signal: Option<i64>,
+    /// Decoded standard output.
+    pub stdout: String,
+    /// Decoded standard error.
+    pub stderr: String,
+    /// Whether either stream was truncated by the agent.
+    pub truncated: bool,
+}
+
+impl AgentExecResult {
+    /// Builds the result from a finished [`AgentExecStatus`], decoding the output streams.
+    pub(crate) fn from_status(pid: u64, status: AgentExecStatus) -> Self {
+        Self {
+            pid,
+            exitcode: status.exitcode,
+            signal: status.signal,
+            stdout: decode_output(status.out_data.as_deref()),
+            stderr: decode_output(status.err_data.as_deref()),
+            truncated: status.out_truncated.unwrap_or(false)
+                || status.err_truncated.unwrap_or(false),
+        }
+    }
+
+    /// Returns `true` if the process exited with status `0`.
+    #[must_use]
+    pub fn success(&self) -> bool {
+        self.exitcode == Some(0)
+    }
+}
+
+/// Envelope of agent commands that return a value (`{"result": ...}`).
+#[derive(Debug, Deserialize)]
+pub(crate) struct AgentResponse<T> {
+    pub(crate) result: T,
+}
+
+/// A guest network interface from `/agent/network-get-interfaces`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct AgentInterface {
+    /// Interface name inside the guest (e.g., "eth0", "lo").
+    pub name: String,
+    /// MAC address (absent for some virtual interfaces).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardware_address: Option<String>,
+    /// Addresses assigned to the interface.
+    #[serde(default)]
+    pub ip_addresses: Vec<AgentIpAddress>,
+    /// Fields returned by the agent that this crate does not model yet (e.g., `statistics`).
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// An address reported by the guest agent.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AgentIpAddress {
+    /// The address without prefix (e.g., "192.168.1.50", "fe80::1").
+    pub ip_address: String,
+    /// Address family.
+    pub ip_address_type: IpAddressType,
+    /// Prefix length of the network.
+    pub prefix: u8,
+}
+
+/// Address family of an [`AgentIpAddress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpAddressType {
+    /// IPv4.
+    Ipv4,
+    /// IPv6.
+    Ipv6,
+}
+
+/// Decodes agent output.
+///
+/// Depending on the Proxmox and agent versions, output is returned either as plain text or
+/// base64-encoded. Strict base64 that decodes to valid UTF-8 is decoded; anything else is
+/// returned verbatim.
+fn decode_output(data: Option<&str>) -> String {
+    let Some(data) = data else {
+        return String::new();
+    };
+    STANDARD
+        .decode(data.trim_end())
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| data.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_output_base64() {
+        assert_eq!(decode_output(Some("aGVsbG8K")), "hello\n");
+    }
+
+    #[test]
+    fn test_decode_output_plain() {
+        assert_eq!(decode_output(Some("hello world\n")), "hello world\n");
+        assert_eq!(decode_output(None), "");
+    }
+
+    #[test]
+    fn test_exec_status_integer_exited() {
+        let status: AgentExecStatus =
+            serde_json::from_value(serde_json::json!({ "exited": 1, "exitcode": 0 })).unwrap();
+        assert!(status.exited);
+    }
+}
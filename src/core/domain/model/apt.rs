@@ -0,0 +1,154 @@
+//! Domain models for the `/nodes/{node}/apt` endpoints.
+//!
+//! This module defines the installed package versions and pending updates reported by a
+//! node, and helpers for picking out installed kernel packages.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// An installed Proxmox-related package.
+///
+/// Returned by the `/api2/json/nodes/{node}/apt/versions` endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[non_exhaustive]
+pub struct PackageVersion {
+    /// Package name (e.g., "proxmox-kernel-6.8.12-5-pve-signed").
+    pub package: String,
+    /// Installed version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Previously installed version, if the package was upgraded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<String>,
+    /// Installation state (e.g., "Installed", "ConfigFiles").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_state: Option<String>,
+    /// Package title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Running kernel release; only set on the `proxmox-ve` entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running_kernel: Option<String>,
+    /// Running manager version; only set on the `proxmox-ve` entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manager_version: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl PackageVersion {
+    /// Returns `true` if the package is installed (not merely leaving config files behind).
+    #[must_use]
+    pub fn is_installed(&self) -> bool {
+        self.current_state.as_deref() == Some("Installed")
+    }
+
+    /// Returns the kernel release provided by this package (e.g., "6.8.12-5-pve"), if it is
+    /// an installed kernel image package.
+    ///
+    /// Both `proxmox-kernel-*` and the older `pve-kernel-*` names are recognized; meta
+    /// packages such as `proxmox-kernel-6.8` are not kernels themselves and yield `None`.
+    #[must_use]
+    pub fn kernel_release(&self) -> Option<&str> {
+        if !self.is_installed() {
+            return None;
+        }
+        let name = self
+            .package
+            .strip_prefix("proxmox-kernel-")
+            .or_else(|| self.package.strip_prefix("pve-kernel-"))?;
+        let release = name.strip_suffix("-signed").unwrap_or(name);
+        release.ends_with("-pve").then_some(release)
+    }
+}
+
+/// A package with an update available.
+///
+/// Returned by the `/api2/json/nodes/{node}/apt/update` endpoint, which lists the updates
+/// found by the node's last `apt update`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+#[non_exhaustive]
+pub struct AptPackage {
+    /// Package name (e.g., "pve-manager").
+    pub package: String,
+    /// Currently installed version, if the package is installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<String>,
+    /// Version the update installs.
+    pub version: String,
+    /// Short package description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Debian priority (e.g., "optional", "important").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Orders kernel releases such as "6.8.12-4-pve" by their numeric components.
+pub(crate) fn compare_kernel_releases(a: &str, b: &str) -> Ordering {
+    let key = |release: &str| -> Vec<u64> {
+        release
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    };
+    key(a).cmp(&key(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, state: &str) -> PackageVersion {
+        serde_json::from_value(serde_json::json!({
+            "Package": name,
+            "Version": "6.8.12-5",
+            "CurrentState": state
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_kernel_release() {
+        assert_eq!(
+            package("proxmox-kernel-6.8.12-5-pve-signed", "Installed").kernel_release(),
+            Some("6.8.12-5-pve")
+        );
+        assert_eq!(
+            package("pve-kernel-5.15.30-1-pve", "Installed").kernel_release(),
+            Some("5.15.30-1-pve")
+        );
+        assert_eq!(
+            package("proxmox-kernel-6.8", "Installed").kernel_release(),
+            None
+        );
+        assert_eq!(
+            package("proxmox-kernel-6.5.13-1-pve-signed", "ConfigFiles").kernel_release(),
+            None
+        );
+        assert_eq!(package("pve-manager", "Installed").kernel_release(), None);
+    }
+
+    #[test]
+    fn test_compare_kernel_releases() {
+        assert_eq!(
+            compare_kernel_releases("6.8.12-5-pve", "6.8.12-4-pve"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_kernel_releases("6.8.4-2-pve", "6.8.12-1-pve"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_kernel_releases("6.8.12-4-pve", "6.8.12-4-pve"),
+            Ordering::Equal
+        );
+    }
+}
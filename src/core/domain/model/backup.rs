@@ -0,0 +1,370 @@
+//! Domain models for backups.
+//!
+//! This module defines the backup archives listed under `/nodes/{node}/storage/{storage}/content`
+//! and the parameters used to create and restore them.
+
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+
+/// A backup archive stored on a storage, as returned with `content=backup`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct BackupItem {
+    /// Volume identifier (e.g., "local:backup/vzdump-qemu-100-2024_01_01-00_00_00.vma.zst").
+    pub volid: String,
+    /// Identifier of the guest this backup belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vmid: Option<u32>,
+    /// Creation time as seconds since UNIX epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ctime: Option<u64>,
+    /// Archive size in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Archive format (e.g., "vma.zst", "tar.zst", "pbs-vm").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Notes attached to the backup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Protection from pruning and removal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected: Option<u8>,
+    /// Result of the last verification (Proxmox Backup Server only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<BackupVerification>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A restore point, as returned by [`ProxmoxClient::list_backups`](crate::ProxmoxClient::list_backups).
+pub type BackupFile = BackupItem;
+
+/// Verification state of a backup.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BackupVerification {
+    /// Verification result (e.g., "ok", "failed").
+    pub state: String,
+    /// Task ID of the verification job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upid: Option<String>,
+}
+
+/// The kind of guest a backup archive contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GuestType {
+    /// A QEMU virtual machine (restored via `/nodes/{node}/qemu`).
+    Qemu,
+    /// An LXC container (restored via `/nodes/{node}/lxc`).
+    Lxc,
+}
+
+/// Parameters for restoring a guest from a backup archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoreParams {
+    /// The kind of guest contained in the archive.
+    pub guest_type: GuestType,
+    /// Identifier of the guest to create (or overwrite with `force`).
+    pub vmid: u32,
+    /// Backup volume identifier (e.g., "local:backup/vzdump-qemu-100-....vma.zst").
+    pub archive: String,
+    /// Target storage for the restored disks (defaults to the original storage).
+    pub storage: Option<String>,
+    /// Overwrite an existing guest with the same identifier.
+    pub force: bool,
+}
+
+impl RestoreParams {
+    /// Creates restore parameters that keep the original storage and refuse to overwrite.
+    #[must_use]
+    pub fn new(guest_type: GuestType, vmid: u32, archive: impl Into<String>) -> Self {
+        Self {
+            guest_type,
+            vmid,
+            archive: archive.into(),
+            storage: None,
+            force: false,
+        }
+    }
+
+    /// Sets the target storage.
+    #[must_use]
+    pub fn storage(mut self, storage: impl Into<String>) -> Self {
+        self.storage = Some(storage.into());
+        self
+    }
+
+    /// Allows overwriting an existing guest.
+    #[must_use]
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Checks that the archive is a backup volume identifier (`storage:backup/...`).
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` with a [`ValidationError::Field`] for `archive`
+    /// otherwise.
+    pub fn validate_archive(&self) -> ProxmoxResult<()> {
+        let valid = self
+            .archive
+            .split_once(':')
+            .is_some_and(|(storage, volume)| {
+                !storage.is_empty()
+                    && volume
+                        .strip_prefix("backup/")
+                        .is_some_and(|name| !name.is_empty())
+            });
+        if valid {
+            return Ok(());
+        }
+        Err(ProxmoxError::Validation {
+            source: ValidationError::Field {
+                field: "archive".to_string(),
+                message: format!(
+                    "Expected a backup volume ID like 'storage:backup/...', got '{}'",
+                    self.archive
+                ),
+            },
+            backtrace: Backtrace::capture(),
+        })
+    }
+
+    /// Builds the creation request body for the guest type.
+    ///
+    /// VMs take the archive as `archive`; containers take it as `ostemplate` with `restore=1`.
+    pub(crate) fn to_body(&self) -> RestoreBody<'_> {
+        let (archive, ostemplate, restore) = match self.guest_type {
+            GuestType::Qemu => (Some(self.archive.as_str()), None, None),
+            GuestType::Lxc => (None, Some(self.archive.as_str()), Some(1)),
+        };
+        RestoreBody {
+            vmid: self.vmid,
+            archive,
+            ostemplate,
+            restore,
+            storage: self.storage.as_deref(),
+            force: self.force.then_some(1),
+        }
+    }
+}
+
+/// The form body of a restore request, built by [`RestoreParams::to_body`].
+#[derive(Debug, Serialize)]
+pub(crate) struct RestoreBody<'a> {
+    vmid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archive: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ostemplate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restore: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    force: Option<u8>,
+}
+
+/// How `vzdump` keeps a running guest consistent while backing it up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupMode {
+    /// Back up a live snapshot without interrupting the guest.
+    Snapshot,
+    /// Suspend the guest for the duration of the backup.
+    Suspend,
+    /// Stop the guest, back it up and start it again.
+    Stop,
+}
+
+impl BackupMode {
+    /// Returns the value as sent to the API.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Snapshot => "snapshot",
+            Self::Suspend => "suspend",
+            Self::Stop => "stop",
+        }
+    }
+}
+
+/// Compression applied to a backup archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupCompression {
+    /// Zstandard (`.zst`).
+    Zstd,
+    /// Gzip (`.gz`).
+    Gzip,
+    /// LZO (`.lzo`).
+    Lzo,
+}
+
+impl BackupCompression {
+    /// Returns the value as sent to the API.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+            Self::Lzo => "lzo",
+        }
+    }
+}
+
+/// Parameters for backing up a guest with `vzdump`.
+///
+/// Unset values fall back to the node's `vzdump.conf` defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackupParams {
+    /// Storage the archive is written to.
+    pub storage: Option<String>,
+    /// Consistency mode.
+    pub mode: Option<BackupMode>,
+    /// Archive compression.
+    pub compress: Option<BackupCompression>,
+    /// Template for the backup notes (e.g., "{{guestname}} nightly").
+    pub notes_template: Option<String>,
+}
+
+impl BackupParams {
+    /// Creates parameters that use the node defaults for everything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the target storage.
+    #[must_use]
+    pub fn storage(mut self, storage: impl Into<String>) -> Self {
+        self.storage = Some(storage.into());
+        self
+    }
+
+    /// Sets the consistency mode.
+    #[must_use]
+    pub fn mode(mut self, mode: BackupMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets the archive compression.
+    #[must_use]
+    pub fn compress(mut self, compress: BackupCompression) -> Self {
+        self.compress = Some(compress);
+        self
+    }
+
+    /// Sets the notes template.
+    #[must_use]
+    pub fn notes_template(mut self, template: impl Into<String>) -> Self {
+        self.notes_template = Some(template.into());
+        self
+    }
+
+    /// Builds the `vzdump` request body for `vmid`.
+    pub(crate) fn to_body(&self, vmid: u32) -> serde_json::Value {
+        let mut body = serde_json::Map::new();
+        body.insert("vmid".to_string(), vmid.into());
+        if let Some(storage) = &self.storage {
+            body.insert("storage".to_string(), storage.clone().into());
+        }
+        if let Some(mode) = self.mode {
+            body.insert("mode".to_string(), mode.as_str().into());
+        }
+        if let Some(compress) = self.compress {
+            body.insert("compress".to_string(), compress.as_str().into());
+        }
+        if let Some(template) = &self.notes_template {
+            body.insert("notes-template".to_string(), template.clone().into());
+        }
+        serde_json::Value::Object(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_body() {
+        let params = BackupParams::new()
+            .storage("local")
+            .mode(BackupMode::Snapshot)
+            .compress(BackupCompression::Zstd)
+            .notes_template("{{guestname}}");
+        assert_eq!(
+            params.to_body(100),
+            serde_json::json!({
+                "vmid": 100,
+                "storage": "local",
+                "mode": "snapshot",
+                "compress": "zstd",
+                "notes-template": "{{guestname}}"
+            })
+        );
+        assert_eq!(
+            BackupParams::new().to_body(100),
+            serde_json::json!({"vmid": 100})
+        );
+    }
+
+    #[test]
+    fn test_validate_archive() {
+        let valid =
+            RestoreParams::new(GuestType::Qemu, 120, "local:backup/vzdump-qemu-100.vma.zst");
+        assert!(valid.validate_archive().is_ok());
+
+        for archive in [
+            "vzdump-qemu-100.vma.zst",
+            "/var/lib/vz/dump/vzdump-qemu-100.vma.zst",
+            ":backup/vzdump-qemu-100.vma.zst",
+            "local:iso/debian.iso",
+            "local:backup/",
+        ] {
+            let err = RestoreParams::new(GuestType::Qemu, 120, archive)
+                .validate_archive()
+                .unwrap_err();
+            assert!(
+                matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "archive"),
+                "{archive}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_restore_body_qemu() {
+        let params =
+            RestoreParams::new(GuestType::Qemu, 120, "local:backup/vzdump-qemu-100.vma.zst")
+                .storage("local-lvm")
+                .force(true);
+        assert_eq!(
+            serde_json::to_value(params.to_body()).unwrap(),
+            serde_json::json!({
+                "vmid": 120,
+                "archive": "local:backup/vzdump-qemu-100.vma.zst",
+                "storage": "local-lvm",
+                "force": 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_restore_body_lxc() {
+        let params = RestoreParams::new(GuestType::Lxc, 200, "local:backup/vzdump-lxc-200.tar.zst");
+        assert_eq!(
+            serde_json::to_value(params.to_body()).unwrap(),
+            serde_json::json!({
+                "vmid": 200,
+                "ostemplate": "local:backup/vzdump-lxc-200.tar.zst",
+                "restore": 1
+            })
+        );
+    }
+}
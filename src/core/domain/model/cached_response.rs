@@ -0,0 +1,52 @@
+//! A response paired with the time it was fetched from the server.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// A response body and when it was fetched.
+///
+/// With a [`CacheConfig`](crate::CacheConfig), `data` may have been served from the cache;
+/// `fetched_at` is then the time of the original request, not of the call that returned it.
+/// It serializes as seconds since the UNIX epoch.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct CachedResponse<T> {
+    /// The response body (the API's `data` field).
+    pub data: T,
+    /// When the server produced the response.
+    #[serde(with = "crate::core::domain::value_object::serde_helpers::system_time")]
+    pub fetched_at: SystemTime,
+}
+
+impl<T> CachedResponse<T> {
+    pub(crate) fn new(data: T, fetched_at: SystemTime) -> Self {
+        Self { data, fetched_at }
+    }
+
+    /// Returns how long ago the response was fetched (zero if the clock went backwards).
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_cached_response_serde_round_trip() {
+        let response = CachedResponse::new(
+            vec!["pve1".to_string()],
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        );
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "data": ["pve1"], "fetched_at": 1_700_000_000 })
+        );
+        let parsed: CachedResponse<Vec<String>> = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, response);
+    }
+}
@@ -5,6 +5,7 @@
 //! each identified by a `type` field. We model this as an enum to provide type safety.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A resource discovered in the Proxmox cluster.
 ///
@@ -12,6 +13,7 @@ use serde::{Deserialize, Serialize};
 /// like `node`, `id`, and `status`, but may have additional type‑specific fields.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum ClusterResource {
     /// A QEMU virtual machine.
     Qemu(QemuResource),
@@ -21,7 +23,123 @@ pub enum ClusterResource {
     Storage(StorageResource),
     /// A node in the cluster.
     Node(NodeResource),
-    // Other resource types (e.g., `pool`, `sdn`) can be added as needed.
+    /// A resource pool.
+    Pool(PoolResource),
+    /// An SDN zone or object.
+    Sdn(SdnResource),
+    /// A resource type this crate does not model yet.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ClusterResource {
+    /// Returns the node the resource belongs to, or `None` for cluster-wide resources such as
+    /// pools.
+    #[must_use]
+    pub fn node(&self) -> Option<&str> {
+        match self {
+            Self::Qemu(vm) => Some(&vm.common.node),
+            Self::Lxc(ct) => Some(&ct.common.node),
+            Self::Storage(storage) => Some(&storage.common.node),
+            Self::Node(node) => Some(&node.common.node),
+            Self::Sdn(sdn) => sdn.node.as_deref(),
+            Self::Pool(_) | Self::Unknown => None,
+        }
+    }
+
+    /// Returns `true` if the resource is of the given kind.
+    #[must_use]
+    pub fn is_kind(&self, kind: ResourceKind) -> bool {
+        matches!(
+            (kind, self),
+            (ResourceKind::Vm, Self::Qemu(_) | Self::Lxc(_))
+                | (ResourceKind::Qemu, Self::Qemu(_))
+                | (ResourceKind::Lxc, Self::Lxc(_))
+                | (ResourceKind::Storage, Self::Storage(_))
+                | (ResourceKind::Node, Self::Node(_))
+                | (ResourceKind::Pool, Self::Pool(_))
+                | (ResourceKind::Sdn, Self::Sdn(_))
+        )
+    }
+}
+
+/// A category of cluster resource, used with [`ResourceFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    /// Guests of either type (QEMU VMs and LXC containers).
+    Vm,
+    /// QEMU virtual machines only.
+    Qemu,
+    /// LXC containers only.
+    Lxc,
+    /// Storages.
+    Storage,
+    /// Cluster nodes.
+    Node,
+    /// Resource pools.
+    Pool,
+    /// SDN zones.
+    Sdn,
+}
+
+impl ResourceKind {
+    /// Returns the `type` value the server filters on, if it supports this kind.
+    ///
+    /// The server only distinguishes guests as a whole, so QEMU and LXC are narrowed further
+    /// after the response arrives.
+    pub(crate) fn server_type(self) -> Option<&'static str> {
+        match self {
+            Self::Vm | Self::Qemu | Self::Lxc => Some("vm"),
+            Self::Storage => Some("storage"),
+            Self::Node => Some("node"),
+            Self::Sdn => Some("sdn"),
+            Self::Pool => None,
+        }
+    }
+}
+
+/// Narrows the result of
+/// [`ProxmoxClient::cluster_resources_filtered`](crate::ProxmoxClient::cluster_resources_filtered).
+///
+/// Unset criteria match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceFilter {
+    /// Only resources of this kind.
+    pub kind: Option<ResourceKind>,
+    /// Only resources on this node (cluster-wide resources such as pools never match).
+    pub node: Option<String>,
+}
+
+impl ResourceFilter {
+    /// Creates a filter that matches every resource.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the result to one kind of resource.
+    #[must_use]
+    pub fn kind(mut self, kind: ResourceKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Restricts the result to resources on `node`.
+    #[must_use]
+    pub fn node(mut self, node: impl Into<String>) -> Self {
+        self.node = Some(node.into());
+        self
+    }
+
+    /// Returns `true` if `resource` satisfies every set criterion.
+    #[must_use]
+    pub fn matches(&self, resource: &ClusterResource) -> bool {
+        self.kind.is_none_or(|kind| resource.is_kind(kind))
+            && self
+                .node
+                .as_deref()
+                .is_none_or(|node| resource.node() == Some(node))
+    }
 }
 
 /// Common fields present in every resource.
@@ -39,58 +157,113 @@ pub struct CommonResourceFields {
     /// Resource status (e.g., `running`, `stopped`, `available`).
     pub status: String,
     /// Uptime in seconds (if applicable).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub uptime: Option<u64>,
 }
 
 /// A QEMU virtual machine resource.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct QemuResource {
     /// Common fields.
     #[serde(flatten)]
     pub common: CommonResourceFields,
     /// The VM identifier (unique per cluster).
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::string_or_number::deserialize"
+    )]
     pub vmid: u32,
     /// Number of allocated virtual CPUs.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxcpu: Option<u32>,
     /// Maximum memory in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxmem: Option<u64>,
     /// Disk usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub disk: Option<u64>,
     /// Network usage statistics (optional).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub netin: Option<u64>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub netout: Option<u64>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// An LXC container resource.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct LxcResource {
     /// Common fields.
     #[serde(flatten)]
     pub common: CommonResourceFields,
     /// The container identifier.
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::string_or_number::deserialize"
+    )]
     pub vmid: u32,
     /// Number of allocated virtual CPUs.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxcpu: Option<u32>,
     /// Maximum memory in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxmem: Option<u64>,
     /// Disk usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub disk: Option<u64>,
     /// Swap usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub swap: Option<u64>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// A storage resource (e.g., directory, ZFS, LVM).
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct StorageResource {
     /// Common fields.
     #[serde(flatten)]
@@ -101,30 +274,58 @@ pub struct StorageResource {
     #[serde(rename = "plugintype")]
     pub storage_type: String,
     /// Total capacity in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub total: Option<u64>,
     /// Used space in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub used: Option<u64>,
     /// Available space in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub avail: Option<u64>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// A node resource (the node itself).
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct NodeResource {
     /// Common fields.
     #[serde(flatten)]
     pub common: CommonResourceFields,
     /// Node CPU usage percentage.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub cpu: Option<f64>,
     /// Node memory usage percentage.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mem: Option<f64>,
     /// Node load average (1,5,15 minutes).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::loadavg::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub loadavg: Option<[f64; 3]>,
     /// Kernel version.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -132,4 +333,186 @@ pub struct NodeResource {
     /// Current power state (e.g., `online`, `offline`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub power: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A resource pool. Pools are cluster-wide, so there is no `node` or `status`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct PoolResource {
+    /// Unique resource identifier (e.g., `/pool/production`).
+    pub id: String,
+    /// Pool name.
+    pub pool: String,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// An SDN zone as seen by a node.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct SdnResource {
+    /// Unique resource identifier (e.g., `sdn/pve1/localnetwork`).
+    pub id: String,
+    /// SDN zone name.
+    pub sdn: String,
+    /// SDN status on the node (e.g., `ok`, `error`).
+    pub status: String,
+    /// The node reporting this SDN zone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_fields_preserved_alongside_common() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "qemu",
+            "id": "qemu/100",
+            "node": "pve1",
+            "status": "running",
+            "vmid": 100,
+            "hastate": "started"
+        }))
+        .unwrap();
+        let ClusterResource::Qemu(qemu) = resource else {
+            panic!("expected a qemu resource");
+        };
+        assert_eq!(qemu.common.node, "pve1");
+        assert_eq!(qemu.extra.len(), 1);
+        assert_eq!(qemu.extra["hastate"], "started");
+    }
+
+    #[test]
+    fn test_node_resource_with_string_loadavg() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "node",
+            "id": "node/pve1",
+            "node": "pve1",
+            "status": "online",
+            "loadavg": "0.50 0.30 0.10"
+        }))
+        .unwrap();
+        let ClusterResource::Node(node) = resource else {
+            panic!("expected a node resource");
+        };
+        assert_eq!(node.loadavg, Some([0.5, 0.3, 0.1]));
+    }
+
+    #[test]
+    fn test_string_encoded_numbers() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "qemu",
+            "id": "qemu/100",
+            "node": "pve1",
+            "status": "running",
+            "vmid": "100",
+            "uptime": "3600",
+            "maxcpu": "4",
+            "maxmem": "8589934592",
+            "disk": 0
+        }))
+        .unwrap();
+        let ClusterResource::Qemu(qemu) = resource else {
+            panic!("expected a qemu resource");
+        };
+        assert_eq!(qemu.vmid, 100);
+        assert_eq!(qemu.common.uptime, Some(3600));
+        assert_eq!(qemu.maxcpu, Some(4));
+        assert_eq!(qemu.maxmem, Some(8_589_934_592));
+        assert_eq!(qemu.disk, Some(0));
+
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "node",
+            "id": "node/pve1",
+            "node": "pve1",
+            "status": "online",
+            "cpu": "0.15",
+            "mem": "0.5"
+        }))
+        .unwrap();
+        let ClusterResource::Node(node) = resource else {
+            panic!("expected a node resource");
+        };
+        assert_eq!((node.cpu, node.mem), (Some(0.15), Some(0.5)));
+    }
+
+    #[test]
+    fn test_pool_resource() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "pool",
+            "id": "/pool/production",
+            "pool": "production"
+        }))
+        .unwrap();
+        let ClusterResource::Pool(pool) = resource else {
+            panic!("expected a pool resource");
+        };
+        assert_eq!(pool.pool, "production");
+        assert_eq!(pool.id, "/pool/production");
+    }
+
+    #[test]
+    fn test_sdn_resource() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "sdn",
+            "id": "sdn/pve1/localnetwork",
+            "sdn": "localnetwork",
+            "node": "pve1",
+            "status": "ok"
+        }))
+        .unwrap();
+        let ClusterResource::Sdn(sdn) = resource else {
+            panic!("expected an sdn resource");
+        };
+        assert_eq!(sdn.sdn, "localnetwork");
+        assert_eq!(sdn.status, "ok");
+        assert_eq!(sdn.node.as_deref(), Some("pve1"));
+    }
+
+    #[test]
+    fn test_resource_filter_matches() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "lxc",
+            "id": "lxc/200",
+            "node": "pve2",
+            "status": "running",
+            "vmid": 200
+        }))
+        .unwrap();
+        assert!(ResourceFilter::new().matches(&resource));
+        assert!(
+            ResourceFilter::new()
+                .kind(ResourceKind::Vm)
+                .matches(&resource)
+        );
+        assert!(
+            !ResourceFilter::new()
+                .kind(ResourceKind::Qemu)
+                .matches(&resource)
+        );
+        assert!(ResourceFilter::new().node("pve2").matches(&resource));
+        assert!(!ResourceFilter::new().node("pve1").matches(&resource));
+        assert_eq!(ResourceKind::Lxc.server_type(), Some("vm"));
+        assert_eq!(ResourceKind::Pool.server_type(), None);
+    }
+
+    #[test]
+    fn test_unknown_resource_type() {
+        let resource: ClusterResource = serde_json::from_value(serde_json::json!({
+            "type": "openvz",
+            "id": "openvz/1"
+        }))
+        .unwrap();
+        assert_eq!(resource, ClusterResource::Unknown);
+    }
 }
@@ -0,0 +1,64 @@
+//! Domain models for the `/cluster/status` endpoint.
+//!
+//! The response mixes one `cluster` entry (absent on standalone nodes) with one `node`
+//! entry per member, distinguished by the `type` field.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An entry of the `/cluster/status` response.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ClusterStatusEntry {
+    /// Cluster-wide information; only present when the node is part of a cluster.
+    Cluster(ClusterInfo),
+    /// A member node (on a standalone host, just the local node).
+    Node(ClusterNodeStatus),
+}
+
+/// Cluster-wide information from `/cluster/status`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ClusterInfo {
+    /// Cluster name.
+    pub name: String,
+    /// Number of member nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<u32>,
+    /// Whether the cluster has quorum (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quorate: Option<u8>,
+    /// Cluster configuration version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Membership status of a node from `/cluster/status`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ClusterNodeStatus {
+    /// Node name.
+    pub name: String,
+    /// Corosync node ID (`0` on standalone hosts).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nodeid: Option<u32>,
+    /// Node IP address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    /// Whether the node is online (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub online: Option<u8>,
+    /// Whether this is the node that answered the request (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<u8>,
+    /// Subscription level (empty if none).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
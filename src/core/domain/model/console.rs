@@ -0,0 +1,81 @@
+//! Domain models for remote console access via `vncproxy` and `spiceproxy`.
+//!
+//! Console tickets are short-lived and single-use, so they are never cached; request a
+//! fresh one for every connection attempt.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// VNC proxy ticket returned by `/nodes/{node}/qemu/{vmid}/vncproxy`.
+///
+/// Connect to the `vncwebsocket` endpoint with `port` and `ticket` shortly after retrieval.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct VncProxy {
+    /// One-time VNC ticket (used as the VNC password).
+    pub ticket: String,
+    /// Port the VNC proxy listens on.
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::string_or_number::deserialize"
+    )]
+    pub port: u16,
+    /// PEM certificate of the node, for TLS verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert: Option<String>,
+    /// User the ticket was issued for (e.g., "root@pam").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Task ID of the proxy worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upid: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// SPICE connection settings returned by `/nodes/{node}/qemu/{vmid}/spiceproxy`.
+///
+/// These map to the `[virt-viewer]` section of a `.vv` connection file.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct SpiceProxy {
+    /// Connection type (always "spice").
+    #[serde(rename = "type")]
+    pub connection_type: String,
+    /// SPICE host identifier (e.g., "pvespiceproxy:...").
+    pub host: String,
+    /// HTTP proxy to connect through (e.g., "http://pve1:3128").
+    pub proxy: String,
+    /// One-time SPICE password.
+    pub password: String,
+    /// TLS port of the SPICE server.
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::string_or_number::deserialize"
+    )]
+    pub tls_port: u16,
+    /// Expected certificate subject of the host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_subject: Option<String>,
+    /// CA certificate (PEM, with escaped newlines).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca: Option<String>,
+    /// Window title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Whether the viewer should delete the connection file after reading it (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_this_file: Option<u8>,
+    /// Key combination for secure attention (e.g., "Ctrl+Alt+Ins").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure_attention: Option<String>,
+    /// Key combination to release the cursor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_cursor: Option<String>,
+    /// Key combination to toggle full screen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toggle_fullscreen: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
@@ -0,0 +1,111 @@
+//! Domain models for high availability from `/cluster/ha/resources`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A guest managed by the HA stack.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct HaResource {
+    /// Service identifier (e.g., "vm:100", "ct:200").
+    pub sid: String,
+    /// Requested state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HaState>,
+    /// HA group restricting the nodes the resource may run on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Restart attempts on the same node after a start failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_restart: Option<u32>,
+    /// Relocation attempts to other nodes after a start failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_relocate: Option<u32>,
+    /// Free-form description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// State requested for an HA resource.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HaState {
+    /// Keep the guest running, recovering it on another node if needed.
+    Started,
+    /// Keep the guest stopped, but still recover it after a node failure.
+    Stopped,
+    /// Stop the guest and do not recover it.
+    Disabled,
+    /// Leave the guest alone; HA neither starts, stops nor recovers it.
+    Ignored,
+    /// A state this crate does not know, holding the raw value.
+    Unknown(String),
+}
+
+impl HaState {
+    /// Returns the value as used by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Started => "started",
+            Self::Stopped => "stopped",
+            Self::Disabled => "disabled",
+            Self::Ignored => "ignored",
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for HaState {
+    fn from(value: &str) -> Self {
+        match value {
+            // "enabled" is the legacy alias of "started".
+            "started" | "enabled" => Self::Started,
+            "stopped" => Self::Stopped,
+            "disabled" => Self::Disabled,
+            "ignored" => Self::Ignored,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for HaState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for HaState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for HaState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ha_state_accepts_legacy_alias() {
+        let states: Vec<HaState> =
+            serde_json::from_str(r#"["enabled", "ignored", "migrating"]"#).unwrap();
+        assert_eq!(
+            states,
+            vec![
+                HaState::Started,
+                HaState::Ignored,
+                HaState::Unknown("migrating".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! Domain models for LXC containers under `/nodes/{node}/lxc`.
+//!
+//! This module defines the parameters for creating a container and the container
+//! configuration with its updates.
+
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use crate::core::domain::model::vm::{
+    indexed_keys, serialize_comma_list, validate_vm_name, validate_vmid,
+};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Parameters for creating a new container.
+///
+/// Build them with [`CreateLxcParams::builder`]. The root password is redacted from the
+/// `Debug` output.
+#[derive(Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct CreateLxcParams {
+    /// Container identifier (required, must be unique in the cluster).
+    pub vmid: u32,
+    /// OS template volume (required, e.g., "local:vztmpl/debian-12-standard_12.7-1_amd64.tar.zst").
+    pub ostemplate: String,
+    /// Hostname (optional, defaults to "CT{vmid}").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Memory in MB (optional, default 512).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u32>,
+    /// Swap in MB (optional, default 512).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<u32>,
+    /// Number of cores (optional, default unlimited).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cores: Option<u32>,
+    /// Root filesystem as `storage:size_in_gb` (optional, e.g., "local-lvm:8").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rootfs: Option<String>,
+    /// First network interface (optional, e.g., "name=eth0,bridge=vmbr0,ip=dhcp").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net0: Option<String>,
+    /// Root password (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Public SSH keys for root, one per line (optional).
+    #[serde(rename = "ssh-public-keys", skip_serializing_if = "Option::is_none")]
+    pub ssh_public_keys: Option<String>,
+    /// Run as an unprivileged container (optional, default 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unprivileged: Option<u8>,
+    /// Start after creation (optional, default 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u8>,
+}
+
+impl CreateLxcParams {
+    /// Returns a builder for the given container identifier and OS template.
+    #[must_use]
+    pub fn builder(vmid: u32, ostemplate: impl Into<String>) -> CreateLxcParamsBuilder {
+        CreateLxcParamsBuilder::new(vmid, ostemplate)
+    }
+
+    /// Checks the parameters before they are sent.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError::Validation`] if `vmid` is outside 100–999999999, `ostemplate`
+    /// is not a `storage:volume` ID, or `hostname` is not a valid DNS name.
+    pub fn validate(&self) -> ProxmoxResult<()> {
+        validate_vmid(self.vmid)
+            .and_then(|_| validate_ostemplate(&self.ostemplate))
+            .and_then(|_| self.hostname.as_deref().map_or(Ok(()), validate_vm_name))
+            .map_err(|e| ProxmoxError::Validation {
+                source: e,
+                backtrace: Backtrace::capture(),
+            })
+    }
+}
+
+impl fmt::Debug for CreateLxcParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateLxcParams")
+            .field("vmid", &self.vmid)
+            .field("ostemplate", &self.ostemplate)
+            .field("hostname", &self.hostname)
+            .field("memory", &self.memory)
+            .field("swap", &self.swap)
+            .field("cores", &self.cores)
+            .field("rootfs", &self.rootfs)
+            .field("net0", &self.net0)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("ssh_public_keys", &self.ssh_public_keys)
+            .field("unprivileged", &self.unprivileged)
+            .field("start", &self.start)
+            .finish()
+    }
+}
+
+/// Checks that an OS template is a volume ID such as "local:vztmpl/debian-12.tar.zst".
+fn validate_ostemplate(ostemplate: &str) -> Result<(), ValidationError> {
+    match ostemplate.split_once(':') {
+        Some((storage, volume)) if !storage.is_empty() && !volume.is_empty() => Ok(()),
+        _ => Err(ValidationError::Field {
+            field: "ostemplate".to_string(),
+            message: "OS template must be a volume ID like 'local:vztmpl/<file>'".to_string(),
+        }),
+    }
+}
+
+/// Builder for [`CreateLxcParams`].
+///
+/// Unset fields are left to the server defaults.
+#[derive(Debug, Clone)]
+pub struct CreateLxcParamsBuilder {
+    params: CreateLxcParams,
+}
+
+impl CreateLxcParamsBuilder {
+    /// Creates a builder with only the identifier and OS template set.
+    #[must_use]
+    pub fn new(vmid: u32, ostemplate: impl Into<String>) -> Self {
+        Self {
+            params: CreateLxcParams {
+                vmid,
+                ostemplate: ostemplate.into(),
+                hostname: None,
+                memory: None,
+                swap: None,
+                cores: None,
+                rootfs: None,
+                net0: None,
+                password: None,
+                ssh_public_keys: None,
+                unprivileged: None,
+                start: None,
+            },
+        }
+    }
+
+    /// Sets the hostname.
+    #[must_use]
+    pub fn hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.params.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Sets the memory in MB.
+    #[must_use]
+    pub fn memory(mut self, mb: u32) -> Self {
+        self.params.memory = Some(mb);
+        self
+    }
+
+    /// Sets the swap in MB.
+    #[must_use]
+    pub fn swap(mut self, mb: u32) -> Self {
+        self.params.swap = Some(mb);
+        self
+    }
+
+    /// Sets the number of cores.
+    #[must_use]
+    pub fn cores(mut self, cores: u32) -> Self {
+        self.params.cores = Some(cores);
+        self
+    }
+
+    /// Allocates a root filesystem of `size_gb` GiB on `storage`.
+    #[must_use]
+    pub fn rootfs(mut self, storage: &str, size_gb: u32) -> Self {
+        self.params.rootfs = Some(format!("{}:{}", storage, size_gb));
+        self
+    }
+
+    /// Sets the first network interface (e.g., "name=eth0,bridge=vmbr0,ip=dhcp").
+    #[must_use]
+    pub fn net0(mut self, net: impl Into<String>) -> Self {
+        self.params.net0 = Some(net.into());
+        self
+    }
+
+    /// Sets the root password.
+    #[must_use]
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.params.password = Some(password.into());
+        self
+    }
+
+    /// Sets the public SSH keys installed for root, one per line.
+    #[must_use]
+    pub fn ssh_public_keys(mut self, keys: impl Into<String>) -> Self {
+        self.params.ssh_public_keys = Some(keys.into());
+        self
+    }
+
+    /// Runs the container unprivileged, mapping its root user to an unprivileged host user.
+    #[must_use]
+    pub fn unprivileged(mut self, unprivileged: bool) -> Self {
+        self.params.unprivileged = Some(u8::from(unprivileged));
+        self
+    }
+
+    /// Starts the container once it is created.
+    #[must_use]
+    pub fn start(mut self, start: bool) -> Self {
+        self.params.start = Some(u8::from(start));
+        self
+    }
+
+    /// Validates the parameters and returns the finished [`CreateLxcParams`].
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError::Validation`] for the cases listed on
+    /// [`CreateLxcParams::validate`].
+    pub fn build(self) -> ProxmoxResult<CreateLxcParams> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+/// Container configuration from `/nodes/{node}/lxc/{vmid}/config`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct LxcConfig {
+    /// Hostname.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Description (if set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Memory in MB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u32>,
+    /// Swap in MB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap: Option<u32>,
+    /// Number of cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cores: Option<u32>,
+    /// Root filesystem (e.g., "local-lvm:vm-200-disk-0,size=8G").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rootfs: Option<String>,
+    /// OS type detected from the template (e.g., "debian", "alpine").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ostype: Option<String>,
+    /// Architecture (e.g., "amd64").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    /// Enabled container features (e.g., "nesting=1,keyctl=1").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<String>,
+    /// Tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+    /// Whether the container is unprivileged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unprivileged: Option<u8>,
+    /// Start at boot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub onboot: Option<u8>,
+    /// Proxmox configuration digest (for updates).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// Lock held by a running or interrupted operation (e.g., "backup", "mounted").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<String>,
+    /// Fields returned by the server that this crate does not model yet, including the
+    /// indexed `net0..N` and `mp0..N` keys.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl LxcConfig {
+    /// Returns the network interfaces (`net0..N`), keyed by index.
+    #[must_use]
+    pub fn networks(&self) -> BTreeMap<u32, String> {
+        indexed_keys(&self.extra, "net")
+    }
+
+    /// Returns the mount points (`mp0..N`), keyed by index.
+    #[must_use]
+    pub fn mount_points(&self) -> BTreeMap<u32, String> {
+        indexed_keys(&self.extra, "mp")
+    }
+}
+
+/// Changes to a container configuration, sent to `/nodes/{node}/lxc/{vmid}/config`.
+///
+/// Works like [`UpdateVmConfig`](crate::UpdateVmConfig): only fields that are set are sent,
+/// keys without a typed field (such as `net1` or `mp0`) go through [`set`](Self::set), and
+/// keys listed in [`delete`](Self::delete) are removed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UpdateLxcConfig {
+    /// Hostname.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Memory in MB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u32>,
+    /// Swap in MB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap: Option<u32>,
+    /// Number of cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cores: Option<u32>,
+    /// Container features (e.g., "nesting=1").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<String>,
+    /// Tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+    /// Description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Start at boot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub onboot: Option<u8>,
+    /// Digest of the configuration this update is based on (see [`LxcConfig::digest`]).
+    ///
+    /// If set, the server rejects the update with `ProxmoxError::Conflict` when the
+    /// configuration was modified since it was read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// Configuration keys to remove (sent as `delete=net1,mp0`).
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_comma_list"
+    )]
+    pub delete: Vec<String>,
+    /// Other configuration keys to set, such as indexed devices (`net1`, `mp0`).
+    #[serde(flatten)]
+    pub options: BTreeMap<String, String>,
+}
+
+impl UpdateLxcConfig {
+    /// Creates an empty update.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a configuration key that has no typed field (e.g., `net1`).
+    #[must_use]
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    /// Only applies the update if the configuration still has `digest`.
+    #[must_use]
+    pub fn if_digest(mut self, digest: impl Into<String>) -> Self {
+        self.digest = Some(digest.into());
+        self
+    }
+
+    /// Removes a configuration key (e.g., `net1` or `mp0`).
+    #[must_use]
+    pub fn delete(mut self, key: impl Into<String>) -> Self {
+        self.delete.push(key.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_lxc_params_validation() {
+        let template = "local:vztmpl/debian-12-standard_12.7-1_amd64.tar.zst";
+        assert!(
+            CreateLxcParams::builder(200, template)
+                .hostname("web-ct")
+                .build()
+                .is_ok()
+        );
+        assert!(CreateLxcParams::builder(99, template).build().is_err());
+        assert!(
+            CreateLxcParams::builder(200, template)
+                .hostname("bad_host")
+                .build()
+                .is_err()
+        );
+        let err = CreateLxcParams::builder(200, "debian-12.tar.zst")
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. }
+                if field == "ostemplate"
+        ));
+    }
+
+    #[test]
+    fn test_update_lxc_config_serialization() {
+        let update = UpdateLxcConfig {
+            memory: Some(2048),
+            ..UpdateLxcConfig::new()
+        }
+        .set("net1", "name=eth1,bridge=vmbr1")
+        .delete("mp0")
+        .delete("mp1");
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({
+                "memory": 2048,
+                "net1": "name=eth1,bridge=vmbr1",
+                "delete": "mp0,mp1"
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_lxc_params_debug_redacts_password() {
+        let params = CreateLxcParams::builder(200, "local:vztmpl/alpine.tar.xz")
+            .password("hunter2-secret")
+            .build()
+            .unwrap();
+        let debug = format!("{:?}", params);
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains("hunter2-secret"));
+    }
+}
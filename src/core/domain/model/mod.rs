@@ -1,7 +1,23 @@
+pub(crate) mod agent;
+pub(crate) mod apt;
+pub(crate) mod backup;
+pub(crate) mod cached_response;
 pub(crate) mod cluster_resource;
+pub(crate) mod cluster_status;
+pub(crate) mod console;
+pub(crate) mod ha;
+pub(crate) mod lxc;
+pub(crate) mod network;
 pub(crate) mod node_dns;
 pub(crate) mod node_list_item;
 pub(crate) mod node_status;
+pub(crate) mod node_time;
 pub(crate) mod proxmox_auth;
 pub(crate) mod proxmox_connection;
+pub(crate) mod service;
+pub(crate) mod session;
+pub(crate) mod status;
+pub(crate) mod storage;
+pub(crate) mod task;
+pub(crate) mod version;
 pub(crate) mod vm;
@@ -0,0 +1,50 @@
+//! Domain model for node network interfaces from the `/nodes/{node}/network` endpoint.
+//!
+//! This module defines the interface configuration reported for a node, including
+//! bridges, bonds and physical NICs.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A network interface configured on a Proxmox node.
+///
+/// Returned by the `/api2/json/nodes/{node}/network` endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct NetworkInterface {
+    /// Interface name (e.g., "vmbr0", "bond0", "eno1").
+    pub iface: String,
+    /// Interface type (e.g., "bridge", "bond", "eth", "vlan").
+    #[serde(rename = "type")]
+    pub interface_type: String,
+    /// IPv4 configuration method (e.g., "static", "manual", "dhcp").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// IPv4 address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// IPv4 netmask.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netmask: Option<String>,
+    /// Default IPv4 gateway.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<String>,
+    /// Ports attached to a bridge (space-separated).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bridge_ports: Option<String>,
+    /// Bond member interfaces (space-separated).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slaves: Option<String>,
+    /// Bonding mode (e.g., "active-backup", "802.3ad").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bond_mode: Option<String>,
+    /// Whether the interface is brought up at boot (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autostart: Option<u8>,
+    /// Whether the interface is currently active (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<u8>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
@@ -3,12 +3,14 @@
 //! This module defines the DNS settings for a specific node.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// DNS configuration for a Proxmox node.
 ///
 /// Returned by the `/api2/json/nodes/{node}/dns` endpoint.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
 pub struct NodeDnsConfig {
     /// DNS search domain (e.g., "example.com").
     pub domain: String,
@@ -17,4 +19,7 @@ pub struct NodeDnsConfig {
     /// DNS options (if any).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub options: Option<Vec<String>>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
@@ -3,43 +3,90 @@
 //! This module defines the structure of a node as returned by the Proxmox API
 //! when listing all nodes in the cluster.
 
+use crate::core::domain::model::status::NodeOnlineStatus;
+use crate::core::domain::value_object::Fingerprint;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A node in the Proxmox cluster.
 ///
 /// This struct represents a node as returned by the `/api2/json/nodes` endpoint.
 /// It contains identifying information, status, and resource usage statistics.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct NodeListItem {
     /// The node name (e.g., "pve1").
     pub node: String,
-    /// Current node status (e.g., "online", "offline", "unknown").
-    pub status: String,
+    /// Current membership state.
+    pub status: NodeOnlineStatus,
     /// CPU usage percentage (0.0 to 1.0).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub cpu: Option<f64>,
     /// Maximum CPU count (number of cores/threads).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxcpu: Option<u32>,
     /// Memory usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mem: Option<u64>,
     /// Maximum memory in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxmem: Option<u64>,
     /// Disk usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub disk: Option<u64>,
     /// Maximum disk space in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxdisk: Option<u64>,
     /// System uptime in seconds.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub uptime: Option<u64>,
     /// Unique node identifier (e.g., "node/pve1").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
-    /// SSL fingerprint (if available).
+    /// SSL fingerprint (if available), as sent by the server; see
+    /// [`fingerprint`](Self::fingerprint) for a comparable form.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ssl_fingerprint: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl NodeListItem {
+    /// Returns the SSL fingerprint as a [`Fingerprint`], or `None` if it is missing or
+    /// malformed.
+    #[must_use]
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        self.ssl_fingerprint
+            .as_deref()
+            .and_then(|value| Fingerprint::new(value).ok())
+    }
 }
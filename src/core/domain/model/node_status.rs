@@ -2,13 +2,18 @@
 //!
 //! This module defines the detailed status information for a specific node.
 
+use crate::core::domain::error::ProxmoxResult;
+use crate::core::domain::model::version::ProxmoxVersion;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 
 /// Detailed status information for a Proxmox node.
 ///
 /// Returned by the `/api2/json/nodes/{node}/status` endpoint.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
 pub struct NodeStatus {
     /// CPU usage percentage (0.0 to 1.0).
     pub cpu: f64,
@@ -17,37 +22,305 @@ pub struct NodeStatus {
     /// Swap usage in bytes.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub swap: Option<MemoryInfo>,
+    /// Root filesystem usage in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rootfs: Option<MemoryInfo>,
+    /// Kernel same-page merging statistics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ksm: Option<KsmInfo>,
     /// System uptime in seconds.
     pub uptime: u64,
     /// Kernel version string.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub kversion: Option<String>,
     /// Load average over 1, 5, and 15 minutes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::loadavg::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub loadavg: Option<[f64; 3]>,
-    /// Current power state (e.g., "running").
-    pub current_kernel: Option<String>,
+    /// The running kernel.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_kernel: Option<KernelInfo>,
     /// Node description (if set).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// IO delay percentage (0.0 to 1.0) - time spent waiting for I/O operations.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub wait: Option<f64>,
-    /// CPU information model string.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub cpuinfo: Option<String>,
-    /// Hardware model.
+    /// CPU model and topology.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpuinfo: Option<CpuInfo>,
+    /// Proxmox VE manager version (e.g., "pve-manager/8.1.4/ec5affc9").
+    #[serde(default, alias = "pveversion", skip_serializing_if = "Option::is_none")]
     pub pve_version: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl NodeStatus {
+    /// Parses [`pve_version`](Self::pve_version) into a comparable [`ProxmoxVersion`].
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the version string is malformed.
+    pub fn parsed_version(&self) -> ProxmoxResult<Option<ProxmoxVersion>> {
+        self.pve_version
+            .as_deref()
+            .map(ProxmoxVersion::parse)
+            .transpose()
+    }
+
+    /// Returns the release of the running kernel (e.g., "6.8.12-4-pve").
+    ///
+    /// Uses [`current_kernel`](Self::current_kernel) and falls back to the second word of
+    /// [`kversion`](Self::kversion) (`"Linux 6.8.12-4-pve #1 SMP ..."`) on older servers.
+    #[must_use]
+    pub fn running_kernel_release(&self) -> Option<&str> {
+        self.current_kernel
+            .as_ref()
+            .map(|kernel| kernel.release.as_str())
+            .or_else(|| self.kversion.as_deref()?.split_whitespace().nth(1))
+    }
+
+    /// Returns the 1-minute load average divided by the number of CPUs.
+    ///
+    /// Pass `maxcpu` from the matching [`NodeListItem`](crate::NodeListItem), or
+    /// [`CpuInfo::cpus`] where the server reports it. Values above `1.0` mean more runnable
+    /// tasks than CPUs. Returns `None` if the load average is unavailable or `maxcpu` is zero.
+    #[must_use]
+    pub fn load_per_core(&self, maxcpu: u32) -> Option<f64> {
+        if maxcpu == 0 {
+            return None;
+        }
+        self.loadavg.map(|load| load[0] / f64::from(maxcpu))
+    }
 }
 
-/// Memory usage information.
+/// Memory or disk usage information.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct MemoryInfo {
-    /// Total memory in bytes.
+    /// Total size in bytes.
     pub total: u64,
-    /// Used memory in bytes.
+    /// Used bytes.
     pub used: u64,
-    /// Free memory in bytes.
+    /// Free bytes.
     pub free: u64,
+    /// Bytes available to unprivileged users (reported for `rootfs`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avail: Option<u64>,
+}
+
+/// Kernel same-page merging statistics.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct KsmInfo {
+    /// Memory shared by KSM in bytes.
+    pub shared: u64,
+}
+
+/// CPU model and topology as reported in `cpuinfo`.
+///
+/// Older servers report only the model string; it is accepted in place of the object.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "CpuInfoRepr")]
+#[non_exhaustive]
+pub struct CpuInfo {
+    /// CPU model (e.g., "Intel(R) Xeon(R) CPU E5-2680 v4 @ 2.40GHz").
+    pub model: String,
+    /// Number of logical CPUs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<u32>,
+    /// Number of physical cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cores: Option<u32>,
+    /// Number of CPU sockets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sockets: Option<u32>,
+    /// Clock speed in MHz.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mhz: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CpuInfoRepr {
+    Model(String),
+    Full {
+        model: String,
+        #[serde(default)]
+        cpus: Option<u32>,
+        #[serde(default)]
+        cores: Option<u32>,
+        #[serde(default)]
+        sockets: Option<u32>,
+        // Reported as a string ("2399.998") by Proxmox.
+        #[serde(default)]
+        mhz: Option<serde_json::Value>,
+    },
+}
+
+impl From<CpuInfoRepr> for CpuInfo {
+    fn from(repr: CpuInfoRepr) -> Self {
+        match repr {
+            CpuInfoRepr::Model(model) => Self {
+                model,
+                cpus: None,
+                cores: None,
+                sockets: None,
+                mhz: None,
+            },
+            CpuInfoRepr::Full {
+                model,
+                cpus,
+                cores,
+                sockets,
+                mhz,
+            } => Self {
+                model,
+                cpus,
+                cores,
+                sockets,
+                mhz: mhz.and_then(|value| match value {
+                    serde_json::Value::String(s) => s.trim().parse().ok(),
+                    other => other.as_f64(),
+                }),
+            },
+        }
+    }
+}
+
+/// The running kernel as reported in `current-kernel`.
+///
+/// Older servers report only the release string; it is accepted in place of the object.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "KernelInfoRepr")]
+#[non_exhaustive]
+pub struct KernelInfo {
+    /// Kernel release (e.g., "6.8.12-4-pve").
+    pub release: String,
+    /// Operating system name (e.g., "Linux").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sysname: Option<String>,
+    /// Kernel build version (e.g., "#1 SMP PREEMPT_DYNAMIC PMX 6.8.12-4 (2024-11-06T15:04Z)").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Hardware architecture (e.g., "x86_64").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KernelInfoRepr {
+    Release(String),
+    Full {
+        release: String,
+        #[serde(default)]
+        sysname: Option<String>,
+        #[serde(default)]
+        version: Option<String>,
+        #[serde(default)]
+        machine: Option<String>,
+    },
+}
+
+impl From<KernelInfoRepr> for KernelInfo {
+    fn from(repr: KernelInfoRepr) -> Self {
+        match repr {
+            KernelInfoRepr::Release(release) => Self {
+                release,
+                sysname: None,
+                version: None,
+                machine: None,
+            },
+            KernelInfoRepr::Full {
+                release,
+                sysname,
+                version,
+                machine,
+            } => Self {
+                release,
+                sysname,
+                version,
+                machine,
+            },
+        }
+    }
+}
+
+/// A power action on a whole node, sent as the `command` of `POST /nodes/{node}/status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodePowerAction {
+    /// Reboot the node.
+    Reboot,
+    /// Shut the node down.
+    Shutdown,
+}
+
+impl NodePowerAction {
+    /// Returns the command name used by the API for this action.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodePowerAction::Reboot => "reboot",
+            NodePowerAction::Shutdown => "shutdown",
+        }
+    }
+}
+
+impl fmt::Display for NodePowerAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_with_load(loadavg: Option<[f64; 3]>) -> NodeStatus {
+        serde_json::from_value(serde_json::json!({
+            "cpu": 0.5,
+            "memory": { "total": 8, "used": 4, "free": 4 },
+            "uptime": 60,
+            "loadavg": loadavg,
+            "current-kernel": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_load_per_core() {
+        let status = status_with_load(Some([8.0, 6.0, 4.0]));
+        assert_eq!(status.load_per_core(4), Some(2.0));
+        assert_eq!(status.load_per_core(0), None);
+        assert_eq!(status_with_load(None).load_per_core(4), None);
+    }
+
+    #[test]
+    fn test_running_kernel_release() {
+        let status: NodeStatus = serde_json::from_value(serde_json::json!({
+            "cpu": 0.5,
+            "memory": { "total": 8, "used": 4, "free": 4 },
+            "uptime": 60,
+            "current-kernel": {
+                "release": "6.8.12-4-pve",
+                "sysname": "Linux",
+                "machine": "x86_64"
+            }
+        }))
+        .unwrap();
+        assert_eq!(status.running_kernel_release(), Some("6.8.12-4-pve"));
+
+        let legacy: NodeStatus = serde_json::from_value(serde_json::json!({
+            "cpu": 0.5,
+            "memory": { "total": 8, "used": 4, "free": 4 },
+            "uptime": 60,
+            "kversion": "Linux 5.15.30-1-pve #1 SMP PVE 5.15.30-1"
+        }))
+        .unwrap();
+        assert_eq!(legacy.running_kernel_release(), Some("5.15.30-1-pve"));
+    }
 }
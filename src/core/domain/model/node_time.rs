@@ -0,0 +1,29 @@
+//! Domain model for node time settings from the `/nodes/{node}/time` endpoint.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Clock and timezone of a Proxmox node.
+///
+/// Returned by the `/api2/json/nodes/{node}/time` endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct NodeTime {
+    /// Current time as seconds since the Unix epoch (UTC).
+    pub time: i64,
+    /// Current local time as seconds since the Unix epoch, shifted by the timezone offset.
+    pub localtime: i64,
+    /// Configured timezone (e.g., "Europe/Madrid").
+    pub timezone: String,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl NodeTime {
+    /// Returns the offset of the node's local time from UTC, in seconds.
+    #[must_use]
+    pub fn utc_offset_secs(&self) -> i64 {
+        self.localtime - self.time
+    }
+}
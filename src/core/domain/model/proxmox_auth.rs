@@ -20,4 +20,9 @@ impl ProxmoxAuth {
     pub fn csrf_token(&self) -> Option<&ProxmoxCSRFToken> {
         self.csrf_token.as_ref()
     }
+
+    /// Returns `true` if there is no CSRF token, so only read requests can be made.
+    pub fn is_read_only(&self) -> bool {
+        self.csrf_token.is_none()
+    }
 }
@@ -1,17 +1,21 @@
 use crate::core::domain::value_object::{
     ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm, ProxmoxUrl, ProxmoxUsername,
 };
+use std::hash::{Hash, Hasher};
 
 /// Connection details for a Proxmox server.
 ///
 /// This struct holds all validated configuration needed to connect.
 /// Fields are private; access is via getters.
+///
+/// Equality and hashing compare the connection's identity (everything but the password), so
+/// connections to the same server as the same user can key a map.
 #[derive(Debug, Clone)]
 pub struct ProxmoxConnection {
     host: ProxmoxHost,
     port: ProxmoxPort,
     username: ProxmoxUsername,
-    password: ProxmoxPassword,
+    password: Option<ProxmoxPassword>,
     realm: ProxmoxRealm,
     secure: bool,
     accept_invalid_certs: bool,
@@ -20,8 +24,55 @@ pub struct ProxmoxConnection {
 
 #[allow(clippy::too_many_arguments)]
 impl ProxmoxConnection {
-    /// Creates a new connection instance (internal use only, values are already validated).
-    pub(crate) fn new(
+    /// Assembles a connection from already validated parts.
+    ///
+    /// Pass the result to [`ProxmoxClient::from_connection`](crate::ProxmoxClient::from_connection)
+    /// to build a client without [`ProxmoxClientBuilder`](crate::ProxmoxClientBuilder). `url` is
+    /// used as the API base as given, so it should agree with `host`, `port` and `secure`.
+    ///
+    /// # Example
+    /// ```
+    /// use leeca_proxmox::{
+    ///     ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
+    ///     ProxmoxResult, ProxmoxUrl, ProxmoxUsername,
+    /// };
+    ///
+    /// # fn main() -> ProxmoxResult<()> {
+    /// let connection = ProxmoxConnection::new(
+    ///     ProxmoxHost::new("pve.example.com")?,
+    ///     ProxmoxPort::new(8006)?,
+    ///     ProxmoxUsername::new("automation")?,
+    ///     ProxmoxPassword::new("correct-horse-battery")?,
+    ///     ProxmoxRealm::new("pve")?,
+    ///     true,
+    ///     false,
+    ///     ProxmoxUrl::new("https://pve.example.com:8006/")?,
+    /// );
+    /// assert_eq!(connection.host().as_str(), "pve.example.com");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Parts that are already trusted can skip validation with `new_unchecked`:
+    /// ```
+    /// use leeca_proxmox::{
+    ///     ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
+    ///     ProxmoxUrl, ProxmoxUsername,
+    /// };
+    ///
+    /// let connection = ProxmoxConnection::new(
+    ///     ProxmoxHost::new_unchecked("pve.example.com".to_string()),
+    ///     ProxmoxPort::new_unchecked(8006),
+    ///     ProxmoxUsername::new_unchecked("automation".to_string()),
+    ///     ProxmoxPassword::new_unchecked("secret".to_string()),
+    ///     ProxmoxRealm::new_unchecked("pve".to_string()),
+    ///     true,
+    ///     false,
+    ///     ProxmoxUrl::new_unchecked("https://pve.example.com:8006/".to_string()),
+    /// );
+    /// assert_eq!(connection.port().get(), 8006);
+    /// ```
+    pub fn new(
         host: ProxmoxHost,
         port: ProxmoxPort,
         username: ProxmoxUsername,
@@ -35,7 +86,31 @@ impl ProxmoxConnection {
             host,
             port,
             username,
-            password,
+            password: Some(password),
+            realm,
+            secure,
+            accept_invalid_certs,
+            url,
+        }
+    }
+
+    /// Creates a connection without a password, for clients restored from a saved session.
+    ///
+    /// Such a connection cannot log in again once the restored ticket is rejected.
+    pub(crate) fn new_without_password(
+        host: ProxmoxHost,
+        port: ProxmoxPort,
+        username: ProxmoxUsername,
+        realm: ProxmoxRealm,
+        secure: bool,
+        accept_invalid_certs: bool,
+        url: ProxmoxUrl,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            username,
+            password: None,
             realm,
             secure,
             accept_invalid_certs,
@@ -44,13 +119,11 @@ impl ProxmoxConnection {
     }
 
     /// Returns the host.
-    #[allow(dead_code)] // Part of public API, not yet used internally.
     pub fn host(&self) -> &ProxmoxHost {
         &self.host
     }
 
     /// Returns the port.
-    #[allow(dead_code)] // Part of public API, not yet used internally.
     pub fn port(&self) -> &ProxmoxPort {
         &self.port
     }
@@ -60,9 +133,9 @@ impl ProxmoxConnection {
         &self.username
     }
 
-    /// Returns the password.
-    pub fn password(&self) -> &ProxmoxPassword {
-        &self.password
+    /// Returns the password, or `None` if the client was restored from a session without credentials.
+    pub fn password(&self) -> Option<&ProxmoxPassword> {
+        self.password.as_ref()
     }
 
     /// Returns the realm.
@@ -71,7 +144,6 @@ impl ProxmoxConnection {
     }
 
     /// Returns whether HTTPS is used.
-    #[allow(dead_code)] // Part of public API, not yet used internally.
     pub fn is_secure(&self) -> bool {
         self.secure
     }
@@ -86,3 +158,79 @@ impl ProxmoxConnection {
         &self.url
     }
 }
+
+impl ProxmoxConnection {
+    fn identity(&self) -> impl PartialEq + Hash + '_ {
+        (
+            &self.host,
+            &self.port,
+            &self.username,
+            &self.realm,
+            self.secure,
+            self.accept_invalid_certs,
+            &self.url,
+        )
+    }
+}
+
+impl PartialEq for ProxmoxConnection {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for ProxmoxConnection {}
+
+impl Hash for ProxmoxConnection {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_does_not_leak_password() {
+        let connection = ProxmoxConnection::new(
+            ProxmoxHost::new_unchecked("pve.example.com".to_string()),
+            ProxmoxPort::new_unchecked(8006),
+            ProxmoxUsername::new_unchecked("root".to_string()),
+            ProxmoxPassword::new_unchecked("hunter2-secret".to_string()),
+            ProxmoxRealm::new_unchecked("pam".to_string()),
+            true,
+            false,
+            ProxmoxUrl::new_unchecked("https://pve.example.com:8006/".to_string()),
+        );
+        let debug = format!("{:?}", connection);
+        assert!(debug.contains("pve.example.com"));
+        assert!(!debug.contains("hunter2-secret"));
+    }
+
+    #[test]
+    fn test_equal_connections_dedup_in_hash_set() {
+        let connection = |host: &str, password: &str| {
+            ProxmoxConnection::new(
+                ProxmoxHost::new_unchecked(host.to_string()),
+                ProxmoxPort::new_unchecked(8006),
+                ProxmoxUsername::new_unchecked("root".to_string()),
+                ProxmoxPassword::new_unchecked(password.to_string()),
+                ProxmoxRealm::new_unchecked("pam".to_string()),
+                true,
+                false,
+                ProxmoxUrl::new_unchecked(format!("https://{}:8006/", host)),
+            )
+        };
+
+        let connections: std::collections::HashSet<_> = [
+            connection("pve1.example.com", "first-password"),
+            connection("pve1.example.com", "rotated-password"),
+            connection("pve2.example.com", "first-password"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(connections.len(), 2);
+        assert!(connections.contains(&connection("pve2.example.com", "other")));
+    }
+}
@@ -0,0 +1,65 @@
+//! Domain model for node system services from the `/nodes/{node}/services` endpoint.
+//!
+//! This module defines the services managed by a node (e.g., `pveproxy`, `pvestatd`)
+//! and the actions that can be issued against them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A system service running on a Proxmox node.
+///
+/// Returned by the `/api2/json/nodes/{node}/services` endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct NodeService {
+    /// Service identifier used in API paths (e.g., "pveproxy").
+    pub service: String,
+    /// Service unit name.
+    pub name: String,
+    /// Human-readable description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Service state (e.g., "running", "stopped").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Systemd active state (e.g., "active", "inactive", "failed").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_state: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// An action that can be performed on a node service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceAction {
+    /// Start the service.
+    Start,
+    /// Stop the service.
+    Stop,
+    /// Restart the service.
+    Restart,
+    /// Reload the service configuration.
+    Reload,
+}
+
+impl ServiceAction {
+    /// Returns the path segment used by the API for this action.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "start",
+            ServiceAction::Stop => "stop",
+            ServiceAction::Restart => "restart",
+            ServiceAction::Reload => "reload",
+        }
+    }
+}
+
+impl fmt::Display for ServiceAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,185 @@
+//! On-disk format of a persisted session.
+//!
+//! A session file holds the authentication state together with the connection it was
+//! issued for, so a ticket from one server is not replayed against another. Files written
+//! before the connection metadata was added contain only the authentication fields and
+//! still load.
+//!
+//! Sessions can also be stored encrypted: the JSON is sealed with ChaCha20-Poly1305 under
+//! a key derived from a passphrase with Argon2id, and wrapped in a small envelope.
+
+use crate::ValidationConfig;
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use crate::core::domain::model::{
+    proxmox_auth::ProxmoxAuth, proxmox_connection::ProxmoxConnection,
+};
+use argon2::Argon2;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chacha20poly1305::{
+    AeadCore, ChaCha20Poly1305, KeyInit, Nonce,
+    aead::{Aead, OsRng, rand_core::RngCore},
+};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use zeroize::Zeroizing;
+
+/// Envelope format version written by [`SessionFile::encrypt`].
+const ENCRYPTED_SESSION_VERSION: u8 = 1;
+/// Length of the random Argon2 salt in bytes.
+const SALT_LEN: usize = 16;
+
+/// A saved session: connection metadata plus the ticket and CSRF token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SessionFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) secure: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) realm: Option<String>,
+    #[serde(flatten)]
+    pub(crate) auth: ProxmoxAuth,
+}
+
+impl SessionFile {
+    /// Captures the current session of a connection.
+    pub(crate) fn new(connection: &ProxmoxConnection, auth: ProxmoxAuth) -> Self {
+        Self {
+            host: Some(connection.host().as_str().to_string()),
+            port: Some(connection.port().get()),
+            secure: Some(connection.is_secure()),
+            username: Some(connection.username().as_str().to_string()),
+            realm: Some(connection.realm().as_str().to_string()),
+            auth,
+        }
+    }
+
+    /// Rejects tokens that are already expired according to the validation config.
+    pub(crate) fn check_expiry(&self, config: &ValidationConfig) -> ProxmoxResult<()> {
+        if let Some(csrf) = self.auth.csrf_token()
+            && csrf.is_expired(config.csrf_lifetime)
+        {
+            return Err(ProxmoxError::Session(
+                "Loaded CSRF token is expired".to_string(),
+            ));
+        }
+        if self.auth.ticket().is_expired(config.ticket_lifetime) {
+            return Err(ProxmoxError::Session(
+                "Loaded ticket is expired".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rejects a session saved for a different host or port than the one configured.
+    ///
+    /// Unconfigured values (`None`) and sessions without metadata are accepted.
+    pub(crate) fn check_target(&self, host: Option<&str>, port: Option<u16>) -> ProxmoxResult<()> {
+        let mismatch = |field: &str, saved: String, configured: String| ProxmoxError::Validation {
+            source: ValidationError::Field {
+                field: field.to_string(),
+                message: format!(
+                    "Session was saved for {} '{}' but the client is configured for '{}'",
+                    field, saved, configured
+                ),
+            },
+            backtrace: Backtrace::capture(),
+        };
+        if let (Some(saved), Some(configured)) = (&self.host, host)
+            && !saved.eq_ignore_ascii_case(configured)
+        {
+            return Err(mismatch("host", saved.clone(), configured.to_string()));
+        }
+        if let (Some(saved), Some(configured)) = (self.port, port)
+            && saved != configured
+        {
+            return Err(mismatch("port", saved.to_string(), configured.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// On-disk envelope of an encrypted session.
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedSessionFile {
+    version: u8,
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl SessionFile {
+    /// Serializes and encrypts the session with a key derived from `passphrase`.
+    pub(crate) fn encrypt(&self, passphrase: &str) -> ProxmoxResult<String> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(self)?);
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = session_cipher(passphrase, &salt)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| ProxmoxError::Session("Failed to encrypt session".to_string()))?;
+        let envelope = EncryptedSessionFile {
+            version: ENCRYPTED_SESSION_VERSION,
+            kdf: "argon2id".to_string(),
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        };
+        Ok(serde_json::to_string(&envelope)?)
+    }
+
+    /// Decrypts a session written by [`encrypt`](Self::encrypt).
+    ///
+    /// Returns `ProxmoxError::SessionDecryption` if the passphrase is wrong or the
+    /// ciphertext was modified, and `ProxmoxError::Session` if the envelope is malformed.
+    pub(crate) fn decrypt(data: &str, passphrase: &str) -> ProxmoxResult<Self> {
+        let envelope: EncryptedSessionFile = serde_json::from_str(data)?;
+        if envelope.version != ENCRYPTED_SESSION_VERSION || envelope.kdf != "argon2id" {
+            return Err(ProxmoxError::Session(format!(
+                "Unsupported encrypted session format (version {}, kdf '{}')",
+                envelope.version, envelope.kdf
+            )));
+        }
+        let decode = |field: &str, value: &str| {
+            STANDARD.decode(value).map_err(|e| {
+                ProxmoxError::Session(format!("Invalid {} in encrypted session: {}", field, e))
+            })
+        };
+        let salt = decode("salt", &envelope.salt)?;
+        let nonce = decode("nonce", &envelope.nonce)?;
+        let ciphertext = decode("ciphertext", &envelope.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(ProxmoxError::Session(
+                "Invalid nonce length in encrypted session".to_string(),
+            ));
+        }
+
+        let cipher = session_cipher(passphrase, &salt)?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| {
+                    ProxmoxError::SessionDecryption(
+                        "wrong passphrase or corrupted session file".to_string(),
+                    )
+                })?,
+        );
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+/// Derives the session cipher from a passphrase and salt with Argon2id.
+fn session_cipher(passphrase: &str, salt: &[u8]) -> ProxmoxResult<ChaCha20Poly1305> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| ProxmoxError::Session(format!("Failed to derive session key: {}", e)))?;
+    ChaCha20Poly1305::new_from_slice(key.as_ref())
+        .map_err(|e| ProxmoxError::Session(format!("Invalid session key: {}", e)))
+}
@@ -0,0 +1,150 @@
+//! Typed status values for guests and nodes.
+//!
+//! Proxmox reports states as plain strings. These enums cover the documented values and keep
+//! anything else in an `Unknown` variant, so newer servers never break deserialization.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Power state of a virtual machine or container.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PowerStatus {
+    /// The guest is running.
+    Running,
+    /// The guest is stopped.
+    Stopped,
+    /// The guest is paused.
+    Paused,
+    /// The guest is suspended (hibernated to disk or RAM).
+    Suspended,
+    /// A state this crate does not know, holding the raw value.
+    Unknown(String),
+}
+
+impl PowerStatus {
+    /// Returns the value as reported by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Running => "running",
+            Self::Stopped => "stopped",
+            Self::Paused => "paused",
+            Self::Suspended => "suspended",
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for PowerStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "running" => Self::Running,
+            "stopped" => Self::Stopped,
+            "paused" => Self::Paused,
+            "suspended" => Self::Suspended,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for PowerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for PowerStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PowerStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+/// Membership state of a cluster node.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NodeOnlineStatus {
+    /// The node is reachable.
+    Online,
+    /// The node is not reachable.
+    Offline,
+    /// A state this crate does not know (including Proxmox's own "unknown"), holding the raw
+    /// value.
+    Unknown(String),
+}
+
+impl NodeOnlineStatus {
+    /// Returns the value as reported by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Online => "online",
+            Self::Offline => "offline",
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for NodeOnlineStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "online" => Self::Online,
+            "offline" => Self::Offline,
+            other => Self::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for NodeOnlineStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for NodeOnlineStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeOnlineStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_status_parses_known_and_unknown_values() {
+        let parsed: Vec<PowerStatus> =
+            serde_json::from_str(r#"["running", "stopped", "prelaunch"]"#).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                PowerStatus::Running,
+                PowerStatus::Stopped,
+                PowerStatus::Unknown("prelaunch".to_string()),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&PowerStatus::Unknown("prelaunch".to_string())).unwrap(),
+            r#""prelaunch""#
+        );
+    }
+
+    #[test]
+    fn test_node_online_status_round_trips() {
+        let status: NodeOnlineStatus = serde_json::from_str(r#""offline""#).unwrap();
+        assert_eq!(status, NodeOnlineStatus::Offline);
+        assert_eq!(serde_json::to_string(&status).unwrap(), r#""offline""#);
+    }
+}
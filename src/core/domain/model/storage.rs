@@ -0,0 +1,225 @@
+//! Domain models for storage volumes from `/nodes/{node}/storage/{storage}/content/{volume}`.
+//!
+//! This module defines the volume metadata and the [`Checksum`] used to verify its integrity.
+
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Metadata of a single storage volume.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct StorageVolume {
+    /// Path of the volume on the node (or the storage URI for remote storages).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Volume size in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Used space in bytes (may differ from `size` for sparse or thin volumes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used: Option<u64>,
+    /// Volume format (e.g., "raw", "qcow2", "iso").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Notes attached to the volume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Protection from pruning and removal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected: Option<u8>,
+    /// Hex digest recorded for the volume, if the storage reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Algorithm of [`checksum`](Self::checksum) (e.g., "sha256").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum_algorithm: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl StorageVolume {
+    /// Parses the checksum reported for the volume, if any.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the algorithm or digest is malformed.
+    pub fn checksum(&self) -> ProxmoxResult<Option<Checksum>> {
+        match (&self.checksum_algorithm, &self.checksum) {
+            (Some(algorithm), Some(digest)) => Ok(Some(Checksum::new(algorithm.parse()?, digest)?)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Kinds of content that can be uploaded to a storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UploadContentType {
+    /// An installation ISO image.
+    Iso,
+    /// A container template archive.
+    Vztmpl,
+}
+
+impl UploadContentType {
+    /// Returns the name used by the API ("iso" or "vztmpl").
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Iso => "iso",
+            Self::Vztmpl => "vztmpl",
+        }
+    }
+}
+
+impl fmt::Display for UploadContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Hash algorithms accepted by Proxmox for content checksums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChecksumAlgorithm {
+    /// MD5 (128-bit).
+    Md5,
+    /// SHA-1 (160-bit).
+    Sha1,
+    /// SHA-224.
+    Sha224,
+    /// SHA-256.
+    Sha256,
+    /// SHA-384.
+    Sha384,
+    /// SHA-512.
+    Sha512,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the name used by the API (e.g., "sha256").
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Md5 => "md5",
+            Self::Sha1 => "sha1",
+            Self::Sha224 => "sha224",
+            Self::Sha256 => "sha256",
+            Self::Sha384 => "sha384",
+            Self::Sha512 => "sha512",
+        }
+    }
+
+    /// Returns the length of a digest in hex characters.
+    #[must_use]
+    pub fn hex_len(&self) -> usize {
+        match self {
+            Self::Md5 => 32,
+            Self::Sha1 => 40,
+            Self::Sha224 => 56,
+            Self::Sha256 => 64,
+            Self::Sha384 => 96,
+            Self::Sha512 => 128,
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ChecksumAlgorithm {
+    type Err = ProxmoxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "md5" => Ok(Self::Md5),
+            "sha1" => Ok(Self::Sha1),
+            "sha224" => Ok(Self::Sha224),
+            "sha256" => Ok(Self::Sha256),
+            "sha384" => Ok(Self::Sha384),
+            "sha512" => Ok(Self::Sha512),
+            _ => Err(ProxmoxError::Validation {
+                source: ValidationError::Format(format!("Unknown checksum algorithm: '{}'", s)),
+                backtrace: Backtrace::capture(),
+            }),
+        }
+    }
+}
+
+/// A content checksum: an algorithm and a hex digest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Checksum {
+    algorithm: ChecksumAlgorithm,
+    digest: String,
+}
+
+impl Checksum {
+    /// Creates a checksum, normalizing the digest to lowercase.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` with a [`ValidationError::Format`] if the digest is
+    /// not hex or does not have the length produced by `algorithm`.
+    pub fn new(algorithm: ChecksumAlgorithm, digest: &str) -> ProxmoxResult<Self> {
+        let digest = digest.trim().to_ascii_lowercase();
+        if digest.len() != algorithm.hex_len() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ProxmoxError::Validation {
+                source: ValidationError::Format(format!(
+                    "Expected a {}-character hex {} digest, got '{}'",
+                    algorithm.hex_len(),
+                    algorithm,
+                    digest
+                )),
+                backtrace: Backtrace::capture(),
+            });
+        }
+        Ok(Self { algorithm, digest })
+    }
+
+    /// Returns the hash algorithm.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the lowercase hex digest.
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_validation() {
+        let checksum =
+            Checksum::new(ChecksumAlgorithm::Md5, "D41D8CD98F00B204E9800998ECF8427E").unwrap();
+        assert_eq!(checksum.digest(), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(checksum.to_string(), "md5:d41d8cd98f00b204e9800998ecf8427e");
+
+        assert!(
+            Checksum::new(
+                ChecksumAlgorithm::Sha256,
+                "d41d8cd98f00b204e9800998ecf8427e"
+            )
+            .is_err()
+        );
+        assert!(Checksum::new(ChecksumAlgorithm::Md5, "z41d8cd98f00b204e9800998ecf8427e").is_err());
+        assert!("crc32".parse::<ChecksumAlgorithm>().is_err());
+        assert_eq!(
+            "SHA256".parse::<ChecksumAlgorithm>().unwrap(),
+            ChecksumAlgorithm::Sha256
+        );
+    }
+}
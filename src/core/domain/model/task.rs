@@ -0,0 +1,427 @@
+//! Domain model for Proxmox tasks identified by a UPID.
+//!
+//! Long-running operations (start, migrate, backup, ...) return a UPID such as
+//! `UPID:pve1:000A1B2C:0012D687:65A1B2C3:qmstart:100:root@pam:`; this module parses it
+//! and models the status reported by `/nodes/{node}/tasks/{upid}/status`.
+
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use serde::{Deserialize, Deserializer, Serialize, de};
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed Proxmox task identifier (UPID).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskId {
+    raw: String,
+    node: String,
+    pid: u32,
+    start_time: u64,
+    task_type: String,
+    id: String,
+    user: String,
+}
+
+impl TaskId {
+    /// Parses a UPID string.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` with a [`ValidationError::Format`] if the UPID is malformed.
+    pub fn parse(upid: &str) -> ProxmoxResult<Self> {
+        let invalid = || ProxmoxError::Validation {
+            source: ValidationError::Format(format!("Invalid UPID: '{}'", upid)),
+            backtrace: Backtrace::capture(),
+        };
+        let rest = upid.strip_prefix("UPID:").ok_or_else(invalid)?;
+        let fields: Vec<&str> = rest.split(':').collect();
+        if fields.len() < 7 || fields[0].is_empty() || fields[4].is_empty() {
+            return Err(invalid());
+        }
+        let pid = u32::from_str_radix(fields[1], 16).map_err(|_| invalid())?;
+        u64::from_str_radix(fields[2], 16).map_err(|_| invalid())?;
+        let start_time = u64::from_str_radix(fields[3], 16).map_err(|_| invalid())?;
+        Ok(Self {
+            raw: upid.to_string(),
+            node: fields[0].to_string(),
+            pid,
+            start_time,
+            task_type: fields[4].to_string(),
+            id: fields[5].to_string(),
+            user: fields[6].to_string(),
+        })
+    }
+
+    /// Returns the full UPID string.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the node that runs the task.
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+
+    /// Returns the worker process ID.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Returns the task start time as a Unix timestamp.
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+
+    /// Returns the task type (e.g., "qmstart", "vzdump").
+    pub fn task_type(&self) -> &str {
+        &self.task_type
+    }
+
+    /// Returns the task object identifier (e.g., the VMID); may be empty.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the user that started the task (e.g., "root@pam").
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+}
+
+impl FromStr for TaskId {
+    type Err = ProxmoxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// The result of an operation that may either complete immediately or start a task.
+///
+/// Proxmox answers synchronous operations (e.g., most configuration changes) with
+/// `{"data": null}` and asynchronous ones with a UPID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskOutcome {
+    /// The operation completed before the response was sent.
+    Sync,
+    /// The operation runs in the background as the given task.
+    Async(TaskId),
+}
+
+impl TaskOutcome {
+    /// Returns the task ID if the operation started a task.
+    #[must_use]
+    pub fn task_id(&self) -> Option<&TaskId> {
+        match self {
+            Self::Sync => None,
+            Self::Async(task) => Some(task),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskOutcome {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            None => Ok(Self::Sync),
+            Some(upid) => TaskId::parse(&upid)
+                .map(Self::Async)
+                .map_err(|_| de::Error::custom(format!("invalid UPID '{}'", upid))),
+        }
+    }
+}
+
+/// The status of a task as returned by `/nodes/{node}/tasks/{upid}/status`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct TaskStatus {
+    /// The task ID.
+    pub upid: String,
+    /// The node running the task.
+    pub node: String,
+    /// "running" or "stopped".
+    pub status: String,
+    /// Exit status once stopped: "OK", "WARNINGS: n", or an error message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exitstatus: Option<String>,
+    /// Task type (e.g., "qmstart", "qmmove").
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub task_type: Option<String>,
+    /// Task object identifier (e.g., the VMID).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The user that started the task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Start time as a Unix timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starttime: Option<u64>,
+    /// Worker process ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// Latest progress percentage found in the task log (0.0 to 100.0).
+    ///
+    /// Not part of the status response; only filled in by
+    /// [`ProxmoxClient::wait_for_task_with_progress`](crate::ProxmoxClient::wait_for_task_with_progress).
+    #[serde(skip)]
+    pub progress: Option<f64>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl TaskStatus {
+    /// Returns `true` while the task has not finished.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.status == "running"
+    }
+
+    /// Returns `true` if the task finished with "OK" or only warnings.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        !self.is_running()
+            && self
+                .exitstatus
+                .as_deref()
+                .is_some_and(|s| s == "OK" || s.starts_with("WARNINGS"))
+    }
+}
+
+/// A finished or running task as listed by `/nodes/{node}/tasks`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct TaskListItem {
+    /// The task ID.
+    pub upid: String,
+    /// The node that ran the task.
+    pub node: String,
+    /// Task type (e.g., "qmstart", "vzdump").
+    #[serde(rename = "type")]
+    pub task_type: String,
+    /// Task object identifier (e.g., the VMID); may be empty.
+    #[serde(default)]
+    pub id: String,
+    /// The user that started the task.
+    pub user: String,
+    /// Start time as a Unix timestamp.
+    pub starttime: u64,
+    /// End time as a Unix timestamp, once the task has stopped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endtime: Option<u64>,
+    /// Exit status once stopped: "OK", "WARNINGS: n", or an error message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Filters and page size for [`ProxmoxClient::node_tasks_stream`](crate::ProxmoxClient::node_tasks_stream).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskListOptions {
+    /// Number of tasks fetched per request.
+    pub limit: u64,
+    /// Only list tasks for this guest.
+    pub vmid: Option<u32>,
+    /// Only list tasks of this type (e.g., "vzdump").
+    pub typefilter: Option<String>,
+    /// Only list tasks started by this user (e.g., "root@pam").
+    pub userfilter: Option<String>,
+    /// Only list failed tasks.
+    pub errors: bool,
+}
+
+impl Default for TaskListOptions {
+    fn default() -> Self {
+        Self {
+            limit: 50,
+            vmid: None,
+            typefilter: None,
+            userfilter: None,
+            errors: false,
+        }
+    }
+}
+
+impl TaskListOptions {
+    /// Creates options listing every task, 50 per request.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of tasks fetched per request.
+    #[must_use]
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Lists only tasks for `vmid`.
+    #[must_use]
+    pub fn vmid(mut self, vmid: u32) -> Self {
+        self.vmid = Some(vmid);
+        self
+    }
+
+    /// Lists only tasks of the given type.
+    #[must_use]
+    pub fn typefilter(mut self, task_type: impl Into<String>) -> Self {
+        self.typefilter = Some(task_type.into());
+        self
+    }
+
+    /// Lists only tasks started by the given user.
+    #[must_use]
+    pub fn userfilter(mut self, user: impl Into<String>) -> Self {
+        self.userfilter = Some(user.into());
+        self
+    }
+
+    /// Lists only failed tasks.
+    #[must_use]
+    pub fn errors(mut self, errors: bool) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Builds the percent-encoded query string for the page starting at `start`.
+    pub(crate) fn to_query(&self, start: u64) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("start", &start.to_string())
+            .append_pair("limit", &self.limit.to_string());
+        if let Some(vmid) = self.vmid {
+            query.append_pair("vmid", &vmid.to_string());
+        }
+        if let Some(task_type) = &self.typefilter {
+            query.append_pair("typefilter", task_type);
+        }
+        if let Some(user) = &self.userfilter {
+            query.append_pair("userfilter", user);
+        }
+        if self.errors {
+            query.append_pair("errors", "1");
+        }
+        query.finish()
+    }
+}
+
+/// A line of `/nodes/{node}/tasks/{upid}/log`.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct TaskLogLine {
+    /// Line number, starting at 1.
+    pub(crate) n: u64,
+    /// Line text.
+    pub(crate) t: String,
+}
+
+/// Extracts the progress percentage from a task log line.
+///
+/// Matches the first number directly followed by `%`, as printed by vzdump
+/// ("INFO:  12% (1.2 GiB of 10.0 GiB) in 5s"), disk moves and downloads
+/// ("transferred 1.0 GiB of 32.0 GiB (3.12%)").
+pub(crate) fn parse_progress(line: &str) -> Option<f64> {
+    line.match_indices('%').find_map(|(end, _)| {
+        let digits = line[..end]
+            .rfind(|c: char| !c.is_ascii_digit() && c != '.')
+            .map_or(0, |i| i + 1);
+        line[digits..end]
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| (0.0..=100.0).contains(percent))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_upid() {
+        let task =
+            TaskId::parse("UPID:pve1:000A1B2C:0012D687:65A1B2C3:qmstart:100:root@pam:").unwrap();
+        assert_eq!(task.node(), "pve1");
+        assert_eq!(task.pid(), 0x000A_1B2C);
+        assert_eq!(task.start_time(), 0x65A1_B2C3);
+        assert_eq!(task.task_type(), "qmstart");
+        assert_eq!(task.id(), "100");
+        assert_eq!(task.user(), "root@pam");
+        assert_eq!(
+            task.to_string(),
+            "UPID:pve1:000A1B2C:0012D687:65A1B2C3:qmstart:100:root@pam:"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_upid() {
+        for upid in [
+            "",
+            "pve1:000A1B2C:0012D687:65A1B2C3:qmstart:100:root@pam:",
+            "UPID:pve1:zzzz:0012D687:65A1B2C3:qmstart:100:root@pam:",
+            "UPID:pve1:000A1B2C",
+        ] {
+            let err = TaskId::parse(upid).unwrap_err();
+            assert!(matches!(
+                err,
+                ProxmoxError::Validation {
+                    source: ValidationError::Format(_),
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_task_status_outcome() {
+        let status = |state: &str, exit: Option<&str>| -> TaskStatus {
+            serde_json::from_value(serde_json::json!({
+                "upid": "UPID:pve1:000A1B2C:0012D687:65A1B2C3:qmmove:100:root@pam:",
+                "node": "pve1",
+                "status": state,
+                "exitstatus": exit,
+                "type": "qmmove"
+            }))
+            .unwrap()
+        };
+        assert!(status("running", None).is_running());
+        assert!(!status("running", None).is_success());
+        assert!(status("stopped", Some("OK")).is_success());
+        assert!(status("stopped", Some("WARNINGS: 2")).is_success());
+        assert!(!status("stopped", Some("storage migration failed")).is_success());
+    }
+
+    #[test]
+    fn test_task_list_query_encodes_filters() {
+        let options = TaskListOptions::new()
+            .limit(10)
+            .typefilter("vzdump")
+            .userfilter("ops team+1@pve&x#y")
+            .errors(true);
+        assert_eq!(
+            options.to_query(20),
+            "start=20&limit=10&typefilter=vzdump&userfilter=ops+team%2B1%40pve%26x%23y&errors=1"
+        );
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            parse_progress("INFO:  12% (1.2 GiB of 10.0 GiB) in 5s, read: 200 MiB/s"),
+            Some(12.0)
+        );
+        assert_eq!(
+            parse_progress("transferred 1.0 GiB of 32.0 GiB (3.12%)"),
+            Some(3.12)
+        );
+        assert_eq!(parse_progress("INFO: starting new backup job"), None);
+        assert_eq!(parse_progress("usage: 100%%"), Some(100.0));
+        assert_eq!(parse_progress("ratio %"), None);
+    }
+}
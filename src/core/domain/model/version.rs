@@ -0,0 +1,160 @@
+//! Domain model for Proxmox VE version information from the `/version` endpoint.
+//!
+//! This module defines the raw version response and a parsed, comparable
+//! [`ProxmoxVersion`] for branching on server capabilities.
+
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Version information returned by the `/api2/json/version` endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct VersionInfo {
+    /// Version string (e.g., "8.1.4").
+    pub version: String,
+    /// Release series (e.g., "8.1").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release: Option<String>,
+    /// Repository commit identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repoid: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl VersionInfo {
+    /// Parses [`version`](Self::version) into a comparable [`ProxmoxVersion`].
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the version string is malformed.
+    pub fn parsed(&self) -> ProxmoxResult<ProxmoxVersion> {
+        ProxmoxVersion::parse(&self.version)
+    }
+}
+
+/// A parsed Proxmox VE version, ordered by `(major, minor, patch)`.
+///
+/// Compares directly against `(major, minor)` tuples:
+///
+/// ```
+/// use leeca_proxmox::ProxmoxVersion;
+///
+/// let version = ProxmoxVersion::parse("8.1.4").unwrap();
+/// assert!(version >= (8, 0));
+/// assert!(version < (8, 2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProxmoxVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch level or package release (`1` in "7.3-1").
+    pub patch: u32,
+}
+
+impl ProxmoxVersion {
+    /// Parses a version such as "7.3-1", "8.1.4" or "pve-manager/8.1.4/ec5affc9".
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if no numeric version can be found.
+    pub fn parse(raw: &str) -> ProxmoxResult<Self> {
+        let invalid = || ProxmoxError::Validation {
+            source: ValidationError::Format(format!("Invalid Proxmox version: '{}'", raw)),
+            backtrace: Backtrace::capture(),
+        };
+        let candidate = raw
+            .split('/')
+            .find(|part| part.starts_with(|c: char| c.is_ascii_digit()))
+            .ok_or_else(invalid)?;
+
+        let mut numbers = candidate
+            .split(['.', '-'])
+            .map(|part| part.parse::<u32>().map_err(|_| invalid()));
+        let major = numbers.next().ok_or_else(invalid)??;
+        let minor = numbers.next().transpose()?.unwrap_or(0);
+        let patch = numbers.next().transpose()?.unwrap_or(0);
+        Ok(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for ProxmoxVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl PartialEq<(u32, u32)> for ProxmoxVersion {
+    fn eq(&self, other: &(u32, u32)) -> bool {
+        (self.major, self.minor) == *other
+    }
+}
+
+impl PartialOrd<(u32, u32)> for ProxmoxVersion {
+    fn partial_cmp(&self, other: &(u32, u32)) -> Option<Ordering> {
+        Some((self.major, self.minor).cmp(other))
+    }
+}
+
+impl PartialEq<(u32, u32, u32)> for ProxmoxVersion {
+    fn eq(&self, other: &(u32, u32, u32)) -> bool {
+        (self.major, self.minor, self.patch) == *other
+    }
+}
+
+impl PartialOrd<(u32, u32, u32)> for ProxmoxVersion {
+    fn partial_cmp(&self, other: &(u32, u32, u32)) -> Option<Ordering> {
+        Some((self.major, self.minor, self.patch).cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_package_release() {
+        let version = ProxmoxVersion::parse("7.3-1").unwrap();
+        assert_eq!(version, (7, 3, 1));
+        assert!(version < (8, 0));
+        assert!(version >= (7, 3));
+    }
+
+    #[test]
+    fn test_parse_semver() {
+        let version = ProxmoxVersion::parse("8.1.4").unwrap();
+        assert_eq!(
+            version,
+            ProxmoxVersion {
+                major: 8,
+                minor: 1,
+                patch: 4
+            }
+        );
+        assert!(version >= (8, 0));
+        assert!(version > ProxmoxVersion::parse("8.1.3").unwrap());
+        assert_eq!(version.to_string(), "8.1.4");
+    }
+
+    #[test]
+    fn test_parse_pveversion_string() {
+        let version = ProxmoxVersion::parse("pve-manager/8.1.4/ec5affc9e41f1d79").unwrap();
+        assert_eq!(version, (8, 1, 4));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(ProxmoxVersion::parse("").is_err());
+        assert!(ProxmoxVersion::parse("pve-manager/unknown").is_err());
+        assert!(ProxmoxVersion::parse("8.x").is_err());
+    }
+}
@@ -2,38 +2,80 @@
 //!
 //! This module defines the structures used when interacting with VMs via the Proxmox API.
 
-use serde::{Deserialize, Serialize};
+use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+use crate::core::domain::model::status::PowerStatus;
+use crate::core::domain::model::task::TaskOutcome;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, ser::SerializeMap};
+use std::backtrace::Backtrace;
+use std::collections::{BTreeMap, HashMap};
+
+/// Smallest VM identifier accepted by Proxmox.
+pub const MIN_VMID: u32 = 100;
+/// Largest VM identifier accepted by Proxmox.
+pub const MAX_VMID: u32 = 999_999_999;
 
 /// A virtual machine as returned by the `/nodes/{node}/qemu` endpoint.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct VmListItem {
     /// The VM identifier (unique per cluster).
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::string_or_number::deserialize"
+    )]
     pub vmid: u32,
     /// Human-readable name.
     pub name: String,
-    /// Current status (e.g., "running", "stopped").
-    pub status: String,
+    /// Current power state.
+    pub status: PowerStatus,
     /// CPU usage percentage (0.0 to 1.0).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub cpu: Option<f64>,
     /// Maximum CPU count (number of cores/threads).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxcpu: Option<u32>,
     /// Memory usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mem: Option<u64>,
     /// Maximum memory in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxmem: Option<u64>,
     /// Disk usage in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub disk: Option<u64>,
     /// Maximum disk space in bytes.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maxdisk: Option<u64>,
     /// Uptime in seconds (if running).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_string_or_number::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub uptime: Option<u64>,
     /// The Proxmox node where this VM resides.
     pub node: String,
@@ -42,13 +84,32 @@ pub struct VmListItem {
     /// Additional tags (if any).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<String>,
+    /// Whether the VM is a template (reported as `template: 1`; absent for regular VMs).
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_int_bool::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub template: Option<bool>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl VmListItem {
+    /// Returns `true` if the VM is a template.
+    #[must_use]
+    pub fn is_template(&self) -> bool {
+        self.template == Some(true)
+    }
 }
 
 /// Detailed runtime status of a VM from `/nodes/{node}/qemu/{vmid}/status/current`.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VmStatusCurrent {
-    /// Current VM status (e.g., "running", "stopped", "paused").
-    pub status: String,
+    /// Current power state.
+    pub status: PowerStatus,
     /// VM name.
     pub name: String,
     /// CPU usage percentage (0.0 to 1.0).
@@ -99,6 +160,9 @@ pub struct VmStatusCurrent {
     /// Total memory in bytes.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub totalmem: Option<u64>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Balloon device information.
@@ -116,18 +180,25 @@ pub struct BalloonInfo {
 /// VM configuration from `/nodes/{node}/qemu/{vmid}/config`.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct VmConfig {
-    /// VM identifier.
+    /// VM identifier. The endpoint does not return it; [`ProxmoxClient::vm_config`] fills it
+    /// in from the request.
+    ///
+    /// [`ProxmoxClient::vm_config`]: crate::ProxmoxClient::vm_config
+    #[serde(default)]
     pub vmid: u32,
-    /// VM name.
-    pub name: String,
+    /// VM name (unset for VMs created without one).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// Description (if set).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Memory in MB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory: Option<u32>,
-    /// Balloon device minimum memory.
+    /// Balloon device minimum memory in MB; `0` disables ballooning entirely
+    /// (see [`ballooning_enabled`](Self::ballooning_enabled)).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub balloon: Option<u32>,
     /// Number of CPU sockets.
@@ -142,10 +213,12 @@ pub struct VmConfig {
     /// CPU type.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cpu: Option<String>,
-    /// Network devices.
+    /// Network devices under a bare `net` key; servers report indexed `net0..N` keys,
+    /// see [`networks`](Self::networks).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub net: Option<serde_json::Value>,
-    /// Storage devices.
+    /// Storage devices under a bare `scsi` key; indexed disks are returned by
+    /// [`disks`](Self::disks).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scsi: Option<serde_json::Value>,
     /// IDE devices.
@@ -178,6 +251,9 @@ pub struct VmConfig {
     /// Proxmox configuration digest (for updates).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub digest: Option<String>,
+    /// Lock held by a running or interrupted operation (e.g., "backup", "clone", "disk").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<String>,
     /// Start at boot.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub onboot: Option<u8>,
@@ -196,16 +272,743 @@ pub struct VmConfig {
     /// BIOS type (seabios, ovmf).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bios: Option<String>,
-    /// EFI disk (for OVMF).
+    /// Machine type (e.g., "q35", "pc-i440fx-7.1").
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<String>,
+    /// EFI disk (for OVMF).
+    #[serde(default, alias = "efidisk0", skip_serializing_if = "Option::is_none")]
     pub efidisk: Option<String>,
     /// TPM state.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default, alias = "tpmstate0", skip_serializing_if = "Option::is_none")]
     pub tpmstate: Option<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Bus prefixes of the indexed disk keys in a VM configuration.
+const DISK_BUSES: [&str; 4] = ["ide", "sata", "scsi", "virtio"];
+
+impl VmConfig {
+    /// Collects the indexed keys `{prefix}0..N` (e.g., `net0`, `net1`), keyed by index.
+    ///
+    /// Indexed devices are kept in [`extra`](Self::extra) as returned by the server; this
+    /// gathers them so multi-NIC and multi-disk configurations can be walked without knowing
+    /// the indices in advance.
+    #[must_use]
+    pub fn indexed(&self, prefix: &str) -> BTreeMap<u32, String> {
+        indexed_keys(&self.extra, prefix)
+    }
+
+    /// Returns the network devices (`net0..N`), keyed by index.
+    #[must_use]
+    pub fn networks(&self) -> BTreeMap<u32, String> {
+        self.indexed("net")
+    }
+
+    /// Returns all disks and drives on the IDE, SATA, SCSI and VirtIO buses, keyed by
+    /// their configuration key (e.g., "scsi0", "ide2").
+    #[must_use]
+    pub fn disks(&self) -> BTreeMap<String, String> {
+        DISK_BUSES
+            .iter()
+            .flat_map(|bus| {
+                self.indexed(bus)
+                    .into_iter()
+                    .map(move |(index, spec)| (format!("{}{}", bus, index), spec))
+            })
+            .collect()
+    }
+
+    /// Returns the orphaned volumes (`unused0..N`) still owned by the VM, keyed by index.
+    #[must_use]
+    pub fn unused_disks(&self) -> BTreeMap<u32, String> {
+        self.indexed("unused")
+    }
+
+    /// Returns `false` if the balloon device is disabled (`balloon: 0`).
+    ///
+    /// A `balloon` of `0` does not mean a 0 MB minimum: it removes the balloon device,
+    /// so the VM always holds its full `memory`. Unset means the server default (enabled,
+    /// with the minimum equal to `memory`).
+    #[must_use]
+    pub fn ballooning_enabled(&self) -> bool {
+        self.balloon != Some(0)
+    }
+
+    /// Parses the EFI disk, if configured.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is malformed.
+    pub fn efidisk_spec(&self) -> ProxmoxResult<Option<EfiDiskSpec>> {
+        self.efidisk.as_deref().map(EfiDiskSpec::parse).transpose()
+    }
+
+    /// Parses the machine type, if configured.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is not an i440fx or q35 machine.
+    pub fn machine_type(&self) -> ProxmoxResult<Option<MachineType>> {
+        self.machine.as_deref().map(MachineType::parse).transpose()
+    }
+
+    /// Parses the TPM state volume, if configured.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is malformed.
+    pub fn tpmstate_spec(&self) -> ProxmoxResult<Option<TpmStateSpec>> {
+        self.tpmstate
+            .as_deref()
+            .map(TpmStateSpec::parse)
+            .transpose()
+    }
+}
+
+/// Collects the string values of the keys `{prefix}0..N` in `extra`, keyed by index.
+pub(crate) fn indexed_keys(
+    extra: &HashMap<String, serde_json::Value>,
+    prefix: &str,
+) -> BTreeMap<u32, String> {
+    extra
+        .iter()
+        .filter_map(|(key, value)| {
+            let index = key.strip_prefix(prefix)?;
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some((index.parse().ok()?, value.as_str()?.to_string()))
+        })
+        .collect()
+}
+
+/// One configuration key from `/nodes/{node}/qemu/{vmid}/pending`, comparing the active value
+/// with a change that takes effect on the next restart.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct PendingConfigItem {
+    /// Configuration key (e.g., "memory", "net0").
+    pub key: String,
+    /// Active value; absent for a key that is only being added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    /// Value that replaces the active one on the next restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending: Option<serde_json::Value>,
+    /// Set when the key is removed on the next restart (`1`, or `2` if the removal is forced).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete: Option<u8>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl PendingConfigItem {
+    /// Returns `true` if the key changes or disappears on the next restart.
+    #[must_use]
+    pub fn requires_restart(&self) -> bool {
+        self.pending.is_some() || self.delete.is_some_and(|delete| delete > 0)
+    }
+}
+
+/// QEMU chipset family of a machine type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MachineChipset {
+    /// Legacy Intel 440FX chipset (the Proxmox default, `pc`).
+    I440fx,
+    /// Intel Q35 chipset with native PCIe, needed for PCIe passthrough and Windows 11.
+    Q35,
+}
+
+/// A parsed `machine` value such as "q35", "pc" or "pc-i440fx-7.1".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MachineType {
+    /// Chipset family.
+    pub chipset: MachineChipset,
+    /// Pinned QEMU machine version (e.g., "7.1" or "8.1+pve1"); `None` tracks the latest.
+    pub version: Option<String>,
+}
+
+impl MachineType {
+    /// The latest Q35 machine.
+    #[must_use]
+    pub fn q35() -> Self {
+        Self {
+            chipset: MachineChipset::Q35,
+            version: None,
+        }
+    }
+
+    /// The latest i440fx machine.
+    #[must_use]
+    pub fn i440fx() -> Self {
+        Self {
+            chipset: MachineChipset::I440fx,
+            version: None,
+        }
+    }
+
+    /// Pins the machine to a QEMU version (e.g., "8.1").
+    #[must_use]
+    pub fn pinned(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Parses a `machine` configuration value; options after a comma (e.g., `viommu`) are ignored.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is not an i440fx or q35 machine.
+    pub fn parse(raw: &str) -> ProxmoxResult<Self> {
+        let name = raw.split(',').next().unwrap_or_default();
+        let (chipset, version) = match name {
+            "pc" | "i440fx" => (MachineChipset::I440fx, None),
+            "q35" => (MachineChipset::Q35, None),
+            _ => {
+                if let Some(version) = name.strip_prefix("pc-i440fx-") {
+                    (MachineChipset::I440fx, Some(version))
+                } else if let Some(version) = name.strip_prefix("pc-q35-") {
+                    (MachineChipset::Q35, Some(version))
+                } else {
+                    return Err(ProxmoxError::Validation {
+                        source: ValidationError::Field {
+                            field: "machine".to_string(),
+                            message: format!("Unknown machine type '{}'", raw),
+                        },
+                        backtrace: Backtrace::capture(),
+                    });
+                }
+            }
+        };
+        Ok(Self {
+            chipset,
+            version: version.filter(|v| !v.is_empty()).map(str::to_string),
+        })
+    }
+}
+
+impl std::fmt::Display for MachineType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.chipset, &self.version) {
+            (MachineChipset::I440fx, None) => f.write_str("pc"),
+            (MachineChipset::Q35, None) => f.write_str("q35"),
+            (MachineChipset::I440fx, Some(v)) => write!(f, "pc-i440fx-{}", v),
+            (MachineChipset::Q35, Some(v)) => write!(f, "pc-q35-{}", v),
+        }
+    }
+}
+
+/// Guest operating system type (`ostype`), used by Proxmox to tune hardware defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OsType {
+    /// An unspecified operating system (`other`).
+    Unspecified,
+    /// Windows XP.
+    Wxp,
+    /// Windows 2000.
+    W2k,
+    /// Windows Server 2003.
+    W2k3,
+    /// Windows Vista or Server 2008.
+    W2k8,
+    /// Windows Vista.
+    Wvista,
+    /// Windows 7 or Server 2008 R2.
+    Win7,
+    /// Windows 8, Server 2012 or 2012 R2.
+    Win8,
+    /// Windows 10 or Server 2016/2019.
+    Win10,
+    /// Windows 11 or Server 2022/2025.
+    Win11,
+    /// Linux 2.4 kernel.
+    L24,
+    /// Linux 2.6 or later kernel.
+    L26,
+    /// Solaris or OpenSolaris kernel.
+    Solaris,
+    /// A value this crate does not know, passed through as is.
+    Other(String),
+}
+
+impl OsType {
+    /// Returns the value as accepted by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Unspecified => "other",
+            Self::Wxp => "wxp",
+            Self::W2k => "w2k",
+            Self::W2k3 => "w2k3",
+            Self::W2k8 => "w2k8",
+            Self::Wvista => "wvista",
+            Self::Win7 => "win7",
+            Self::Win8 => "win8",
+            Self::Win10 => "win10",
+            Self::Win11 => "win11",
+            Self::L24 => "l24",
+            Self::L26 => "l26",
+            Self::Solaris => "solaris",
+            Self::Other(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for OsType {
+    fn from(value: &str) -> Self {
+        match value {
+            "other" => Self::Unspecified,
+            "wxp" => Self::Wxp,
+            "w2k" => Self::W2k,
+            "w2k3" => Self::W2k3,
+            "w2k8" => Self::W2k8,
+            "wvista" => Self::Wvista,
+            "win7" => Self::Win7,
+            "win8" => Self::Win8,
+            "win10" => Self::Win10,
+            "win11" => Self::Win11,
+            "l24" => Self::L24,
+            "l26" => Self::L26,
+            "solaris" => Self::Solaris,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for OsType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl std::fmt::Display for OsType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for OsType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OsType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+/// Firmware used to boot the VM (`bios`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BiosType {
+    /// Legacy BIOS (SeaBIOS, the default).
+    Seabios,
+    /// UEFI (OVMF); requires an EFI disk.
+    Ovmf,
+    /// A value this crate does not know, passed through as is.
+    Other(String),
+}
+
+impl BiosType {
+    /// Returns the value as accepted by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Seabios => "seabios",
+            Self::Ovmf => "ovmf",
+            Self::Other(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for BiosType {
+    fn from(value: &str) -> Self {
+        match value {
+            "seabios" => Self::Seabios,
+            "ovmf" => Self::Ovmf,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for BiosType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl std::fmt::Display for BiosType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for BiosType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for BiosType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+/// Display adapter type (`vga`).
+///
+/// Values with options (e.g., "qxl,memory=32") are kept in [`Other`](Self::Other).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VgaType {
+    /// Standard VGA.
+    Std,
+    /// Cirrus Logic (for very old guests).
+    Cirrus,
+    /// VMware SVGA II.
+    Vmware,
+    /// SPICE QXL with one display.
+    Qxl,
+    /// SPICE QXL with two displays.
+    Qxl2,
+    /// SPICE QXL with three displays.
+    Qxl3,
+    /// SPICE QXL with four displays.
+    Qxl4,
+    /// Serial terminal on the first serial port.
+    Serial0,
+    /// Serial terminal on the second serial port.
+    Serial1,
+    /// Serial terminal on the third serial port.
+    Serial2,
+    /// Serial terminal on the fourth serial port.
+    Serial3,
+    /// VirtIO GPU.
+    Virtio,
+    /// VirtIO GPU with VirGL 3D acceleration.
+    VirtioGl,
+    /// No display adapter (`none`).
+    Disabled,
+    /// A value this crate does not know, passed through as is.
+    Other(String),
+}
+
+impl VgaType {
+    /// Returns the value as accepted by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Std => "std",
+            Self::Cirrus => "cirrus",
+            Self::Vmware => "vmware",
+            Self::Qxl => "qxl",
+            Self::Qxl2 => "qxl2",
+            Self::Qxl3 => "qxl3",
+            Self::Qxl4 => "qxl4",
+            Self::Serial0 => "serial0",
+            Self::Serial1 => "serial1",
+            Self::Serial2 => "serial2",
+            Self::Serial3 => "serial3",
+            Self::Virtio => "virtio",
+            Self::VirtioGl => "virtio-gl",
+            Self::Disabled => "none",
+            Self::Other(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for VgaType {
+    fn from(value: &str) -> Self {
+        match value {
+            "std" => Self::Std,
+            "cirrus" => Self::Cirrus,
+            "vmware" => Self::Vmware,
+            "qxl" => Self::Qxl,
+            "qxl2" => Self::Qxl2,
+            "qxl3" => Self::Qxl3,
+            "qxl4" => Self::Qxl4,
+            "serial0" => Self::Serial0,
+            "serial1" => Self::Serial1,
+            "serial2" => Self::Serial2,
+            "serial3" => Self::Serial3,
+            "virtio" => Self::Virtio,
+            "virtio-gl" => Self::VirtioGl,
+            "none" => Self::Disabled,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for VgaType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl std::fmt::Display for VgaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for VgaType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for VgaType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+/// SCSI controller model (`scsihw`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ScsiControllerType {
+    /// LSI Logic 53C895A (the default).
+    Lsi,
+    /// LSI Logic 53C810.
+    Lsi53c810,
+    /// VirtIO SCSI with one controller for all disks.
+    VirtioScsiPci,
+    /// VirtIO SCSI with one controller per disk (needed for IO threads).
+    VirtioScsiSingle,
+    /// LSI MegaRAID SAS 1078.
+    Megasas,
+    /// VMware Paravirtual SCSI.
+    Pvscsi,
+    /// A value this crate does not know, passed through as is.
+    Other(String),
+}
+
+impl ScsiControllerType {
+    /// Returns the value as accepted by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Lsi => "lsi",
+            Self::Lsi53c810 => "lsi53c810",
+            Self::VirtioScsiPci => "virtio-scsi-pci",
+            Self::VirtioScsiSingle => "virtio-scsi-single",
+            Self::Megasas => "megasas",
+            Self::Pvscsi => "pvscsi",
+            Self::Other(raw) => raw,
+        }
+    }
+}
+
+impl From<&str> for ScsiControllerType {
+    fn from(value: &str) -> Self {
+        match value {
+            "lsi" => Self::Lsi,
+            "lsi53c810" => Self::Lsi53c810,
+            "virtio-scsi-pci" => Self::VirtioScsiPci,
+            "virtio-scsi-single" => Self::VirtioScsiSingle,
+            "megasas" => Self::Megasas,
+            "pvscsi" => Self::Pvscsi,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ScsiControllerType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl std::fmt::Display for ScsiControllerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ScsiControllerType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ScsiControllerType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::from(raw.as_str()))
+    }
+}
+
+/// A parsed `efidisk0` value (e.g., "local-lvm:vm-100-disk-1,efitype=4m,pre-enrolled-keys=1").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EfiDiskSpec {
+    /// Storage holding the EFI vars volume.
+    pub storage: String,
+    /// Volume name within the storage.
+    pub volume: String,
+    /// EFI vars size type ("2m" or "4m").
+    pub efitype: Option<String>,
+    /// Whether Microsoft secure-boot keys are pre-enrolled.
+    pub pre_enrolled_keys: Option<bool>,
+}
+
+impl EfiDiskSpec {
+    /// Parses an `efidisk0` configuration string.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the volume part is missing or malformed.
+    pub fn parse(raw: &str) -> ProxmoxResult<Self> {
+        let (storage, volume, options) = split_volume_spec("efidisk", raw)?;
+        let mut spec = Self {
+            storage,
+            volume,
+            efitype: None,
+            pre_enrolled_keys: None,
+        };
+        for (key, value) in options {
+            match key {
+                "efitype" => spec.efitype = Some(value.to_string()),
+                "pre-enrolled-keys" => spec.pre_enrolled_keys = Some(value == "1"),
+                _ => {}
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// A parsed `tpmstate0` value (e.g., "local-lvm:vm-100-disk-2,version=v2.0").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TpmStateSpec {
+    /// Storage holding the TPM state volume.
+    pub storage: String,
+    /// Volume name within the storage.
+    pub volume: String,
+    /// TPM interface version ("v1.2" or "v2.0").
+    pub version: Option<String>,
+}
+
+impl TpmStateSpec {
+    /// Parses a `tpmstate0` configuration string.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the volume part is missing or malformed.
+    pub fn parse(raw: &str) -> ProxmoxResult<Self> {
+        let (storage, volume, options) = split_volume_spec("tpmstate", raw)?;
+        let version = options
+            .into_iter()
+            .find(|(key, _)| *key == "version")
+            .map(|(_, value)| value.to_string());
+        Ok(Self {
+            storage,
+            volume,
+            version,
+        })
+    }
+}
+
+/// `key=value` options following a volume identifier.
+type VolumeOptions<'a> = Vec<(&'a str, &'a str)>;
+
+/// Splits "storage:volume,key=value,..." into its volume parts and options.
+fn split_volume_spec<'a>(
+    field: &str,
+    raw: &'a str,
+) -> ProxmoxResult<(String, String, VolumeOptions<'a>)> {
+    let invalid = |message: &str| ProxmoxError::Validation {
+        source: ValidationError::Field {
+            field: field.to_string(),
+            message: message.to_string(),
+        },
+        backtrace: Backtrace::capture(),
+    };
+    let mut parts = raw.split(',');
+    let volid = parts.next().unwrap_or_default();
+    let (storage, volume) = volid
+        .split_once(':')
+        .filter(|(storage, volume)| !storage.is_empty() && !volume.is_empty())
+        .ok_or_else(|| invalid("Expected 'storage:volume'"))?;
+    let options = parts
+        .map(|option| {
+            option
+                .split_once('=')
+                .ok_or_else(|| invalid("Expected 'key=value' option"))
+        })
+        .collect::<ProxmoxResult<Vec<_>>>()?;
+    Ok((storage.to_string(), volume.to_string(), options))
+}
+
+/// How [`ProxmoxClient::shutdown_vm_with_fallback`](crate::ProxmoxClient::shutdown_vm_with_fallback)
+/// brought a VM down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// The guest shut down within the grace period.
+    Graceful,
+    /// The grace period expired and the VM was stopped; holds the outcome of the stop request.
+    Forced(TaskOutcome),
+}
+
+/// Flags for deleting a VM.
+///
+/// `purge` is always sent; the other flags only when enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeleteVmOptions {
+    /// Also remove the VM from backup jobs, replication jobs and HA resources.
+    pub purge: bool,
+    /// Also destroy disks on enabled storages that match the VM but are not referenced in its
+    /// configuration.
+    pub destroy_unreferenced_disks: bool,
+    /// Ignore a lock held on the VM (only allowed for `root@pam`).
+    pub skiplock: bool,
+}
+
+impl DeleteVmOptions {
+    /// Creates options with every flag disabled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether related jobs and HA resources are removed too.
+    #[must_use]
+    pub fn purge(mut self, purge: bool) -> Self {
+        self.purge = purge;
+        self
+    }
+
+    /// Sets whether unreferenced disks belonging to the VM are destroyed too.
+    #[must_use]
+    pub fn destroy_unreferenced_disks(mut self, destroy: bool) -> Self {
+        self.destroy_unreferenced_disks = destroy;
+        self
+    }
+
+    /// Sets whether a lock on the VM is ignored.
+    #[must_use]
+    pub fn skiplock(mut self, skiplock: bool) -> Self {
+        self.skiplock = skiplock;
+        self
+    }
+
+    /// Builds the DELETE query string (e.g., "purge=1&destroy-unreferenced-disks=1").
+    pub(crate) fn to_query(self) -> String {
+        let mut query = format!("purge={}", u8::from(self.purge));
+        if self.destroy_unreferenced_disks {
+            query.push_str("&destroy-unreferenced-disks=1");
+        }
+        if self.skiplock {
+            query.push_str("&skiplock=1");
+        }
+        query
+    }
 }
 
 /// Parameters for creating a new VM.
-#[derive(Debug, Clone, PartialEq, Serialize)]
+///
+/// The type also deserializes from the same shape it serializes to, so a VM spec can be kept
+/// in a JSON or YAML file: absent optional fields become `None`, and disks are read from
+/// indexed keys such as `scsi0: "local-lvm:32,format=qcow2"`. Any other unknown key is
+/// rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateVmParams {
     /// VM identifier (required, must be unique in the cluster).
@@ -229,7 +1032,7 @@ pub struct CreateVmParams {
     pub cpu: Option<String>,
     /// OS type (optional, e.g., "l26", "win10").
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub ostype: Option<String>,
+    pub ostype: Option<OsType>,
     /// Enable/disable KVM hardware virtualization (optional, default 1).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub kvm: Option<u8>,
@@ -241,7 +1044,7 @@ pub struct CreateVmParams {
     pub net: Option<String>,
     /// SCSI controller type (optional, e.g., "virtio-scsi-pci").
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub scsihw: Option<String>,
+    pub scsihw: Option<ScsiControllerType>,
     /// Boot order (optional, e.g., "order=scsi0;net0").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub boot: Option<String>,
@@ -262,10 +1065,13 @@ pub struct CreateVmParams {
     pub tablet: Option<u8>,
     /// VGA configuration (optional, e.g., "virtio").
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub vga: Option<String>,
+    pub vga: Option<VgaType>,
     /// BIOS type (optional, "seabios" or "ovmf").
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub bios: Option<String>,
+    pub bios: Option<BiosType>,
+    /// Machine type (optional, e.g., "q35" or a pinned "pc-q35-8.1"; see [`MachineType`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<String>,
     /// EFI disk (optional, for OVMF).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub efidisk: Option<String>,
@@ -275,4 +1081,1288 @@ pub struct CreateVmParams {
     /// Agent enabled (optional, 1 to enable QEMU Guest Agent).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent: Option<u8>,
+    /// Disks to allocate, serialized as indexed keys (`scsi0`, `scsi1`, `virtio0`, ...).
+    #[serde(
+        flatten,
+        serialize_with = "serialize_disks",
+        deserialize_with = "deserialize_disks"
+    )]
+    pub disks: Vec<DiskSpec>,
+}
+
+/// A disk to allocate when creating a VM.
+///
+/// Disks are numbered per interface in the order they appear, so two `scsi` disks
+/// become `scsi0` and `scsi1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiskSpec {
+    /// Bus interface (e.g., "scsi", "virtio", "sata", "ide").
+    pub interface: String,
+    /// Target storage (e.g., "local-lvm").
+    pub storage: String,
+    /// Disk size in GiB.
+    pub size_gb: u32,
+    /// Image format (e.g., "raw", "qcow2"); storage default if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl DiskSpec {
+    /// Creates a disk specification using the storage's default format.
+    #[must_use]
+    pub fn new(interface: impl Into<String>, storage: impl Into<String>, size_gb: u32) -> Self {
+        Self {
+            interface: interface.into(),
+            storage: storage.into(),
+            size_gb,
+            format: None,
+        }
+    }
+
+    /// Sets the image format.
+    #[must_use]
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// Formats the disk as a Proxmox allocation string (e.g., "local-lvm:32,format=qcow2").
+    #[must_use]
+    pub fn to_proxmox_value(&self) -> String {
+        match &self.format {
+            Some(format) => format!("{}:{},format={}", self.storage, self.size_gb, format),
+            None => format!("{}:{}", self.storage, self.size_gb),
+        }
+    }
+}
+
+/// Serializes disks into indexed keys, numbering each interface independently.
+fn serialize_disks<S>(disks: &[DiskSpec], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut counters: HashMap<&str, u32> = HashMap::new();
+    let mut map = serializer.serialize_map(Some(disks.len()))?;
+    for disk in disks {
+        let index = counters.entry(disk.interface.as_str()).or_insert(0);
+        map.serialize_entry(
+            &format!("{}{}", disk.interface, index),
+            &disk.to_proxmox_value(),
+        )?;
+        *index += 1;
+    }
+    map.end()
+}
+
+/// Bus interfaces whose indexed keys are read as disks.
+const DISK_INTERFACES: [&str; 4] = ["scsi", "virtio", "sata", "ide"];
+
+/// Reads disks back from indexed keys, ordered by interface and index.
+///
+/// Serialization numbers each interface from 0, so gaps in the indices are not preserved.
+fn deserialize_disks<'de, D>(deserializer: D) -> Result<Vec<DiskSpec>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    let entries = BTreeMap::<String, String>::deserialize(deserializer)?;
+    let mut disks = Vec::with_capacity(entries.len());
+    for (key, value) in entries {
+        let Some((interface, index)) = DISK_INTERFACES.iter().find_map(|interface| {
+            let index = key.strip_prefix(interface)?.parse::<u32>().ok()?;
+            Some((*interface, index))
+        }) else {
+            return Err(D::Error::custom(format!("unknown field `{}`", key)));
+        };
+        let disk = parse_disk_allocation(interface, &value).ok_or_else(|| {
+            D::Error::custom(format!(
+                "invalid disk `{}`: expected \"storage:size[,format=...]\", got \"{}\"",
+                key, value
+            ))
+        })?;
+        disks.push((interface, index, disk));
+    }
+    disks.sort_by_key(|(interface, index, _)| (*interface, *index));
+    Ok(disks.into_iter().map(|(_, _, disk)| disk).collect())
+}
+
+/// Parses an allocation string produced by [`DiskSpec::to_proxmox_value`].
+fn parse_disk_allocation(interface: &str, value: &str) -> Option<DiskSpec> {
+    let mut parts = value.split(',');
+    let (storage, size) = parts.next()?.split_once(':')?;
+    if storage.is_empty() {
+        return None;
+    }
+    let mut disk = DiskSpec::new(interface, storage, size.parse().ok()?);
+    for option in parts {
+        disk.format = Some(option.strip_prefix("format=")?.to_string());
+    }
+    Some(disk)
+}
+
+impl CreateVmParams {
+    /// Returns a builder for the given VM identifier and name.
+    #[must_use]
+    pub fn builder(vmid: u32, name: impl Into<String>) -> CreateVmParamsBuilder {
+        CreateVmParamsBuilder::new(vmid, name)
+    }
+
+    /// Checks the parameters before they are sent.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError::Validation`] if `vmid` is outside 100–999999999, `name` is not
+    /// a valid DNS name, or `net`, `scsihw` or `boot` is malformed (a
+    /// [`ValidationError::Format`] naming the offending token).
+    pub fn validate(&self) -> ProxmoxResult<()> {
+        validate_vmid(self.vmid)
+            .and_then(|_| validate_vm_name(&self.name))
+            .and_then(|_| self.net.as_deref().map_or(Ok(()), validate_net_spec))
+            .and_then(|_| {
+                self.scsihw
+                    .as_ref()
+                    .map_or(Ok(()), |scsihw| validate_scsihw(scsihw.as_str()))
+            })
+            .and_then(|_| self.boot.as_deref().map_or(Ok(()), validate_boot_spec))
+            .map_err(|e| ProxmoxError::Validation {
+                source: e,
+                backtrace: Backtrace::capture(),
+            })
+    }
+}
+
+/// SCSI controller types accepted by Proxmox.
+const SCSI_CONTROLLERS: [&str; 6] = [
+    "lsi",
+    "lsi53c810",
+    "virtio-scsi-pci",
+    "virtio-scsi-single",
+    "megasas",
+    "pvscsi",
+];
+
+/// Splits a `key=value` token, rejecting empty keys or values.
+fn split_option<'a>(field: &str, token: &'a str) -> Result<(&'a str, &'a str), ValidationError> {
+    match token.split_once('=') {
+        Some((key, value)) if !key.is_empty() && !value.is_empty() => Ok((key, value)),
+        _ => Err(ValidationError::Format(format!(
+            "Invalid {} token '{}': expected key=value",
+            field, token
+        ))),
+    }
+}
+
+/// Validates a network device string such as "virtio=BC:24:11:00:00:01,bridge=vmbr0,tag=20".
+///
+/// The first token is the model, optionally with a MAC address. Known keys are checked;
+/// unknown `key=value` options are passed through.
+pub(crate) fn validate_net_spec(spec: &str) -> Result<(), ValidationError> {
+    let invalid = |token: &str, reason: &str| {
+        ValidationError::Format(format!("Invalid net token '{}': {}", token, reason))
+    };
+    let mut tokens = spec.split(',');
+    let model_token = tokens.next().unwrap_or_default();
+    let model = model_token.split_once('=').map_or(model_token, |(m, _)| m);
+    if model.is_empty() || !model.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(invalid(
+            model_token,
+            "expected a NIC model such as 'virtio'",
+        ));
+    }
+    if let Some((_, mac)) = model_token.split_once('=') {
+        let octets: Vec<&str> = mac.split(':').collect();
+        if octets.len() != 6
+            || !octets
+                .iter()
+                .all(|o| o.len() == 2 && o.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(invalid(
+                model_token,
+                "expected a MAC address like BC:24:11:00:00:01",
+            ));
+        }
+    }
+    for token in tokens {
+        let (key, value) = split_option("net", token)?;
+        let numeric = || value.parse::<u32>().is_ok();
+        let valid = match key {
+            "bridge" => value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)),
+            "tag" => value
+                .parse::<u16>()
+                .is_ok_and(|tag| (1..=4094).contains(&tag)),
+            "firewall" | "link_down" => value == "0" || value == "1",
+            "mtu" | "queues" => numeric(),
+            "rate" => value.parse::<f64>().is_ok(),
+            _ => true,
+        };
+        if !valid {
+            return Err(invalid(token, &format!("invalid value for '{}'", key)));
+        }
+    }
+    Ok(())
+}
+
+/// Validates a SCSI controller type.
+pub(crate) fn validate_scsihw(value: &str) -> Result<(), ValidationError> {
+    if SCSI_CONTROLLERS.contains(&value) {
+        Ok(())
+    } else {
+        Err(ValidationError::Format(format!(
+            "Invalid scsihw '{}': expected one of {}",
+            value,
+            SCSI_CONTROLLERS.join(", ")
+        )))
+    }
+}
+
+/// Validates a boot order such as "order=scsi0;ide2;net0" (or the legacy "cdn" form).
+pub(crate) fn validate_boot_spec(spec: &str) -> Result<(), ValidationError> {
+    if !spec.is_empty() && !spec.contains('=') {
+        return if spec.chars().all(|c| "acdn".contains(c)) {
+            Ok(())
+        } else {
+            Err(ValidationError::Format(format!(
+                "Invalid boot token '{}': expected order=<devices> or legacy letters (a, c, d, n)",
+                spec
+            )))
+        };
+    }
+    for token in spec.split(',') {
+        let (key, value) = split_option("boot", token)?;
+        if key == "order" {
+            for device in value.split(';') {
+                let bus = device.trim_end_matches(|c: char| c.is_ascii_digit());
+                if bus.is_empty()
+                    || bus.len() == device.len()
+                    || !bus.chars().all(|c| c.is_ascii_lowercase())
+                {
+                    return Err(ValidationError::Format(format!(
+                        "Invalid boot token '{}': '{}' is not a device such as 'scsi0'",
+                        token, device
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fluent builder for [`CreateVmParams`].
+///
+/// Unset fields are left to the server defaults, except memory (512 MB),
+/// sockets (1) and cores (1), which are always sent explicitly.
+#[derive(Debug, Clone)]
+pub struct CreateVmParamsBuilder {
+    params: CreateVmParams,
+}
+
+impl CreateVmParamsBuilder {
+    /// Creates a builder with the default memory, socket and core counts.
+    #[must_use]
+    pub fn new(vmid: u32, name: impl Into<String>) -> Self {
+        Self {
+            params: CreateVmParams {
+                vmid,
+                name: name.into(),
+                memory: Some(512),
+                sockets: Some(1),
+                cores: Some(1),
+                threads: None,
+                cpu: None,
+                ostype: None,
+                kvm: None,
+                numa: None,
+                net: None,
+                scsihw: None,
+                boot: None,
+                start: None,
+                tags: None,
+                description: None,
+                protection: None,
+                tablet: None,
+                vga: None,
+                bios: None,
+                machine: None,
+                efidisk: None,
+                tpmstate: None,
+                agent: None,
+                disks: Vec::new(),
+            },
+        }
+    }
+
+    /// Sets the memory in MB.
+    #[must_use]
+    pub fn memory(mut self, mb: u32) -> Self {
+        self.params.memory = Some(mb);
+        self
+    }
+
+    /// Sets the number of CPU sockets.
+    #[must_use]
+    pub fn sockets(mut self, sockets: u32) -> Self {
+        self.params.sockets = Some(sockets);
+        self
+    }
+
+    /// Sets the number of cores per socket.
+    #[must_use]
+    pub fn cores(mut self, cores: u32) -> Self {
+        self.params.cores = Some(cores);
+        self
+    }
+
+    /// Sets the number of threads per core.
+    #[must_use]
+    pub fn threads(mut self, threads: u32) -> Self {
+        self.params.threads = Some(threads);
+        self
+    }
+
+    /// Sets the CPU type (e.g., "host").
+    #[must_use]
+    pub fn cpu(mut self, cpu: impl Into<String>) -> Self {
+        self.params.cpu = Some(cpu.into());
+        self
+    }
+
+    /// Sets the OS type (e.g., "l26").
+    #[must_use]
+    pub fn ostype(mut self, ostype: impl Into<OsType>) -> Self {
+        self.params.ostype = Some(ostype.into());
+        self
+    }
+
+    /// Enables or disables KVM hardware virtualization.
+    #[must_use]
+    pub fn kvm(mut self, enabled: bool) -> Self {
+        self.params.kvm = Some(u8::from(enabled));
+        self
+    }
+
+    /// Enables or disables NUMA.
+    #[must_use]
+    pub fn numa(mut self, enabled: bool) -> Self {
+        self.params.numa = Some(u8::from(enabled));
+        self
+    }
+
+    /// Sets the network configuration (e.g., "virtio,bridge=vmbr0").
+    #[must_use]
+    pub fn net(mut self, net: impl Into<String>) -> Self {
+        self.params.net = Some(net.into());
+        self
+    }
+
+    /// Sets the SCSI controller type (e.g., "virtio-scsi-pci").
+    #[must_use]
+    pub fn scsihw(mut self, scsihw: impl Into<ScsiControllerType>) -> Self {
+        self.params.scsihw = Some(scsihw.into());
+        self
+    }
+
+    /// Sets the boot order (e.g., "order=scsi0;net0").
+    #[must_use]
+    pub fn boot(mut self, boot: impl Into<String>) -> Self {
+        self.params.boot = Some(boot.into());
+        self
+    }
+
+    /// Starts the VM after creation.
+    #[must_use]
+    pub fn start(mut self, start: bool) -> Self {
+        self.params.start = Some(u8::from(start));
+        self
+    }
+
+    /// Sets the tags.
+    #[must_use]
+    pub fn tags(mut self, tags: impl Into<String>) -> Self {
+        self.params.tags = Some(tags.into());
+        self
+    }
+
+    /// Sets the description.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.params.description = Some(description.into());
+        self
+    }
+
+    /// Enables or disables protection from accidental removal.
+    #[must_use]
+    pub fn protection(mut self, enabled: bool) -> Self {
+        self.params.protection = Some(u8::from(enabled));
+        self
+    }
+
+    /// Enables or disables the tablet USB pointer.
+    #[must_use]
+    pub fn tablet(mut self, enabled: bool) -> Self {
+        self.params.tablet = Some(u8::from(enabled));
+        self
+    }
+
+    /// Sets the VGA configuration (e.g., "virtio").
+    #[must_use]
+    pub fn vga(mut self, vga: impl Into<VgaType>) -> Self {
+        self.params.vga = Some(vga.into());
+        self
+    }
+
+    /// Sets the BIOS type ("seabios" or "ovmf").
+    #[must_use]
+    pub fn bios(mut self, bios: impl Into<BiosType>) -> Self {
+        self.params.bios = Some(bios.into());
+        self
+    }
+
+    /// Sets the machine type (e.g., "q35"); see [`MachineType`] for a typed form.
+    #[must_use]
+    pub fn machine(mut self, machine: impl Into<String>) -> Self {
+        self.params.machine = Some(machine.into());
+        self
+    }
+
+    /// Sets the EFI disk (for OVMF).
+    #[must_use]
+    pub fn efidisk(mut self, efidisk: impl Into<String>) -> Self {
+        self.params.efidisk = Some(efidisk.into());
+        self
+    }
+
+    /// Sets the TPM state volume.
+    #[must_use]
+    pub fn tpmstate(mut self, tpmstate: impl Into<String>) -> Self {
+        self.params.tpmstate = Some(tpmstate.into());
+        self
+    }
+
+    /// Enables or disables the QEMU Guest Agent.
+    #[must_use]
+    pub fn agent(mut self, enabled: bool) -> Self {
+        self.params.agent = Some(u8::from(enabled));
+        self
+    }
+
+    /// Adds a disk; disks are numbered per interface in the order they are added.
+    #[must_use]
+    pub fn disk(mut self, disk: DiskSpec) -> Self {
+        self.params.disks.push(disk);
+        self
+    }
+
+    /// Validates the parameters and returns the finished [`CreateVmParams`].
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError::Validation`] for the cases listed on
+    /// [`CreateVmParams::validate`].
+    pub fn build(self) -> ProxmoxResult<CreateVmParams> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+/// A VM identifier returned by `/cluster/nextid`, which Proxmox encodes as a string.
+#[derive(Debug, Deserialize)]
+pub(crate) struct NextVmid(
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::string_or_number::deserialize"
+    )]
+    pub(crate) u32,
+);
+
+/// Outcome of [`ProxmoxClient::ensure_vm`](crate::ProxmoxClient::ensure_vm).
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnsuredVm {
+    /// The VM already existed; creation was skipped.
+    Existing(Box<VmConfig>),
+    /// The VM did not exist and creation was started; holds the task ID.
+    Created(String),
+}
+
+/// A partial VM configuration update for `PUT /nodes/{node}/qemu/{vmid}/config`.
+///
+/// Only fields that are set are sent. Keys without a typed field (such as `net1` or
+/// `scsi2`) go through [`set`](Self::set), and keys listed in [`delete`](Self::delete)
+/// are removed from the configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UpdateVmConfig {
+    /// VM name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Memory in MB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u32>,
+    /// Balloon device minimum memory in MB (`0` disables ballooning).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balloon: Option<u32>,
+    /// Number of CPU sockets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sockets: Option<u32>,
+    /// Number of cores per socket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cores: Option<u32>,
+    /// CPU type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<String>,
+    /// OS type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ostype: Option<String>,
+    /// SCSI controller type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scsihw: Option<String>,
+    /// Boot order (e.g., "order=scsi0;net0").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boot: Option<String>,
+    /// Start at boot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub onboot: Option<u8>,
+    /// Tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+    /// Description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Protection from accidental removal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protection: Option<u8>,
+    /// Agent enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<u8>,
+    /// Digest of the configuration this update is based on (see [`VmConfig::digest`]).
+    ///
+    /// If set, the server rejects the update with `ProxmoxError::Conflict` when the
+    /// configuration was modified since it was read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// Configuration keys to remove (sent as `delete=net1,unused0`).
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_comma_list"
+    )]
+    pub delete: Vec<String>,
+    /// Other configuration keys to set, such as indexed devices (`net1`, `scsi2`).
+    #[serde(flatten)]
+    pub options: BTreeMap<String, String>,
+}
+
+impl UpdateVmConfig {
+    /// Creates an empty update.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a configuration key that has no typed field (e.g., `net1`).
+    #[must_use]
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    /// Only applies the update if the configuration still has `digest`.
+    #[must_use]
+    pub fn if_digest(mut self, digest: impl Into<String>) -> Self {
+        self.digest = Some(digest.into());
+        self
+    }
+
+    /// Removes a configuration key (e.g., `net1` or `unused0`).
+    #[must_use]
+    pub fn delete(mut self, key: impl Into<String>) -> Self {
+        self.delete.push(key.into());
+        self
+    }
+
+    /// Computes the update that turns `current` into exactly `desired`.
+    ///
+    /// Keys whose value differs are set, and keys present in `current` but absent from
+    /// `desired` are deleted. `vmid`, `digest` and `lock` are not configuration keys and are
+    /// ignored.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if a configuration cannot be serialized.
+    pub fn diff(current: &VmConfig, desired: &VmConfig) -> ProxmoxResult<Self> {
+        let current = config_keys(current)?;
+        let desired = config_keys(desired)?;
+        let mut update = Self::new();
+        for (key, value) in &desired {
+            if current.get(key) != Some(value) {
+                update.options.insert(key.clone(), value.clone());
+            }
+        }
+        update.delete = current
+            .keys()
+            .filter(|key| !desired.contains_key(*key))
+            .cloned()
+            .collect();
+        Ok(update)
+    }
+
+    /// Returns `true` if the update would not change anything.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        Self {
+            digest: None,
+            ..self.clone()
+        } == Self::default()
+    }
+}
+
+/// Keys of [`VmConfig`] that are not part of the configuration itself.
+const NON_CONFIG_KEYS: [&str; 3] = ["digest", "lock", "vmid"];
+
+/// Flattens a configuration into the `key=value` form the API accepts, using the server's
+/// key names.
+fn config_keys(config: &VmConfig) -> ProxmoxResult<BTreeMap<String, String>> {
+    let value = serde_json::to_value(config)
+        .map_err(|e| ProxmoxError::Unexpected(format!("Failed to serialize VM config: {}", e)))?;
+    let serde_json::Value::Object(map) = value else {
+        return Ok(BTreeMap::new());
+    };
+    Ok(map
+        .into_iter()
+        .filter(|(key, value)| !NON_CONFIG_KEYS.contains(&key.as_str()) && !value.is_null())
+        .map(|(key, value)| {
+            let key = match key.as_str() {
+                "efidisk" => "efidisk0".to_string(),
+                "tpmstate" => "tpmstate0".to_string(),
+                _ => key,
+            };
+            let value = match value {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            (key, value)
+        })
+        .collect())
+}
+
+/// Serializes a list as the comma-separated string the API expects.
+pub(crate) fn serialize_comma_list<S>(items: &[String], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&items.join(","))
+}
+
+/// Parameters for cloning a VM via `/nodes/{node}/qemu/{vmid}/clone`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CloneVmParams {
+    /// Identifier of the new VM.
+    pub newid: u32,
+    /// Name of the new VM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Target node (only allowed for VMs on shared storage).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Create a full copy of all disks instead of a linked clone (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<u8>,
+    /// Target storage for full clones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<String>,
+    /// Target image format for full clones (e.g., "raw", "qcow2").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Resource pool to add the new VM to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+    /// Description of the new VM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Snapshot to clone from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapname: Option<String>,
+    /// I/O bandwidth limit in KiB/s for copying disks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bwlimit: Option<u64>,
+}
+
+impl CloneVmParams {
+    /// Creates clone parameters for the given new VM identifier, leaving everything else
+    /// to the server defaults (a linked clone where possible).
+    #[must_use]
+    pub fn new(newid: u32) -> Self {
+        Self {
+            newid,
+            name: None,
+            target: None,
+            full: None,
+            storage: None,
+            format: None,
+            pool: None,
+            description: None,
+            snapname: None,
+            bwlimit: None,
+        }
+    }
+}
+
+/// Parameters for moving a VM disk via `/nodes/{node}/qemu/{vmid}/move_disk`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MoveDiskParams {
+    /// Disk to move (e.g., "scsi0").
+    pub disk: String,
+    /// Target storage.
+    pub storage: String,
+    /// Delete the original disk after a successful copy (1 = yes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete: Option<u8>,
+    /// Target image format (e.g., "raw", "qcow2").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// I/O bandwidth limit in KiB/s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bwlimit: Option<u64>,
+}
+
+impl MoveDiskParams {
+    /// Creates parameters moving `disk` to `storage`, keeping the original volume.
+    #[must_use]
+    pub fn new(disk: impl Into<String>, storage: impl Into<String>) -> Self {
+        Self {
+            disk: disk.into(),
+            storage: storage.into(),
+            delete: None,
+            format: None,
+            bwlimit: None,
+        }
+    }
+}
+
+/// Migration preconditions from `GET /nodes/{node}/qemu/{vmid}/migrate`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MigratePreconditions {
+    /// Whether the VM is running, so only an online migration is possible.
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::int_bool::deserialize"
+    )]
+    pub running: bool,
+    /// Nodes the VM can be migrated to.
+    #[serde(default)]
+    pub allowed_nodes: Vec<String>,
+    /// Nodes the VM cannot be migrated to, with the reason reported for each.
+    #[serde(default)]
+    pub not_allowed_nodes: HashMap<String, MigrateBlocker>,
+    /// Disks on storage that is not shared with the target, which must be copied.
+    #[serde(default)]
+    pub local_disks: Vec<MigrateLocalDisk>,
+    /// Node-local devices that prevent migration (e.g., "hostpci0", "usb0").
+    #[serde(default)]
+    pub local_resources: Vec<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl MigratePreconditions {
+    /// Returns `true` if `target` is among the [`allowed_nodes`](Self::allowed_nodes) and no
+    /// local device pins the VM to its node.
+    #[must_use]
+    pub fn allows(&self, target: &str) -> bool {
+        self.local_resources.is_empty() && self.allowed_nodes.iter().any(|node| node == target)
+    }
+}
+
+/// Why a node cannot receive a VM.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MigrateBlocker {
+    /// Storages used by the VM that are not available on the node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable_storages: Vec<String>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A VM disk on storage that is local to its node.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MigrateLocalDisk {
+    /// Volume ID (e.g., "local-lvm:vm-100-disk-0").
+    pub volid: String,
+    /// Size in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Configuration key the disk is attached as (e.g., "scsi0"); absent for unused volumes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drivename: Option<String>,
+    /// Whether the volume is an unused disk.
+    #[serde(
+        default,
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::option_int_bool::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_unused: Option<bool>,
+    /// Fields returned by the server that this crate does not model yet.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Validates that a VM identifier is within the range accepted by Proxmox.
+pub(crate) fn validate_vmid(vmid: u32) -> Result<(), ValidationError> {
+    if !(MIN_VMID..=MAX_VMID).contains(&vmid) {
+        return Err(vmid_out_of_range(vmid));
+    }
+    Ok(())
+}
+
+/// Builds the error for a VM identifier outside `MIN_VMID..=MAX_VMID`, quoting `vmid` as given.
+pub(crate) fn vmid_out_of_range(vmid: impl std::fmt::Display) -> ValidationError {
+    ValidationError::Field {
+        field: "vmid".to_string(),
+        message: format!(
+            "VM ID must be between {} and {} (got {})",
+            MIN_VMID, MAX_VMID, vmid
+        ),
+    }
+}
+
+/// Validates a VM name against the DNS-name format Proxmox enforces.
+pub(crate) fn validate_vm_name(name: &str) -> Result<(), ValidationError> {
+    if name.is_empty() {
+        return Err(ValidationError::Field {
+            field: "name".to_string(),
+            message: "VM name cannot be empty".to_string(),
+        });
+    }
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(ValidationError::Format(
+                "Each VM name label must be 1-63 characters".to_string(),
+            ));
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(ValidationError::Format(
+                "VM name can only contain alphanumeric characters, hyphens, and dots".to_string(),
+            ));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(ValidationError::Format(
+                "VM name labels cannot start or end with hyphen".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ballooning_enabled() {
+        let config = |balloon: Option<u32>| -> VmConfig {
+            serde_json::from_value(serde_json::json!({
+                "vmid": 100,
+                "name": "test-vm",
+                "memory": 4096,
+                "balloon": balloon
+            }))
+            .unwrap()
+        };
+        assert!(!config(Some(0)).ballooning_enabled());
+        assert!(config(Some(1024)).ballooning_enabled());
+        assert!(config(None).ballooning_enabled());
+    }
+
+    #[test]
+    fn test_indexed_devices_preserved() {
+        let config: VmConfig = serde_json::from_value(serde_json::json!({
+            "vmid": 100,
+            "name": "multi-nic",
+            "net0": "virtio=BC:24:11:00:00:01,bridge=vmbr0",
+            "net1": "virtio=BC:24:11:00:00:02,bridge=vmbr1,tag=20",
+            "scsi0": "local-lvm:vm-100-disk-0,size=32G",
+            "ide2": "none,media=cdrom",
+            "scsihw": "virtio-scsi-single",
+            "unused0": "local-lvm:vm-100-disk-1"
+        }))
+        .unwrap();
+
+        let networks = config.networks();
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[&0], "virtio=BC:24:11:00:00:01,bridge=vmbr0");
+        assert_eq!(networks[&1], "virtio=BC:24:11:00:00:02,bridge=vmbr1,tag=20");
+
+        let disks = config.disks();
+        assert_eq!(
+            disks.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["ide2", "scsi0"]
+        );
+        assert_eq!(disks["scsi0"], "local-lvm:vm-100-disk-0,size=32G");
+        assert_eq!(config.unused_disks()[&0], "local-lvm:vm-100-disk-1");
+
+        let round_trip = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            round_trip["net1"],
+            "virtio=BC:24:11:00:00:02,bridge=vmbr1,tag=20"
+        );
+        assert_eq!(round_trip["unused0"], "local-lvm:vm-100-disk-1");
+    }
+
+    #[test]
+    fn test_update_vm_config_serializes_only_set_fields() {
+        let update = UpdateVmConfig {
+            cores: Some(4),
+            ..UpdateVmConfig::new()
+        }
+        .set("net1", "virtio,bridge=vmbr1")
+        .delete("net2")
+        .delete("unused0");
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            serde_json::json!({
+                "cores": 4,
+                "net1": "virtio,bridge=vmbr1",
+                "delete": "net2,unused0"
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(UpdateVmConfig::new()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn test_validate_net_spec() {
+        assert!(validate_net_spec("virtio,bridge=vmbr0").is_ok());
+        assert!(
+            validate_net_spec("virtio=BC:24:11:00:00:01,bridge=vmbr0,tag=20,firewall=1").is_ok()
+        );
+        assert!(validate_net_spec("e1000,bridge=vmbr1,custom=value").is_ok());
+
+        for (spec, token) in [
+            ("virtio,bridge=", "bridge="),
+            ("virtio,,bridge=vmbr0", "''"),
+            ("virtio,bridge=vmbr0,tag=5000", "tag=5000"),
+            ("virtio=not-a-mac,bridge=vmbr0", "virtio=not-a-mac"),
+            (",bridge=vmbr0", "''"),
+        ] {
+            match validate_net_spec(spec) {
+                Err(ValidationError::Format(message)) => {
+                    assert!(message.contains(token), "{}: {}", spec, message)
+                }
+                other => panic!("{}: expected a format error, got {:?}", spec, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_hardware_enums_serialize_as_api_strings() {
+        assert_eq!(serde_json::to_string(&BiosType::Ovmf).unwrap(), r#""ovmf""#);
+        assert_eq!(
+            serde_json::to_string(&ScsiControllerType::VirtioScsiSingle).unwrap(),
+            r#""virtio-scsi-single""#
+        );
+        assert_eq!(VgaType::from("none"), VgaType::Disabled);
+        assert_eq!(OsType::from("other"), OsType::Unspecified);
+
+        let custom: VgaType = serde_json::from_str(r#""qxl,memory=32""#).unwrap();
+        assert_eq!(custom, VgaType::Other("qxl,memory=32".to_string()));
+        assert_eq!(
+            serde_json::to_string(&custom).unwrap(),
+            r#""qxl,memory=32""#
+        );
+
+        let body = serde_json::to_value(
+            CreateVmParams::builder(100, "web")
+                .ostype(OsType::Win11)
+                .bios(BiosType::Ovmf)
+                .vga("virtio-gl")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(body["ostype"], "win11");
+        assert_eq!(body["bios"], "ovmf");
+        assert_eq!(body["vga"], "virtio-gl");
+    }
+
+    #[test]
+    fn test_validate_scsihw_and_boot() {
+        assert!(validate_scsihw("virtio-scsi-single").is_ok());
+        assert!(validate_scsihw("virtio-scsi").is_err());
+        assert!(validate_boot_spec("order=scsi0;ide2;net0").is_ok());
+        assert!(validate_boot_spec("cdn").is_ok());
+        assert!(validate_boot_spec("order=scsi0;;net0").is_err());
+        assert!(validate_boot_spec("order=").is_err());
+
+        let params = CreateVmParams::builder(100, "web")
+            .net("virtio,bridge=")
+            .build();
+        assert!(matches!(
+            params,
+            Err(ProxmoxError::Validation {
+                source: ValidationError::Format(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_machine_type_parse() {
+        assert_eq!(MachineType::parse("q35").unwrap(), MachineType::q35());
+        assert_eq!(MachineType::parse("pc").unwrap(), MachineType::i440fx());
+
+        let pinned = MachineType::parse("pc-i440fx-7.1").unwrap();
+        assert_eq!(pinned.chipset, MachineChipset::I440fx);
+        assert_eq!(pinned.version.as_deref(), Some("7.1"));
+
+        let pinned_q35 = MachineType::parse("pc-q35-8.1+pve1,viommu=intel").unwrap();
+        assert_eq!(pinned_q35, MachineType::q35().pinned("8.1+pve1"));
+        assert_eq!(pinned_q35.to_string(), "pc-q35-8.1+pve1");
+
+        assert!(MachineType::parse("virt").is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_preserved_in_extra() {
+        let item: VmListItem = serde_json::from_value(serde_json::json!({
+            "vmid": 100,
+            "name": "test-vm",
+            "status": "running",
+            "node": "pve1",
+            "id": "qemu/100",
+            "lock": "backup",
+            "serial": 1
+        }))
+        .unwrap();
+        assert_eq!(item.extra.len(), 2);
+        assert_eq!(item.extra["lock"], serde_json::json!("backup"));
+        assert_eq!(item.extra["serial"], serde_json::json!(1));
+
+        let round_trip = serde_json::to_value(&item).unwrap();
+        assert_eq!(round_trip["lock"], "backup");
+    }
+
+    #[test]
+    fn test_vm_list_item_string_encoded_numbers() {
+        let item: VmListItem = serde_json::from_value(serde_json::json!({
+            "vmid": "100",
+            "name": "test-vm",
+            "status": "running",
+            "node": "pve1",
+            "id": "qemu/100",
+            "cpu": "0.15",
+            "maxcpu": 2,
+            "mem": "1073741824",
+            "maxmem": "8589934592",
+            "uptime": ""
+        }))
+        .unwrap();
+        assert_eq!(item.vmid, 100);
+        assert_eq!(item.cpu, Some(0.15));
+        assert_eq!(item.maxcpu, Some(2));
+        assert_eq!(item.mem, Some(1_073_741_824));
+        assert_eq!(item.maxmem, Some(8_589_934_592));
+        assert_eq!(item.uptime, None);
+        assert_eq!(
+            serde_json::to_value(&item).unwrap()["maxmem"],
+            8_589_934_592_u64
+        );
+    }
+
+    #[test]
+    fn test_clone_vm_params_bwlimit_serialized() {
+        let params = CloneVmParams {
+            full: Some(1),
+            bwlimit: Some(51_200),
+            ..CloneVmParams::new(201)
+        };
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            serde_json::json!({ "newid": 201, "full": 1, "bwlimit": 51200 })
+        );
+        let linked = serde_json::to_value(CloneVmParams::new(202)).unwrap();
+        assert_eq!(linked, serde_json::json!({ "newid": 202 }));
+    }
+
+    #[test]
+    fn test_efidisk_spec_parse() {
+        let spec =
+            EfiDiskSpec::parse("local-lvm:vm-100-disk-1,efitype=4m,pre-enrolled-keys=1").unwrap();
+        assert_eq!(spec.storage, "local-lvm");
+        assert_eq!(spec.volume, "vm-100-disk-1");
+        assert_eq!(spec.efitype.as_deref(), Some("4m"));
+        assert_eq!(spec.pre_enrolled_keys, Some(true));
+
+        let bare = EfiDiskSpec::parse("local:100/vm-100-disk-0.qcow2,size=528K").unwrap();
+        assert_eq!(bare.volume, "100/vm-100-disk-0.qcow2");
+        assert_eq!(bare.efitype, None);
+        assert_eq!(bare.pre_enrolled_keys, None);
+
+        assert!(EfiDiskSpec::parse("vm-100-disk-1").is_err());
+        assert!(EfiDiskSpec::parse("local-lvm:vm-100-disk-1,efitype").is_err());
+    }
+
+    #[test]
+    fn test_tpmstate_spec_parse() {
+        let spec = TpmStateSpec::parse("local-lvm:vm-100-disk-2,size=4M,version=v2.0").unwrap();
+        assert_eq!(spec.storage, "local-lvm");
+        assert_eq!(spec.volume, "vm-100-disk-2");
+        assert_eq!(spec.version.as_deref(), Some("v2.0"));
+        assert!(TpmStateSpec::parse(":vm-100-disk-2").is_err());
+    }
+
+    #[test]
+    fn test_vm_config_efidisk_accessors() {
+        let config: VmConfig = serde_json::from_value(serde_json::json!({
+            "vmid": 100,
+            "name": "win11",
+            "bios": "ovmf",
+            "efidisk0": "local-lvm:vm-100-disk-1,efitype=4m,pre-enrolled-keys=1",
+            "tpmstate0": "local-lvm:vm-100-disk-2,version=v2.0"
+        }))
+        .unwrap();
+        let efi = config.efidisk_spec().unwrap().unwrap();
+        assert_eq!(efi.efitype.as_deref(), Some("4m"));
+        let tpm = config.tpmstate_spec().unwrap().unwrap();
+        assert_eq!(tpm.version.as_deref(), Some("v2.0"));
+    }
+
+    #[test]
+    fn test_create_vm_params_builder_minimal() {
+        let params = CreateVmParams::builder(100, "test-vm").build().unwrap();
+        assert_eq!(params.vmid, 100);
+        assert_eq!(params.name, "test-vm");
+        assert_eq!(params.memory, Some(512));
+        assert_eq!(params.sockets, Some(1));
+        assert_eq!(params.cores, Some(1));
+        assert_eq!(params.net, None);
+        assert_eq!(params.agent, None);
+    }
+
+    #[test]
+    fn test_create_vm_params_builder_setters() {
+        let params = CreateVmParams::builder(200, "web.example")
+            .memory(2048)
+            .cores(4)
+            .net("virtio,bridge=vmbr0")
+            .agent(true)
+            .start(false)
+            .build()
+            .unwrap();
+        assert_eq!(params.memory, Some(2048));
+        assert_eq!(params.cores, Some(4));
+        assert_eq!(params.net.as_deref(), Some("virtio,bridge=vmbr0"));
+        assert_eq!(params.agent, Some(1));
+        assert_eq!(params.start, Some(0));
+    }
+
+    #[test]
+    fn test_disks_serialize_to_indexed_keys() {
+        let params = CreateVmParams::builder(100, "test-vm")
+            .disk(DiskSpec::new("scsi", "local-lvm", 32))
+            .disk(DiskSpec::new("scsi", "local", 8).with_format("qcow2"))
+            .disk(DiskSpec::new("virtio", "local-lvm", 16))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(json["scsi0"], "local-lvm:32");
+        assert_eq!(json["scsi1"], "local:8,format=qcow2");
+        assert_eq!(json["virtio0"], "local-lvm:16");
+        assert!(json.get("disks").is_none());
+    }
+
+    #[test]
+    fn test_create_vm_params_round_trips_json_spec() {
+        let spec = serde_json::json!({
+            "vmid": 200,
+            "name": "web-01",
+            "memory": 2048,
+            "cores": 2,
+            "net": "virtio,bridge=vmbr0",
+            "scsi0": "local-lvm:32",
+            "scsi1": "local:8,format=qcow2",
+            "virtio0": "local-lvm:16"
+        });
+        let params: CreateVmParams = serde_json::from_value(spec.clone()).unwrap();
+        assert_eq!(params.vmid, 200);
+        assert_eq!(params.memory, Some(2048));
+        assert_eq!(params.sockets, None);
+        assert_eq!(params.bios, None);
+        assert_eq!(
+            params.disks,
+            vec![
+                DiskSpec::new("scsi", "local-lvm", 32),
+                DiskSpec::new("scsi", "local", 8).with_format("qcow2"),
+                DiskSpec::new("virtio", "local-lvm", 16),
+            ]
+        );
+        assert_eq!(serde_json::to_value(&params).unwrap(), spec);
+
+        let typo = serde_json::json!({"vmid": 200, "name": "web-01", "memroy": 2048});
+        assert!(serde_json::from_value::<CreateVmParams>(typo).is_err());
+    }
+
+    #[test]
+    fn test_no_disks_serializes_no_disk_keys() {
+        let params = CreateVmParams::builder(100, "test-vm").build().unwrap();
+        let json = serde_json::to_value(&params).unwrap();
+        assert!(json.get("scsi0").is_none());
+        assert!(json.get("disks").is_none());
+    }
+
+    #[test]
+    fn test_create_vm_params_builder_rejects_out_of_range_vmid() {
+        for vmid in [0, 99, 1_000_000_000] {
+            let err = CreateVmParams::builder(vmid, "test-vm")
+                .build()
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "vmid"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_create_vm_params_builder_rejects_invalid_name() {
+        assert!(CreateVmParams::builder(100, "").build().is_err());
+        assert!(CreateVmParams::builder(100, "bad_name").build().is_err());
+        assert!(CreateVmParams::builder(100, "-leading").build().is_err());
+        assert!(CreateVmParams::builder(100, "double..dot").build().is_err());
+    }
+
+    #[test]
+    fn test_update_diff_sets_changes_and_deletes_missing_keys() {
+        let current: VmConfig = serde_json::from_value(serde_json::json!({
+            "vmid": 100,
+            "name": "web01",
+            "memory": 2048,
+            "efidisk0": "local-lvm:vm-100-disk-0,efitype=4m",
+            "net0": "virtio,bridge=vmbr0",
+            "serial0": "socket",
+            "digest": "abc123"
+        }))
+        .unwrap();
+        let mut desired = current.clone();
+        desired.memory = Some(4096);
+        desired.efidisk = Some("local-lvm:vm-100-disk-1,efitype=4m".to_string());
+        desired.extra.remove("serial0");
+        desired.digest = None;
+
+        let update = UpdateVmConfig::diff(&current, &desired).unwrap();
+        assert_eq!(update.delete, vec!["serial0".to_string()]);
+        assert_eq!(
+            update.options,
+            BTreeMap::from([
+                (
+                    "efidisk0".to_string(),
+                    "local-lvm:vm-100-disk-1,efitype=4m".to_string()
+                ),
+                ("memory".to_string(), "4096".to_string()),
+            ])
+        );
+        assert!(UpdateVmConfig::diff(&current, &current).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,168 @@
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::backtrace::Backtrace;
+use std::fmt;
+use std::str::FromStr;
+
+/// A certificate fingerprint (e.g., the SHA-256 `ssl_fingerprint` of a node).
+///
+/// Fingerprints are stored as upper-case hex bytes joined by colons, so `aa:bb`, `AA:BB`,
+/// `AABB` and `aa-bb` are all the same value and compare equal. This makes it suitable for
+/// trust-on-first-use pinning, where the recorded and the presented fingerprint may come
+/// from tools that format them differently.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint(String);
+
+impl Fingerprint {
+    /// Parses and normalizes a fingerprint.
+    ///
+    /// Colons, dashes and whitespace between the hex digits are ignored.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if `value` contains no hex digits, an odd number of
+    /// them, or any other character.
+    pub fn new(value: &str) -> Result<Self, ProxmoxError> {
+        normalize_fingerprint(value)
+            .map(Self)
+            .map_err(|source| ProxmoxError::Validation {
+                source,
+                backtrace: Backtrace::capture(),
+            })
+    }
+
+    /// Returns `true` if `other` is a fingerprint equal to this one, ignoring formatting.
+    ///
+    /// A string that is not a valid fingerprint never matches.
+    #[must_use]
+    pub fn matches(&self, other: &str) -> bool {
+        normalize_fingerprint(other).is_ok_and(|other| other == self.0)
+    }
+
+    /// Returns the normalized fingerprint (e.g., "AA:BB:CC").
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = ProxmoxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for Fingerprint {
+    type Error = ProxmoxError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl AsRef<str> for Fingerprint {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for Fingerprint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fingerprint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        normalize_fingerprint(&value)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Normalizes a fingerprint to upper-case hex bytes joined by colons.
+pub(crate) fn normalize_fingerprint(value: &str) -> Result<String, ValidationError> {
+    let invalid = |message: &str| ValidationError::Field {
+        field: "fingerprint".to_string(),
+        message: message.to_string(),
+    };
+    let mut digits = Vec::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            ':' | '-' => {}
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_hexdigit() => digits.push(c.to_ascii_uppercase()),
+            _ => {
+                return Err(invalid(
+                    "Fingerprint can only contain hex digits and separators",
+                ));
+            }
+        }
+    }
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return Err(invalid(
+            "Fingerprint must contain a whole number of hex bytes",
+        ));
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|byte| byte.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_formatting() {
+        let pinned = Fingerprint::new("aa:bb:cc").unwrap();
+        assert_eq!(pinned, Fingerprint::new("AA:BB:CC").unwrap());
+        assert_eq!(pinned, Fingerprint::new("AABBCC").unwrap());
+        assert_eq!(pinned.as_str(), "AA:BB:CC");
+        assert!(pinned.matches("aa-bb-cc"));
+        assert!(pinned.matches(" AA BB CC "));
+    }
+
+    #[test]
+    fn test_fingerprint_differs() {
+        let pinned = Fingerprint::new("AA:BB:CC").unwrap();
+        assert_ne!(pinned, Fingerprint::new("AA:BB:CD").unwrap());
+        assert_ne!(pinned, Fingerprint::new("AA:BB").unwrap());
+        assert!(!pinned.matches("AA:BB:CD"));
+        assert!(!pinned.matches("not a fingerprint"));
+    }
+
+    #[test]
+    fn test_fingerprint_invalid() {
+        for bad in ["", "::", "AA:B", "GG:HH", "AA;BB"] {
+            assert!(
+                matches!(
+                    Fingerprint::new(bad),
+                    Err(ProxmoxError::Validation {
+                        source: ValidationError::Field { ref field, .. },
+                        ..
+                    }) if field == "fingerprint"
+                ),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_serde() {
+        let fingerprint: Fingerprint = serde_json::from_str("\"aa:bb\"").unwrap();
+        assert_eq!(serde_json::to_string(&fingerprint).unwrap(), "\"AA:BB\"");
+        assert!(serde_json::from_str::<Fingerprint>("\"xyz\"").is_err());
+    }
+}
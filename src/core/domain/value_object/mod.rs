@@ -1,3 +1,5 @@
+mod fingerprint;
+mod node_name;
 mod proxmox_csrf_token;
 mod proxmox_host;
 mod proxmox_password;
@@ -6,8 +8,11 @@ mod proxmox_realm;
 mod proxmox_ticket;
 mod proxmox_uri;
 mod proxmox_username;
-mod serde_helpers;
+pub(crate) mod serde_helpers;
+mod vm_id;
 
+pub use fingerprint::Fingerprint;
+pub use node_name::NodeName;
 pub use proxmox_csrf_token::ProxmoxCSRFToken;
 pub use proxmox_host::ProxmoxHost;
 pub use proxmox_password::ProxmoxPassword;
@@ -16,6 +21,7 @@ pub use proxmox_realm::ProxmoxRealm;
 pub use proxmox_ticket::ProxmoxTicket;
 pub use proxmox_uri::ProxmoxUrl;
 pub use proxmox_username::ProxmoxUsername;
+pub use vm_id::VmId;
 
 // Re-export validation functions for internal use
 pub(crate) use proxmox_csrf_token::validate_csrf_token;
@@ -23,6 +29,8 @@ pub(crate) use proxmox_host::validate_host;
 pub(crate) use proxmox_password::validate_password;
 pub(crate) use proxmox_port::validate_port;
 pub(crate) use proxmox_realm::validate_realm;
+#[cfg(test)]
+pub(crate) use proxmox_ticket::fresh_ticket;
 pub(crate) use proxmox_ticket::validate_ticket;
 pub(crate) use proxmox_uri::validate_url;
 pub(crate) use proxmox_username::validate_username;
@@ -0,0 +1,118 @@
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
+use std::fmt;
+
+/// A validated cluster node name (e.g., "pve1").
+///
+/// Node names are single DNS labels: 1-63 alphanumeric characters or hyphens, not starting or
+/// ending with a hyphen. Methods taking a node accept anything convertible into one, so
+/// `client.vms("pve1")` validates the name before any request is sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeName(String);
+
+impl NodeName {
+    /// Validates and wraps a node name.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if `name` is not a valid DNS label.
+    pub fn new(name: impl Into<String>) -> Result<Self, ProxmoxError> {
+        let name = name.into();
+        validate_node_name(&name).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(name))
+    }
+
+    /// Returns the name as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for NodeName {
+    type Error = ProxmoxError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<String> for NodeName {
+    type Error = ProxmoxError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<&String> for NodeName {
+    type Error = ProxmoxError;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        Self::new(value.as_str())
+    }
+}
+
+impl From<&NodeName> for NodeName {
+    fn from(value: &NodeName) -> Self {
+        value.clone()
+    }
+}
+
+impl AsRef<str> for NodeName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NodeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Validates a node name as a single DNS label.
+pub(crate) fn validate_node_name(name: &str) -> Result<(), ValidationError> {
+    let invalid = |message: &str| ValidationError::Field {
+        field: "node".to_string(),
+        message: message.to_string(),
+    };
+    if name.is_empty() || name.len() > 63 {
+        return Err(invalid("Node name must be 1-63 characters"));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(invalid(
+            "Node name can only contain alphanumeric characters and hyphens",
+        ));
+    }
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err(invalid("Node name cannot start or end with hyphen"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_name_valid() {
+        assert_eq!(NodeName::try_from("pve1").unwrap().as_str(), "pve1");
+        assert!(NodeName::new("pve-node-02").is_ok());
+    }
+
+    #[test]
+    fn test_node_name_invalid() {
+        for bad in ["", "pve1/", "pve 1", "-pve", "pve-", "pve1.example.com"] {
+            let err = NodeName::try_from(bad).unwrap_err();
+            assert!(
+                matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "node"),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+        assert!(NodeName::new("a".repeat(64)).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::core::domain::error::ValidationError;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, SystemTime};
+use zeroize::Zeroize;
 
 /// A Proxmox CSRF protection token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,8 +12,10 @@ pub struct ProxmoxCSRFToken {
 }
 
 impl ProxmoxCSRFToken {
-    /// Creates a new CSRF token without validation.
-    pub(crate) fn new_unchecked(value: String) -> Self {
+    /// Wraps a CSRF token without validating it, recording the current time as its creation
+    /// time.
+    #[must_use]
+    pub fn new_unchecked(value: String) -> Self {
         Self {
             value,
             created_at: SystemTime::now(),
@@ -31,6 +34,12 @@ impl ProxmoxCSRFToken {
         self.created_at
     }
 
+    /// Returns the time the token should be replaced, `lifetime` after its creation.
+    #[must_use]
+    pub fn expires_at(&self, lifetime: Duration) -> SystemTime {
+        self.created_at + lifetime
+    }
+
     /// Checks if the token is expired based on a given lifetime.
     #[must_use]
     pub fn is_expired(&self, lifetime: Duration) -> bool {
@@ -47,6 +56,19 @@ impl ProxmoxCSRFToken {
     }
 }
 
+impl Zeroize for ProxmoxCSRFToken {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for ProxmoxCSRFToken {
+    /// Wipes the token from memory when it goes out of scope.
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 /// Validates the format of a CSRF token string.
 pub(crate) fn validate_csrf_token(token: &str) -> Result<(), ValidationError> {
     if token.is_empty() {
@@ -83,6 +105,13 @@ mod tests {
     use super::*;
     use std::time::{Duration, SystemTime};
 
+    #[test]
+    fn test_zeroize_wipes_value() {
+        let mut secret = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        secret.zeroize();
+        assert!(secret.as_str().is_empty());
+    }
+
     #[test]
     fn test_validate_csrf_token_valid() {
         let valid = "4EEC61E2:lwk7od06fa1+DcPUwBTXCcndyAY/3mKxQp5vR8sNjWuBtL9fZg==";
@@ -110,6 +139,14 @@ mod tests {
         assert!(old_token.is_expired(Duration::from_secs(300)));
     }
 
+    #[test]
+    fn test_csrf_expires_at() {
+        let lifetime = Duration::from_secs(300);
+        let token = ProxmoxCSRFToken::new_unchecked("4EEC61E2:value".to_string());
+        assert_eq!(token.expires_at(lifetime), token.created_at() + lifetime);
+        assert!(token.expires_at(lifetime) > SystemTime::now());
+    }
+
     #[test]
     fn test_csrf_as_header() {
         let token = ProxmoxCSRFToken::new_unchecked("id:val".to_string());
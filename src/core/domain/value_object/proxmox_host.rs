@@ -1,12 +1,30 @@
-use crate::core::domain::error::ValidationError;
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
 
 /// A validated Proxmox hostname or IP address.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProxmoxHost(String);
 
 impl ProxmoxHost {
-    /// Creates a new host without validation (use builder for validation).
-    pub(crate) fn new_unchecked(host: String) -> Self {
+    /// Validates and wraps a hostname or IP address.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is not a valid hostname or IP address (no DNS lookup is performed).
+    pub fn new(host: impl Into<String>) -> Result<Self, ProxmoxError> {
+        let host = host.into();
+        validate_host(&host, false).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(host))
+    }
+
+    /// Wraps a host without validating it.
+    ///
+    /// Prefer [`new`](Self::new) unless the value is already known to be valid; a malformed host
+    /// only surfaces as a connection error.
+    #[must_use]
+    pub fn new_unchecked(host: String) -> Self {
         Self(host)
     }
 
@@ -1,13 +1,35 @@
-use crate::core::domain::error::ValidationError;
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
+use std::fmt;
+use zeroize::Zeroize;
 use zxcvbn::zxcvbn;
 
 /// A Proxmox password (plaintext, only stored temporarily).
-#[derive(Debug, Clone)]
+///
+/// The value is zeroized on drop and never printed by `Debug`.
+#[derive(Clone)]
 pub struct ProxmoxPassword(String);
 
 impl ProxmoxPassword {
-    /// Creates a new password without validation.
-    pub(crate) fn new_unchecked(password: String) -> Self {
+    /// Validates and wraps a password.
+    ///
+    /// No strength check is made; the builder applies one when
+    /// [`ValidationConfig`](crate::ValidationConfig) sets a minimum score.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the password is shorter than 8 or longer than 128 characters.
+    pub fn new(password: impl Into<String>) -> Result<Self, ProxmoxError> {
+        let password = password.into();
+        validate_password(&password, None).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(password))
+    }
+
+    /// Wraps a password without checking its length.
+    #[must_use]
+    pub fn new_unchecked(password: String) -> Self {
         Self(password)
     }
 
@@ -18,9 +40,30 @@ impl ProxmoxPassword {
     }
 
     /// Consumes the object and returns the inner string.
+    ///
+    /// The returned `String` is no longer wiped automatically.
     #[allow(unused)]
-    pub fn into_inner(self) -> String {
-        self.0
+    pub fn into_inner(mut self) -> String {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl fmt::Debug for ProxmoxPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProxmoxPassword(<redacted>)")
+    }
+}
+
+impl Zeroize for ProxmoxPassword {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for ProxmoxPassword {
+    /// Wipes the password from memory when it goes out of scope.
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
@@ -61,6 +104,21 @@ mod tests {
     use super::*;
     use zxcvbn::Score;
 
+    #[test]
+    fn test_debug_redacts_password() {
+        let password = ProxmoxPassword::new_unchecked("hunter2-secret".to_string());
+        let debug = format!("{:?}", password);
+        assert!(!debug.contains("hunter2-secret"));
+        assert_eq!(debug, "ProxmoxPassword(<redacted>)");
+    }
+
+    #[test]
+    fn test_zeroize_wipes_password() {
+        let mut password = ProxmoxPassword::new_unchecked("hunter2-secret".to_string());
+        password.zeroize();
+        assert!(password.as_str().is_empty());
+    }
+
     #[test]
     fn test_validate_password_valid() {
         assert!(validate_password("Str0ng!P@ss", None).is_ok());
@@ -1,12 +1,26 @@
-use crate::core::domain::error::ValidationError;
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
 
 /// A validated Proxmox port number.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ProxmoxPort(u16);
 
 impl ProxmoxPort {
-    /// Creates a new port without validation.
-    pub(crate) fn new_unchecked(port: u16) -> Self {
+    /// Validates and wraps a port number.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is not a usable port.
+    pub fn new(port: u16) -> Result<Self, ProxmoxError> {
+        validate_port(port).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(port))
+    }
+
+    /// Wraps a port without validating it (port 0 is accepted).
+    #[must_use]
+    pub fn new_unchecked(port: u16) -> Self {
         Self(port)
     }
 
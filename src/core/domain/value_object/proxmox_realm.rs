@@ -1,12 +1,27 @@
-use crate::core::domain::error::ValidationError;
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
 
 /// A validated Proxmox authentication realm.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProxmoxRealm(String);
 
 impl ProxmoxRealm {
-    /// Creates a new realm without validation.
-    pub(crate) fn new_unchecked(realm: String) -> Self {
+    /// Validates and wraps an authentication realm (e.g., "pam", "pve").
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is not a valid realm name.
+    pub fn new(realm: impl Into<String>) -> Result<Self, ProxmoxError> {
+        let realm = realm.into();
+        validate_realm(&realm).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(realm))
+    }
+
+    /// Wraps a realm without validating it.
+    #[must_use]
+    pub fn new_unchecked(realm: String) -> Self {
         Self(realm)
     }
 
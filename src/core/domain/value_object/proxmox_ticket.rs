@@ -1,6 +1,7 @@
 use crate::core::domain::error::ValidationError;
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zeroize::Zeroize;
 
 /// A Proxmox authentication ticket.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,12 +12,27 @@ pub struct ProxmoxTicket {
 }
 
 impl ProxmoxTicket {
-    /// Creates a new ticket without validation.
-    pub(crate) fn new_unchecked(value: String) -> Self {
-        Self {
-            value,
-            created_at: SystemTime::now(),
-        }
+    /// Wraps a ticket without validating it.
+    ///
+    /// The creation time is the issuance timestamp embedded in the ticket, falling back to
+    /// the current time if the ticket carries none. A timestamp ahead of the local clock
+    /// (server clock skew) is clamped to the current time.
+    ///
+    /// # Example
+    /// ```
+    /// use leeca_proxmox::{ProxmoxCSRFToken, ProxmoxTicket};
+    /// use std::time::Duration;
+    ///
+    /// let ticket = ProxmoxTicket::new_unchecked("PVE:monitor@pve:4EEC61E2::c2lnbmF0dXJl".to_string());
+    /// assert!(ticket.is_expired(Duration::from_secs(7200)));
+    /// let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:dG9rZW4".to_string());
+    /// assert_eq!(csrf.as_str(), "4EEC61E2:dG9rZW4");
+    /// ```
+    #[must_use]
+    pub fn new_unchecked(value: String) -> Self {
+        let now = SystemTime::now();
+        let created_at = issued_at(&value).map_or(now, |issued| issued.min(now));
+        Self { value, created_at }
     }
 
     /// Returns the ticket value as a string slice.
@@ -25,13 +41,22 @@ impl ProxmoxTicket {
         &self.value
     }
 
-    /// Returns the creation time.
+    /// Returns the time the server issued the ticket.
     #[must_use]
     pub fn created_at(&self) -> SystemTime {
         self.created_at
     }
 
-    /// Checks if the ticket is expired based on a given lifetime.
+    /// Returns the time the ticket stops being valid, `lifetime` after its issuance.
+    ///
+    /// Pass the same lifetime as to [`is_expired`](Self::is_expired) (usually
+    /// [`ValidationConfig::ticket_lifetime`](crate::ValidationConfig::ticket_lifetime)).
+    #[must_use]
+    pub fn expires_at(&self, lifetime: Duration) -> SystemTime {
+        self.created_at + lifetime
+    }
+
+    /// Checks if the ticket is older than `lifetime`, counting from its issuance.
     #[must_use]
     pub fn is_expired(&self, lifetime: Duration) -> bool {
         self.created_at
@@ -40,6 +65,11 @@ impl ProxmoxTicket {
             .unwrap_or(true)
     }
 
+    /// Returns the `(username, realm)` the ticket was issued for, if it is well-formed.
+    pub(crate) fn user_and_realm(&self) -> Option<(&str, &str)> {
+        self.value.split(':').nth(1)?.rsplit_once('@')
+    }
+
     /// Formats the ticket as a cookie header.
     #[must_use]
     pub fn as_cookie_header(&self) -> String {
@@ -47,6 +77,37 @@ impl ProxmoxTicket {
     }
 }
 
+impl Zeroize for ProxmoxTicket {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for ProxmoxTicket {
+    /// Wipes the ticket from memory when it goes out of scope.
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Parses the issuance time from `PVE:user@realm:TIMESTAMP::signature`, where the timestamp
+/// is seconds since the Unix epoch in hexadecimal.
+fn issued_at(ticket: &str) -> Option<SystemTime> {
+    let timestamp = ticket.split(':').nth(2)?;
+    let secs = u64::from_str_radix(timestamp, 16).ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Builds a well-formed ticket value issued now, for tests that need an unexpired ticket.
+#[cfg(test)]
+pub(crate) fn fresh_ticket(user_realm: &str, signature: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!("PVE:{}:{:08X}::{}", user_realm, now, signature)
+}
+
 /// Validates the format of a ticket string.
 pub(crate) fn validate_ticket(ticket: &str) -> Result<(), ValidationError> {
     if ticket.is_empty() {
@@ -76,7 +137,13 @@ pub(crate) fn validate_ticket(ticket: &str) -> Result<(), ValidationError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_zeroize_wipes_value() {
+        let mut secret = ProxmoxTicket::new_unchecked("PVE:user@pam:4EEC61E2::sig".to_string());
+        secret.zeroize();
+        assert!(secret.as_str().is_empty());
+    }
 
     #[test]
     fn test_validate_ticket_valid() {
@@ -94,7 +161,7 @@ mod tests {
 
     #[test]
     fn test_ticket_expiration() {
-        let ticket = ProxmoxTicket::new_unchecked("PVE:user@pam:4EEC61E2::sig".to_string());
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("user@pam", "sig"));
         assert!(!ticket.is_expired(Duration::from_secs(7200))); // fresh
 
         let old_ticket = ProxmoxTicket {
@@ -104,6 +171,46 @@ mod tests {
         assert!(old_ticket.is_expired(Duration::from_secs(7200)));
     }
 
+    #[test]
+    fn test_ticket_created_at_uses_embedded_timestamp() {
+        // 0x4EEC61E2 = 1_324_114_402 (2011-12-17T09:33:22Z).
+        let ticket = ProxmoxTicket::new_unchecked("PVE:user@pam:4EEC61E2::sig".to_string());
+        assert_eq!(
+            ticket.created_at(),
+            UNIX_EPOCH + Duration::from_secs(1_324_114_402)
+        );
+        assert!(ticket.is_expired(Duration::from_secs(7200)));
+
+        let issued = SystemTime::now() - Duration::from_secs(3600);
+        let secs = issued.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let value = format!("PVE:user@pam:{:X}::sig", secs);
+        let ticket = ProxmoxTicket::new_unchecked(value);
+        assert!(!ticket.is_expired(Duration::from_secs(7200)));
+        assert!(ticket.is_expired(Duration::from_secs(1800)));
+    }
+
+    #[test]
+    fn test_ticket_created_at_without_timestamp_or_from_future() {
+        let before = SystemTime::now();
+        let plain = ProxmoxTicket::new_unchecked("PVE:ticket".to_string());
+        assert!(plain.created_at() >= before);
+
+        let skewed = ProxmoxTicket::new_unchecked("PVE:user@pam:FFFFFFFF::sig".to_string());
+        assert!(skewed.created_at() <= SystemTime::now());
+        assert!(!skewed.is_expired(Duration::from_secs(7200)));
+    }
+
+    #[test]
+    fn test_ticket_expires_at() {
+        let lifetime = Duration::from_secs(7200);
+        let ticket = ProxmoxTicket::new_unchecked("PVE:user@pam:4EEC61E2::sig".to_string());
+        assert_eq!(ticket.expires_at(lifetime), ticket.created_at() + lifetime);
+        assert!(ticket.expires_at(lifetime) < SystemTime::now());
+
+        let fresh = ProxmoxTicket::new_unchecked(fresh_ticket("user@pam", "sig"));
+        assert!(fresh.expires_at(lifetime) > SystemTime::now());
+    }
+
     #[test]
     fn test_ticket_as_cookie_header() {
         let ticket = ProxmoxTicket::new_unchecked("PVE:ticket".to_string());
@@ -1,13 +1,30 @@
-use crate::core::domain::error::ValidationError;
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
 use url::Url;
 
 /// A validated Proxmox API URL.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProxmoxUrl(String);
 
 impl ProxmoxUrl {
-    /// Creates a new URL without validation.
-    pub(crate) fn new_unchecked(url: String) -> Self {
+    /// Validates and wraps the base URL of the server (e.g., "https://pve:8006/").
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is not a valid URL.
+    pub fn new(url: impl Into<String>) -> Result<Self, ProxmoxError> {
+        let url = url.into();
+        validate_url(&url).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(url))
+    }
+
+    /// Wraps a URL without parsing it.
+    ///
+    /// The value is used as the API base as given, so it must be an absolute URL ending in `/`.
+    #[must_use]
+    pub fn new_unchecked(url: String) -> Self {
         Self(url)
     }
 
@@ -1,12 +1,33 @@
-use crate::core::domain::error::ValidationError;
+use crate::core::domain::error::{ProxmoxError, ValidationError};
+use std::backtrace::Backtrace;
 
 /// A validated Proxmox username.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ProxmoxUsername(String);
 
 impl ProxmoxUsername {
-    /// Creates a new username without validation.
-    pub(crate) fn new_unchecked(username: String) -> Self {
+    /// Validates and wraps a username (without the realm).
+    ///
+    /// Reserved names such as "root" are accepted; the builder rejects them only when
+    /// [`ValidationConfig`](crate::ValidationConfig) asks it to.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the value is too short, too long or contains invalid characters.
+    pub fn new(username: impl Into<String>) -> Result<Self, ProxmoxError> {
+        let username = username.into();
+        validate_username(&username, false).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(username))
+    }
+
+    /// Wraps a username without validating it.
+    ///
+    /// Prefer [`new`](Self::new) unless the value is already known to be valid; an invalid
+    /// name only surfaces as a failed login.
+    #[must_use]
+    pub fn new_unchecked(username: String) -> Self {
         Self(username)
     }
 
@@ -27,3 +27,221 @@ pub mod system_time {
         Ok(UNIX_EPOCH + Duration::from_secs(secs))
     }
 }
+
+/// Deserialization for flags that Proxmox reports either as JSON booleans or as `0`/`1`.
+pub mod int_bool {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum IntOrBool {
+        Bool(bool),
+        Int(i64),
+    }
+
+    impl From<IntOrBool> for bool {
+        fn from(value: IntOrBool) -> Self {
+            match value {
+                IntOrBool::Bool(b) => b,
+                IntOrBool::Int(i) => i != 0,
+            }
+        }
+    }
+
+    /// Deserialize a `bool` from either a boolean or an integer (non-zero is `true`).
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<bool, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        IntOrBool::deserialize(deserializer).map(bool::from)
+    }
+}
+
+/// Like [`int_bool`], for flags that may be absent or `null`.
+pub mod option_int_bool {
+    use super::int_bool::IntOrBool;
+    use super::*;
+
+    /// Deserialize an optional `bool` from a boolean, an integer or `null`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<IntOrBool>::deserialize(deserializer)?.map(bool::from))
+    }
+}
+
+/// Deserialization for numbers that Proxmox sometimes reports as JSON strings (e.g., `"5900"`).
+pub mod string_or_number {
+    use super::*;
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString<T> {
+        Number(T),
+        String(String),
+    }
+
+    /// Deserialize a number from either a JSON number or a numeric string.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + FromStr,
+        T::Err: Display,
+    {
+        match NumberOrString::<T>::deserialize(deserializer)? {
+            NumberOrString::Number(n) => Ok(n),
+            NumberOrString::String(s) => s.trim().parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Deserialization for optional numbers that Proxmox sometimes reports as JSON strings
+/// (e.g., `"cpu": "0.15"`, `"maxmem": "8589934592"`).
+pub mod option_string_or_number {
+    use super::*;
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString<T> {
+        Number(T),
+        String(String),
+    }
+
+    /// Deserialize an optional number from a JSON number, a numeric string or `null`; an
+    /// empty string is treated as absent.
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + FromStr,
+        T::Err: Display,
+    {
+        match Option::<NumberOrString<T>>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(NumberOrString::Number(n)) => Ok(Some(n)),
+            Some(NumberOrString::String(s)) if s.trim().is_empty() => Ok(None),
+            Some(NumberOrString::String(s)) => {
+                s.trim().parse().map(Some).map_err(serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+/// Deserialization for load averages, which Proxmox reports as numbers, numeric strings or
+/// one space-separated string depending on the version.
+pub mod loadavg {
+    use super::*;
+    use serde::de::Error;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Number(f64),
+        String(String),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum LoadAvg {
+        List(Vec<Value>),
+        Joined(String),
+    }
+
+    /// Deserialize `[1, 5, 15]`-minute load averages from `[0.5, 0.3, 0.1]`,
+    /// `["0.50", "0.30", "0.10"]` or `"0.50 0.30 0.10"`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<[f64; 3]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let parse = |s: &str| s.trim().parse::<f64>().map_err(D::Error::custom);
+        let values = match Option::<LoadAvg>::deserialize(deserializer)? {
+            None => return Ok(None),
+            Some(LoadAvg::List(values)) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Number(n) => Ok(n),
+                    Value::String(s) => parse(&s),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(LoadAvg::Joined(joined)) => joined
+                .split_whitespace()
+                .map(parse)
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        <[f64; 3]>::try_from(values).map(Some).map_err(|values| {
+            D::Error::custom(format!("expected 3 load averages, got {}", values.len()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Load {
+        #[serde(default, deserialize_with = "loadavg::deserialize")]
+        loadavg: Option<[f64; 3]>,
+    }
+
+    fn parse_load(json: serde_json::Value) -> Result<Option<[f64; 3]>, serde_json::Error> {
+        serde_json::from_value::<Load>(json).map(|load| load.loadavg)
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Usage {
+        #[serde(default, deserialize_with = "option_string_or_number::deserialize")]
+        cpu: Option<f64>,
+        #[serde(default, deserialize_with = "option_string_or_number::deserialize")]
+        maxmem: Option<u64>,
+    }
+
+    #[test]
+    fn test_option_string_or_number() {
+        let usage: Usage =
+            serde_json::from_value(serde_json::json!({ "cpu": "0.15", "maxmem": "8589934592" }))
+                .unwrap();
+        assert_eq!((usage.cpu, usage.maxmem), (Some(0.15), Some(8_589_934_592)));
+
+        let usage: Usage =
+            serde_json::from_value(serde_json::json!({ "cpu": 0.15, "maxmem": null })).unwrap();
+        assert_eq!((usage.cpu, usage.maxmem), (Some(0.15), None));
+
+        let usage: Usage = serde_json::from_value(serde_json::json!({ "maxmem": "" })).unwrap();
+        assert_eq!((usage.cpu, usage.maxmem), (None, None));
+
+        assert!(serde_json::from_value::<Usage>(serde_json::json!({ "maxmem": "8 GiB" })).is_err());
+    }
+
+    #[test]
+    fn test_loadavg_representations() {
+        let expected = Some([0.5, 0.3, 0.1]);
+        assert_eq!(
+            parse_load(serde_json::json!({ "loadavg": [0.5, 0.3, 0.1] })).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_load(serde_json::json!({ "loadavg": ["0.50", "0.30", "0.10"] })).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_load(serde_json::json!({ "loadavg": "0.50 0.30 0.10" })).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_load(serde_json::json!({ "loadavg": null })).unwrap(),
+            None
+        );
+        assert_eq!(parse_load(serde_json::json!({})).unwrap(), None);
+    }
+
+    #[test]
+    fn test_loadavg_rejects_malformed_values() {
+        assert!(parse_load(serde_json::json!({ "loadavg": [0.5, 0.3] })).is_err());
+        assert!(parse_load(serde_json::json!({ "loadavg": "0.50 high 0.10" })).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::core::domain::error::ProxmoxError;
+use crate::core::domain::model::vm::{validate_vmid, vmid_out_of_range};
+use std::backtrace::Backtrace;
+use std::fmt;
+
+/// A validated guest identifier in the range Proxmox accepts
+/// ([`MIN_VMID`](crate::MIN_VMID)..=[`MAX_VMID`](crate::MAX_VMID)).
+///
+/// Methods taking a VM identifier accept anything convertible into one, so
+/// `client.start_vm("pve1", 100)` validates the identifier before any request is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VmId(u32);
+
+impl VmId {
+    /// Validates and wraps a VM identifier.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if `vmid` is out of range.
+    pub fn new(vmid: u32) -> Result<Self, ProxmoxError> {
+        validate_vmid(vmid).map_err(|source| ProxmoxError::Validation {
+            source,
+            backtrace: Backtrace::capture(),
+        })?;
+        Ok(Self(vmid))
+    }
+
+    /// Returns the identifier.
+    #[must_use]
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for VmId {
+    type Error = ProxmoxError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+// Integer literals default to `i32`, so `client.start_vm("pve1", 100)` goes through this impl.
+impl TryFrom<i32> for VmId {
+    type Error = ProxmoxError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        u32::try_from(value)
+            .map_err(|_| out_of_range(value))
+            .and_then(Self::new)
+    }
+}
+
+impl TryFrom<u64> for VmId {
+    type Error = ProxmoxError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        u32::try_from(value)
+            .map_err(|_| out_of_range(value))
+            .and_then(Self::new)
+    }
+}
+
+/// Reports a value that does not even fit a `u32`, keeping the original in the message.
+fn out_of_range(value: impl fmt::Display) -> ProxmoxError {
+    ProxmoxError::Validation {
+        source: vmid_out_of_range(value),
+        backtrace: Backtrace::capture(),
+    }
+}
+
+impl From<&VmId> for VmId {
+    fn from(value: &VmId) -> Self {
+        *value
+    }
+}
+
+impl From<VmId> for u32 {
+    fn from(value: VmId) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for VmId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidationError;
+
+    #[test]
+    fn test_vm_id_range() {
+        assert_eq!(VmId::try_from(100).unwrap().get(), 100);
+        assert!(VmId::new(999_999_999).is_ok());
+        for bad in [0, 99, 1_000_000_000] {
+            let err = VmId::try_from(bad).unwrap_err();
+            assert!(
+                matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "vmid")
+            );
+        }
+    }
+
+    #[test]
+    fn test_vm_id_error_quotes_original_value() {
+        let err = VmId::try_from(-5i32).unwrap_err();
+        assert!(err.to_string().contains("(got -5)"), "{}", err);
+        let err = VmId::try_from(5_000_000_000u64).unwrap_err();
+        assert!(err.to_string().contains("(got 5000000000)"), "{}", err);
+        assert!(
+            matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "vmid")
+        );
+    }
+}
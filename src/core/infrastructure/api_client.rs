@@ -1,15 +1,26 @@
 //! Internal HTTP client that handles authentication and automatic ticket refresh.
 
 use crate::{
-    ProxmoxAuth, ProxmoxConnection, ProxmoxError, ProxmoxResult, ValidationConfig,
+    ApiStyle, ProxmoxAuth, ProxmoxConnection, ProxmoxError, ProxmoxResult, RateLimitConfig,
+    ValidationConfig, ValidationError,
     auth::application::service::login_service::LoginService,
+    core::domain::model::cached_response::CachedResponse,
+    core::infrastructure::response_cache::ResponseCache,
+    metrics::{Metrics, api_path},
+    middleware::{MiddlewareChain, RequestParts, ResponseParts},
 };
 use governor::{DefaultDirectRateLimiter, Quota};
-use reqwest::{Client, StatusCode};
+use reqwest::{
+    Client, StatusCode,
+    header::{HeaderMap, HeaderValue},
+};
 use serde::de::DeserializeOwned;
+use std::backtrace::Backtrace;
 use std::num::NonZeroU32;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{Mutex, RwLock, Semaphore};
 
 /// Wrapper for Proxmox API responses that contain a `data` field.
 ///
@@ -23,18 +34,224 @@ struct ProxmoxResponse<T> {
     data: T,
 }
 
+/// Result fields of an ExtJS-style response (`/api2/extjs`), which carry the outcome in the
+/// body rather than in the HTTP status.
+#[derive(Debug, serde::Deserialize)]
+struct ExtJsOutcome {
+    #[serde(
+        deserialize_with = "crate::core::domain::value_object::serde_helpers::int_bool::deserialize"
+    )]
+    success: bool,
+    #[serde(default)]
+    message: Option<String>,
+    /// The HTTP status the JSON endpoint would have answered with.
+    #[serde(default)]
+    status: Option<u16>,
+    /// Per-parameter messages of a failed parameter verification.
+    #[serde(default)]
+    errors: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ExtJsOutcome {
+    fn into_error(self) -> ProxmoxError {
+        let status = self
+            .status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut message = self.message.unwrap_or_default().trim_end().to_string();
+        if let Some(errors) = self.errors.filter(|errors| !errors.is_empty()) {
+            message.push_str(&format!(" {}", serde_json::Value::Object(errors)));
+        }
+        api_error("API error", status, message)
+    }
+}
+
+/// Messages Proxmox uses when a `digest` no longer matches the stored configuration.
+const DIGEST_MISMATCH_MARKERS: [&str; 2] = ["detected modified configuration", "digest mismatch"];
+
+/// Base delay between repeated ticket refreshes; the n-th extra retry waits n times this.
+const AUTH_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Error message for a mutation attempted without a CSRF token.
+const READ_ONLY_SESSION: &str = "read-only session";
+
+/// How a request body is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BodyEncoding {
+    /// `application/json`.
+    Json,
+    /// `application/x-www-form-urlencoded`, as sent by `pvesh` and the web UI.
+    Form,
+}
+
+/// Returns `true` for the methods Proxmox protects with the CSRF token.
+fn is_state_changing(method: &reqwest::Method) -> bool {
+    matches!(
+        *method,
+        reqwest::Method::POST | reqwest::Method::PUT | reqwest::Method::DELETE
+    )
+}
+
+/// Maps an unsuccessful response to an error, reporting digest mismatches as
+/// `ProxmoxError::Conflict`.
+fn api_error(prefix: &str, status: StatusCode, body: String) -> ProxmoxError {
+    let lower = body.to_lowercase();
+    if DIGEST_MISMATCH_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+    {
+        return ProxmoxError::Conflict(body);
+    }
+    ProxmoxError::Api {
+        status: status.as_u16(),
+        message: format!("{} ({}): {}", prefix, status, body),
+    }
+}
+
+/// Builds the limiter for `config`, rejecting a zero rate or burst size.
+fn rate_limiter(config: RateLimitConfig) -> ProxmoxResult<Arc<DefaultDirectRateLimiter>> {
+    let non_zero = |value: u32, name: &str| {
+        NonZeroU32::new(value).ok_or_else(|| ProxmoxError::Validation {
+            source: ValidationError::Field {
+                field: "rate_limit".to_string(),
+                message: format!("{} must be at least 1", name),
+            },
+            backtrace: Backtrace::capture(),
+        })
+    };
+    let quota = Quota::per_second(non_zero(config.requests_per_second, "Requests per second")?)
+        .allow_burst(non_zero(config.burst_size, "Burst size")?);
+    Ok(Arc::new(DefaultDirectRateLimiter::direct(quota)))
+}
+
+/// User-Agent sent when the caller does not configure one.
+pub(crate) const DEFAULT_USER_AGENT: &str = concat!("leeca_proxmox/", env!("CARGO_PKG_VERSION"));
+
+/// Cloudflare Access service token sent with every request.
+#[derive(Clone)]
+pub(crate) struct CloudflareAccess {
+    client_id: String,
+    client_secret: Option<String>,
+}
+
+impl CloudflareAccess {
+    pub(crate) fn new(client_id: String, client_secret: String) -> Self {
+        Self {
+            client_id,
+            client_secret: Some(client_secret),
+        }
+    }
+
+    /// Reads `CF_ACCESS_CLIENT_ID` and `CF_ACCESS_CLIENT_SECRET`; the secret is only used
+    /// together with a non-empty client id.
+    pub(crate) fn from_env() -> Option<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Builds the token from `lookup`; the client id is sent exactly as given.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let client_id = lookup("CF_ACCESS_CLIENT_ID").filter(|id| !id.is_empty())?;
+        let client_secret = lookup("CF_ACCESS_CLIENT_SECRET").filter(|secret| !secret.is_empty());
+        Some(Self {
+            client_id,
+            client_secret,
+        })
+    }
+
+    fn headers(&self) -> ProxmoxResult<HeaderMap> {
+        let value = |name: &str, raw: &str| {
+            HeaderValue::from_str(raw).map_err(|_| ProxmoxError::Validation {
+                source: ValidationError::Field {
+                    field: "cloudflare_access".to_string(),
+                    message: format!("{} is not a valid header value", name),
+                },
+                backtrace: Backtrace::capture(),
+            })
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert("CF-Access-Client-Id", value("Client id", &self.client_id)?);
+        if let Some(secret) = &self.client_secret {
+            let mut secret = value("Client secret", secret)?;
+            secret.set_sensitive(true);
+            headers.insert("CF-Access-Client-Secret", secret);
+        }
+        Ok(headers)
+    }
+}
+
+impl std::fmt::Debug for CloudflareAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CloudflareAccess")
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+/// HTTP settings chosen on the builder.
+#[derive(Debug, Default)]
+pub(crate) struct HttpOptions {
+    /// Overrides [`DEFAULT_USER_AGENT`].
+    pub(crate) user_agent: Option<String>,
+    /// Overrides the Cloudflare Access token from the environment.
+    pub(crate) cloudflare_access: Option<CloudflareAccess>,
+    /// Applied last, so it can override everything else.
+    pub(crate) customizer: Option<HttpCustomizer>,
+    /// Hooks run around every API request.
+    pub(crate) middleware: MiddlewareChain,
+    /// Receives a measurement for every API request.
+    pub(crate) metrics: Option<Arc<dyn Metrics>>,
+}
+
+/// A caller-supplied adjustment applied to the `reqwest::ClientBuilder` before it is built.
+pub(crate) struct HttpCustomizer(
+    Box<dyn FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder + Send>,
+);
+
+impl HttpCustomizer {
+    pub(crate) fn new(
+        customize: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder + Send + 'static,
+    ) -> Self {
+        Self(Box::new(customize))
+    }
+}
+
+impl std::fmt::Debug for HttpCustomizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HttpCustomizer(..)")
+    }
+}
+
 /// Internal HTTP client that manages authentication and provides methods to call the Proxmox API.
 ///
 /// This client automatically adds the necessary authentication headers (`PVEAuthCookie` and
 /// `CSRFPreventionToken`) to each request. If a request receives a `401 Unauthorized` response,
 /// it attempts to refresh the ticket once using the stored credentials and retries the request.
-#[derive(Debug)]
+///
+/// Clones share the authentication state, connection pool, rate limiter and concurrency
+/// limit.
+///
+/// Only one ticket refresh runs at a time: requests that are rejected while a refresh is in
+/// flight wait for it and reuse its ticket instead of logging in again.
+#[derive(Debug, Clone)]
 pub struct ApiClient {
     http_client: Client,
     connection: Arc<ProxmoxConnection>,
     auth: Arc<RwLock<Option<ProxmoxAuth>>>,
+    /// Bumped whenever `auth` is replaced, so a waiting refresh can tell it is no longer needed.
+    auth_generation: Arc<AtomicU64>,
+    /// Held while logging in to refresh the ticket.
+    refresh_lock: Arc<Mutex<()>>,
     config: Arc<ValidationConfig>,
     rate_limiter: Option<Arc<DefaultDirectRateLimiter>>,
+    /// Caps the number of requests in flight at once.
+    concurrency: Option<Arc<Semaphore>>,
+    middleware: MiddlewareChain,
+    metrics: Option<Arc<dyn Metrics>>,
+    cache: Option<Arc<ResponseCache>>,
 }
 
 impl ApiClient {
@@ -42,24 +259,63 @@ impl ApiClient {
     ///
     /// # Errors
     /// Returns `ProxmoxError::Connection` if the HTTP client cannot be built.
+    #[cfg(test)]
     pub fn new(connection: ProxmoxConnection, config: ValidationConfig) -> ProxmoxResult<Self> {
-        let http_client = Client::builder()
+        Self::with_options(connection, config, HttpOptions::default())
+    }
+
+    /// Creates a new `ApiClient` with the builder's HTTP settings. A custom
+    /// [`HttpCustomizer`] runs after the crate's own TLS, User-Agent and Cloudflare Access
+    /// settings.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the Cloudflare Access token is not a valid header
+    /// value or the rate limit is zero, or `ProxmoxError::Connection` if the HTTP client cannot
+    /// be built.
+    pub(crate) fn with_options(
+        connection: ProxmoxConnection,
+        config: ValidationConfig,
+        options: HttpOptions,
+    ) -> ProxmoxResult<Self> {
+        let user_agent = options
+            .user_agent
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        let mut builder = Client::builder()
             .danger_accept_invalid_certs(connection.accept_invalid_certs())
+            .user_agent(user_agent.as_str());
+        if let Some(access) = options
+            .cloudflare_access
+            .or_else(CloudflareAccess::from_env)
+        {
+            builder = builder.default_headers(access.headers()?);
+        }
+        if let Some(HttpCustomizer(customize)) = options.customizer {
+            builder = customize(builder);
+        }
+        let http_client = builder
             .build()
             .map_err(|e| ProxmoxError::Connection(e.to_string()))?;
 
-        let rate_limiter = config.rate_limit.map(|rl| {
-            let quota = Quota::per_second(NonZeroU32::new(rl.requests_per_second).unwrap())
-                .allow_burst(NonZeroU32::new(rl.burst_size).unwrap());
-            Arc::new(DefaultDirectRateLimiter::direct(quota))
-        });
+        let rate_limiter = config.rate_limit.map(rate_limiter).transpose()?;
+        let concurrency = config
+            .max_concurrent_requests
+            .map(|limit| Arc::new(Semaphore::new(limit.max(1))));
+        let cache = config
+            .cache
+            .map(|cache| Arc::new(ResponseCache::new(cache.ttl)));
 
         Ok(Self {
             http_client,
             connection: Arc::new(connection),
             auth: Arc::new(RwLock::new(None)),
+            auth_generation: Arc::new(AtomicU64::new(0)),
+            refresh_lock: Arc::new(Mutex::new(())),
             config: Arc::new(config),
             rate_limiter,
+            concurrency,
+            middleware: options.middleware,
+            metrics: options.metrics,
+            cache,
         })
     }
 
@@ -68,10 +324,25 @@ impl ApiClient {
         &self.connection
     }
 
+    /// Logs in with the stored credentials over this client's HTTP connection pool and
+    /// returns the new authentication state without storing it.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError` if the login fails.
+    pub(crate) async fn login(&self) -> ProxmoxResult<ProxmoxAuth> {
+        LoginService::new()
+            .execute(&self.http_client, &self.connection)
+            .await
+    }
+
     /// Sets the authentication state (used after a successful login or session restore).
     pub async fn set_auth(&self, auth: ProxmoxAuth) {
-        let mut lock = self.auth.write().await;
-        *lock = Some(auth);
+        self.replace_auth(Some(auth)).await;
+    }
+
+    /// Discards the authentication state; the ticket and CSRF token are wiped on drop.
+    pub async fn clear_auth(&self) {
+        self.replace_auth(None).await;
     }
 
     /// Returns the current authentication state, if any.
@@ -87,8 +358,38 @@ impl ApiClient {
             .unwrap_or(false)
     }
 
+    /// Returns `true` if the session has a ticket but no CSRF token, so mutations are refused.
+    pub async fn is_read_only(&self) -> bool {
+        self.auth
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(ProxmoxAuth::is_read_only)
+    }
+
+    /// Probes the unauthenticated `/version` endpoint to check that the server is reachable.
+    ///
+    /// Any HTTP response (including `401 Unauthorized`) counts as reachable; only transport
+    /// failures such as refused connections, DNS errors or TLS handshake failures are reported.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Connection` if the server cannot be reached.
+    pub async fn probe(&self) -> ProxmoxResult<()> {
+        let url = self.api_url("version");
+        self.http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ProxmoxError::Connection(format!("Server unreachable: {}", e)))?;
+        Ok(())
+    }
+
     /// Performs an authenticated GET request.
     ///
+    /// With a [`CacheConfig`](crate::CacheConfig), a response younger than its TTL is served
+    /// without contacting the server. Task, guest power state and agent process status are
+    /// always fetched, so polling loops see changes.
+    ///
     /// # Type Parameters
     /// - `T`: The expected response type (must implement `DeserializeOwned`).
     ///
@@ -100,8 +401,42 @@ impl ApiClient {
     where
         T: DeserializeOwned,
     {
-        self.execute_request(reqwest::Method::GET, path, None::<&()>)
-            .await
+        self.get_cached(path).await.map(|response| response.data)
+    }
+
+    /// Performs an authenticated GET request like [`get`](Self::get), also returning when the
+    /// response was fetched from the server.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError` as for [`get`](Self::get).
+    pub async fn get_cached<T>(&self, path: &str) -> ProxmoxResult<CachedResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| ResponseCache::is_cacheable(path))
+        else {
+            let data = self
+                .execute_request(reqwest::Method::GET, path, None::<&()>, BodyEncoding::Json)
+                .await?;
+            return Ok(CachedResponse::new(data, SystemTime::now()));
+        };
+        let response = match cache.get(path).await {
+            Some(response) => response,
+            None => {
+                let data: serde_json::Value = self
+                    .execute_request(reqwest::Method::GET, path, None::<&()>, BodyEncoding::Json)
+                    .await?;
+                let fetched_at = SystemTime::now();
+                cache.insert(path, data.clone(), fetched_at).await;
+                CachedResponse::new(data, fetched_at)
+            }
+        };
+        let data = serde_json::from_value(response.data)
+            .map_err(|e| ProxmoxError::http("Failed to parse response", e))?;
+        Ok(CachedResponse::new(data, response.fetched_at))
     }
 
     /// Performs an authenticated POST request with a JSON body.
@@ -119,7 +454,7 @@ impl ApiClient {
         B: serde::Serialize,
         T: DeserializeOwned,
     {
-        self.execute_request(reqwest::Method::POST, path, Some(body))
+        self.execute_request(reqwest::Method::POST, path, Some(body), BodyEncoding::Json)
             .await
     }
 
@@ -138,7 +473,37 @@ impl ApiClient {
         B: serde::Serialize,
         T: DeserializeOwned,
     {
-        self.execute_request(reqwest::Method::PUT, path, Some(body))
+        self.execute_request(reqwest::Method::PUT, path, Some(body), BodyEncoding::Json)
+            .await
+    }
+
+    /// Performs an authenticated POST request with a form-encoded body.
+    ///
+    /// The body must serialize to flat key/value pairs (nested values are rejected).
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Http` if the body cannot be form-encoded, or another
+    /// `ProxmoxError` as for [`post`](Self::post).
+    pub async fn post_form<B, T>(&self, path: &str, body: &B) -> ProxmoxResult<T>
+    where
+        B: serde::Serialize,
+        T: DeserializeOwned,
+    {
+        self.execute_request(reqwest::Method::POST, path, Some(body), BodyEncoding::Form)
+            .await
+    }
+
+    /// Performs an authenticated PUT request with a form-encoded body.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Http` if the body cannot be form-encoded, or another
+    /// `ProxmoxError` as for [`put`](Self::put).
+    pub async fn put_form<B, T>(&self, path: &str, body: &B) -> ProxmoxResult<T>
+    where
+        B: serde::Serialize,
+        T: DeserializeOwned,
+    {
+        self.execute_request(reqwest::Method::PUT, path, Some(body), BodyEncoding::Form)
             .await
     }
 
@@ -155,168 +520,352 @@ impl ApiClient {
     where
         T: DeserializeOwned,
     {
-        self.execute_request(reqwest::Method::DELETE, path, None::<&()>)
-            .await
+        self.execute_request(
+            reqwest::Method::DELETE,
+            path,
+            None::<&()>,
+            BodyEncoding::Json,
+        )
+        .await
     }
 
-    /// Core request execution method. It ensures authentication, sends the request,
-    /// handles 401 by refreshing once, and parses the response.
-    async fn execute_request<B, T>(
+    /// Performs an authenticated multipart POST request (e.g., a file upload).
+    ///
+    /// A streamed body cannot be sent twice, so a `401 Unauthorized` is not retried: the
+    /// ticket is refreshed for the next call and the upload fails with
+    /// `ProxmoxError::Authentication`.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError` if the request fails, authentication cannot be refreshed,
+    /// or the response cannot be parsed.
+    pub async fn post_multipart<T>(
         &self,
-        method: reqwest::Method,
         path: &str,
-        body: Option<&B>,
+        form: reqwest::multipart::Form,
     ) -> ProxmoxResult<T>
     where
-        B: serde::Serialize,
         T: DeserializeOwned,
     {
-        // Ensure we have a valid ticket (refresh if needed)
         self.ensure_authenticated().await?;
-
-        // Apply rate limiting if enabled
         if let Some(limiter) = &self.rate_limiter {
-            // `until_ready()` returns a future that completes when capacity is available.
             limiter.until_ready().await;
         }
+        let _permit = self.acquire_slot().await;
 
-        // Build the full URL
-        let base = self.connection.url().as_str().trim_end_matches('/');
-        let url = format!("{}/api2/json/{}", base, path.trim_start_matches('/'));
-
-        // Start building the request (clone method to avoid move)
-        let mut req_builder = self.http_client.request(method.clone(), &url);
-
-        // Add authentication headers
-        {
-            let auth_guard = self.auth.read().await;
-            if let Some(auth) = auth_guard.as_ref() {
-                req_builder = req_builder
-                    .header("Cookie", auth.ticket().as_cookie_header())
-                    .header("CSRFPreventionToken", auth.csrf_token().unwrap().as_str());
-            }
-        }
-
-        // Add body if present
-        if let Some(body) = body {
-            req_builder = req_builder.json(body);
-        }
+        let url = self.api_url(path);
+        let (req_builder, generation) = self
+            .with_auth_headers(self.http_client.post(&url), &reqwest::Method::POST)
+            .await?;
 
-        // Send the request
-        let response = req_builder
-            .send()
+        let response = self
+            .send(req_builder.multipart(form))
             .await
-            .map_err(|e| ProxmoxError::Connection(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| ProxmoxError::http("HTTP request failed", e))?;
+        self.invalidate_cache(path).await;
 
-        // Handle 401 Unauthorized: refresh once and retry
         if response.status() == StatusCode::UNAUTHORIZED {
-            self.refresh_auth().await?;
-            // Retry exactly once (no further recursion)
-            return self.retry_request(method, path, body).await;
+            self.refresh_auth(generation).await?;
+            return Err(ProxmoxError::Authentication(
+                "Upload was rejected with an expired ticket; the ticket has been refreshed, \
+                 retry the upload"
+                    .to_string(),
+            ));
         }
-
-        // Handle other HTTP errors
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "unknown".to_string());
-            return Err(ProxmoxError::Connection(format!(
-                "API error ({}): {}",
-                status, error_text
-            )));
+            return Err(api_error("API error", status, error_text));
         }
 
-        // Parse successful response, extracting the `data` field
-        let proxmox_resp = response
-            .json::<ProxmoxResponse<T>>()
-            .await
-            .map_err(|e| ProxmoxError::Connection(format!("Failed to parse response: {}", e)))?;
-        Ok(proxmox_resp.data)
+        self.parse_response(response).await
     }
 
-    /// Retry a request after a successful token refresh. This method avoids recursion.
-    async fn retry_request<B, T>(
+    /// Core request execution method. It ensures authentication, sends the request,
+    /// handles 401 by refreshing up to `max_auth_retries` times, and parses the response.
+    async fn execute_request<B, T>(
         &self,
         method: reqwest::Method,
         path: &str,
         body: Option<&B>,
+        encoding: BodyEncoding,
     ) -> ProxmoxResult<T>
     where
         B: serde::Serialize,
         T: DeserializeOwned,
     {
-        let base = self.connection.url().as_str().trim_end_matches('/');
-        let url = format!("{}/api2/json/{}", base, path.trim_start_matches('/'));
+        // Encode a form body once, before anything is sent
+        let form = match (body, encoding) {
+            (Some(body), BodyEncoding::Form) => Some(
+                serde_urlencoded::to_string(body)
+                    .map_err(|e| ProxmoxError::http("Failed to encode form body", e))?,
+            ),
+            _ => None,
+        };
 
-        let mut req_builder = self.http_client.request(method, &url);
+        // Ensure we have a valid ticket (refresh if needed)
+        self.ensure_authenticated().await?;
 
-        // Authentication headers must be present after refresh
-        {
-            let auth_guard = self.auth.read().await;
-            if let Some(auth) = auth_guard.as_ref() {
+        // Apply rate limiting if enabled
+        if let Some(limiter) = &self.rate_limiter {
+            // `until_ready()` returns a future that completes when capacity is available.
+            limiter.until_ready().await;
+        }
+
+        // Wait for a free slot if concurrency is limited; held until the response is parsed
+        let _permit = self.acquire_slot().await;
+
+        // Build the full URL
+        let url = self.api_url(path);
+
+        let mut retries = 0;
+        loop {
+            // Add authentication headers (re-read on every attempt to pick up a refresh)
+            let (mut req_builder, generation) = self
+                .with_auth_headers(self.http_client.request(method.clone(), &url), &method)
+                .await?;
+
+            // Add body if present
+            if let Some(form) = &form {
                 req_builder = req_builder
-                    .header("Cookie", auth.ticket().as_cookie_header())
-                    .header("CSRFPreventionToken", auth.csrf_token().unwrap().as_str());
+                    .header(
+                        reqwest::header::CONTENT_TYPE,
+                        "application/x-www-form-urlencoded",
+                    )
+                    .body(form.clone());
+            } else if let Some(body) = body {
+                req_builder = req_builder.json(body);
+            }
+
+            // Send the request
+            let response = self.send(req_builder).await.map_err(|e| {
+                let message = if retries == 0 {
+                    "HTTP request failed"
+                } else {
+                    "HTTP request failed on retry"
+                };
+                ProxmoxError::http(message, e)
+            })?;
+            if is_state_changing(&method) {
+                self.invalidate_cache(path).await;
             }
-        }
 
-        if let Some(body) = body {
-            req_builder = req_builder.json(body);
+            // Parse successful responses, extracting the `data` field
+            let status = response.status();
+            let result = if status.is_success() {
+                self.parse_response(response).await
+            } else {
+                let error_text = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "unknown".to_string());
+                let prefix = if retries == 0 {
+                    "API error"
+                } else {
+                    "API error after refresh"
+                };
+                Err(api_error(prefix, status, error_text))
+            };
+
+            // Handle 401 Unauthorized (ExtJS-style endpoints report it in the body of a
+            // `200 OK`): refresh and retry while attempts remain, backing off from the second
+            // retry on (e.g., while a cluster restarts `pveproxy` node by node)
+            let unauthorized = result.as_ref().err().and_then(ProxmoxError::status_code)
+                == Some(StatusCode::UNAUTHORIZED.as_u16());
+            if unauthorized && retries < self.config.max_auth_retries {
+                if retries > 0 {
+                    tokio::time::sleep(AUTH_RETRY_BACKOFF * retries).await;
+                }
+                retries += 1;
+                self.refresh_auth(generation).await?;
+                continue;
+            }
+            return result;
         }
+    }
 
-        let response = req_builder.send().await.map_err(|e| {
-            ProxmoxError::Connection(format!("HTTP request failed on retry: {}", e))
-        })?;
+    /// Returns the full URL of the API `path` under the configured [`ApiStyle`] prefix.
+    fn api_url(&self, path: &str) -> String {
+        let base = self.connection.url().as_str().trim_end_matches('/');
+        format!(
+            "{}/{}/{}",
+            base,
+            self.config.api_style.prefix(),
+            path.trim_start_matches('/')
+        )
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response
-                .text()
+    /// Extracts the `data` field of a successful response.
+    ///
+    /// ExtJS-style endpoints answer failed requests with `200 OK` and `success: 0`; those are
+    /// reported like any other API error.
+    async fn parse_response<T>(&self, response: reqwest::Response) -> ProxmoxResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        if self.config.api_style == ApiStyle::Json {
+            let proxmox_resp = response
+                .json::<ProxmoxResponse<T>>()
                 .await
-                .unwrap_or_else(|_| "unknown".to_string());
-            return Err(ProxmoxError::Connection(format!(
-                "API error after refresh ({}): {}",
-                status, error_text
-            )));
+                .map_err(|e| ProxmoxError::http("Failed to parse response", e))?;
+            return Ok(proxmox_resp.data);
         }
 
-        let proxmox_resp = response.json::<ProxmoxResponse<T>>().await.map_err(|e| {
-            ProxmoxError::Connection(format!("Failed to parse response after refresh: {}", e))
-        })?;
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ProxmoxError::http("Failed to read response", e))?;
+        let outcome: ExtJsOutcome = serde_json::from_slice(&body)
+            .map_err(|e| ProxmoxError::http("Failed to parse response", e))?;
+        if !outcome.success {
+            return Err(outcome.into_error());
+        }
+        let proxmox_resp: ProxmoxResponse<T> = serde_json::from_slice(&body)
+            .map_err(|e| ProxmoxError::http("Failed to parse response", e))?;
         Ok(proxmox_resp.data)
     }
 
+    /// Adds the ticket cookie and, for state-changing methods, the CSRF token.
+    ///
+    /// Proxmox only checks the CSRF token on POST, PUT and DELETE, so reads work with the
+    /// ticket alone.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Authentication` with "read-only session" if a state-changing
+    /// request is attempted without a CSRF token.
+    ///
+    /// Also returns the generation of the authentication state used, for
+    /// [`refresh_auth`](Self::refresh_auth).
+    async fn with_auth_headers(
+        &self,
+        mut req_builder: reqwest::RequestBuilder,
+        method: &reqwest::Method,
+    ) -> ProxmoxResult<(reqwest::RequestBuilder, u64)> {
+        let auth_guard = self.auth.read().await;
+        let generation = self.auth_generation.load(Ordering::Acquire);
+        let Some(auth) = auth_guard.as_ref() else {
+            return Ok((req_builder, generation));
+        };
+        req_builder = req_builder.header("Cookie", auth.ticket().as_cookie_header());
+        if is_state_changing(method) {
+            let csrf = auth
+                .csrf_token()
+                .ok_or_else(|| ProxmoxError::Authentication(READ_ONLY_SESSION.to_string()))?;
+            req_builder = req_builder.header("CSRFPreventionToken", csrf.as_str());
+        }
+        Ok((req_builder, generation))
+    }
+
+    /// Sends a request, passing it and its response through the registered middleware and
+    /// reporting it to the metrics sink.
+    async fn send(
+        &self,
+        req_builder: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        let mut request = req_builder.build()?;
+        if !self.middleware.is_empty() {
+            let mut parts = RequestParts {
+                method: request.method().clone(),
+                url: request.url().clone(),
+                headers: request.headers().clone(),
+            };
+            self.middleware.on_request(&mut parts).await;
+            *request.method_mut() = parts.method;
+            *request.url_mut() = parts.url;
+            *request.headers_mut() = parts.headers;
+        }
+
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let started = Instant::now();
+        let result = self.http_client.execute(request).await;
+        let elapsed = started.elapsed();
+        if let Some(metrics) = &self.metrics {
+            let status = result.as_ref().ok().map(reqwest::Response::status);
+            metrics.record_request(&method, api_path(url.path()), status, elapsed);
+        }
+        let response = result?;
+
+        if !self.middleware.is_empty() {
+            self.middleware
+                .on_response(&ResponseParts {
+                    method,
+                    url,
+                    status: response.status(),
+                    headers: response.headers().clone(),
+                    elapsed,
+                })
+                .await;
+        }
+        Ok(response)
+    }
+
     /// Ensures that we have a valid (non‑expired) ticket. If not, attempts to refresh.
     async fn ensure_authenticated(&self) -> ProxmoxResult<()> {
-        let need_refresh = {
-            let auth_guard = self.auth.read().await;
-            match auth_guard.as_ref() {
-                Some(auth) => auth.ticket().is_expired(self.config.ticket_lifetime),
-                None => true,
-            }
-        };
+        if !self.needs_login().await {
+            return Ok(());
+        }
+        let _refresh = self.refresh_lock.lock().await;
+        // Another request may have logged in while this one waited for the lock.
+        if self.needs_login().await {
+            let auth = self.login().await?;
+            self.replace_auth(Some(auth)).await;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if there is no ticket or it has expired.
+    async fn needs_login(&self) -> bool {
+        match self.auth.read().await.as_ref() {
+            Some(auth) => auth.ticket().is_expired(self.config.ticket_lifetime),
+            None => true,
+        }
+    }
 
-        if need_refresh {
-            self.refresh_auth().await?;
+    /// Logs in again after the server rejected the ticket of authentication generation `seen`.
+    ///
+    /// If the state was replaced in the meantime (typically by a concurrent refresh this call
+    /// waited for), the new ticket is reused and no login is made.
+    async fn refresh_auth(&self, seen: u64) -> ProxmoxResult<()> {
+        let _refresh = self.refresh_lock.lock().await;
+        if self.auth_generation.load(Ordering::Acquire) != seen {
+            return Ok(());
         }
+        let auth = self.login().await?;
+        self.replace_auth(Some(auth)).await;
         Ok(())
     }
 
-    /// Performs a fresh login using the stored credentials to obtain a new ticket.
-    async fn refresh_auth(&self) -> ProxmoxResult<()> {
-        let service = LoginService::new();
-        let auth = service.execute(&self.connection).await?;
+    /// Waits for a request slot when `max_concurrent_requests` is set.
+    async fn acquire_slot(&self) -> Option<tokio::sync::SemaphorePermit<'_>> {
+        match &self.concurrency {
+            // The semaphore is never closed, so acquiring only fails if that changes
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        }
+    }
+
+    /// Drops the cached responses a mutation of `path` may have made stale.
+    async fn invalidate_cache(&self, path: &str) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(path).await;
+        }
+    }
+
+    /// Stores a new authentication state and bumps its generation.
+    async fn replace_auth(&self, auth: Option<ProxmoxAuth>) {
         let mut lock = self.auth.write().await;
-        *lock = Some(auth);
-        Ok(())
+        *lock = auth;
+        self.auth_generation.fetch_add(1, Ordering::AcqRel);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::domain::value_object::fresh_ticket;
     use crate::{
         ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm, ProxmoxUrl, ProxmoxUsername,
         RateLimitConfig,
@@ -338,7 +887,7 @@ mod tests {
     }
 
     fn create_test_auth() -> ProxmoxAuth {
-        let ticket = ProxmoxTicket::new_unchecked("PVE:testuser@pam:4EEC61E2::sig".to_string());
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
         let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
         ProxmoxAuth::new(ticket, Some(csrf))
     }
@@ -385,7 +934,7 @@ mod tests {
             .and(path("/api2/json/access/ticket"))
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
                 "data": {
-                    "ticket": "PVE:testuser@pam:4EEC61E2::new_sig",
+                    "ticket": fresh_ticket("testuser@pam", "new_sig"),
                     "CSRFPreventionToken": "4EEC61E2:abc123"
                 }
             })))
@@ -406,7 +955,7 @@ mod tests {
 
         // Verify that new auth was stored.
         let auth = client.auth().await.unwrap();
-        assert_eq!(auth.ticket().as_str(), "PVE:testuser@pam:4EEC61E2::new_sig");
+        assert!(auth.ticket().as_str().ends_with("::new_sig"));
         assert_eq!(auth.csrf_token().unwrap().as_str(), "4EEC61E2:abc123");
     }
 
@@ -484,6 +1033,19 @@ mod tests {
         assert!(elapsed >= Duration::from_millis(900));
     }
 
+    #[test]
+    fn test_zero_rate_limit_is_rejected() {
+        let config = ValidationConfig {
+            rate_limit: Some(RateLimitConfig {
+                requests_per_second: 0,
+                burst_size: 1,
+            }),
+            ..Default::default()
+        };
+        let result = ApiClient::new(create_test_connection("http://localhost:8006"), config);
+        assert!(matches!(result, Err(ProxmoxError::Validation { .. })));
+    }
+
     #[tokio::test]
     async fn test_rate_limiting_disabled() {
         use tokio::time::{self, Duration};
@@ -515,6 +1077,76 @@ mod tests {
         assert!(elapsed < Duration::from_millis(500));
     }
 
+    #[tokio::test]
+    async fn test_max_concurrent_requests_caps_in_flight() {
+        use crate::middleware::{Middleware, RequestParts, ResponseParts};
+
+        /// Tracks how many requests have been sent but not yet answered.
+        #[derive(Default)]
+        struct InFlight {
+            current: AtomicU64,
+            peak: AtomicU64,
+        }
+
+        #[async_trait::async_trait]
+        impl Middleware for InFlight {
+            async fn on_request(&self, _request: &mut RequestParts) {
+                let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(now, Ordering::SeqCst);
+            }
+
+            async fn on_response(&self, _response: &ResponseParts) {
+                self.current.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let config = ValidationConfig {
+            max_concurrent_requests: Some(2),
+            ..Default::default()
+        };
+        let in_flight = Arc::new(InFlight::default());
+        let mut options = HttpOptions::default();
+        options.middleware.push(in_flight.clone());
+        let client = ApiClient::with_options(connection, config, options).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": "ok" }))
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .expect(20)
+            .mount(&mock_server)
+            .await;
+
+        let requests = (0..20).map(|_| client.get::<String>("test"));
+        for result in futures::future::join_all(requests).await {
+            result.unwrap();
+        }
+        assert_eq!(in_flight.peak.load(Ordering::SeqCst), 2);
+        assert_eq!(in_flight.current.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_probe_accepts_unauthorized() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/version"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert!(client.probe().await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_post_with_body() {
         let mock_server = MockServer::start().await;
@@ -545,4 +1177,456 @@ mod tests {
         let result: serde_json::Value = client.post("test", &body).await.unwrap();
         assert_eq!(result["result"], "created");
     }
+
+    #[tokio::test]
+    async fn test_max_auth_retries() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let config = ValidationConfig {
+            max_auth_retries: 2,
+            ..ValidationConfig::default()
+        };
+        let client = ApiClient::new(connection, config).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(401))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("testuser@pam", "new_sig"),
+                    "CSRFPreventionToken": "4EEC61E2:abc123"
+                }
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": "ok"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result: String = client.get("test").await.unwrap();
+        assert_eq!(result, "ok");
+
+        // With the default of one retry, the second 401 is reported.
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+        client.set_auth(create_test_auth()).await;
+        mock_server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("testuser@pam", "new_sig"),
+                    "CSRFPreventionToken": "4EEC61E2:abc123"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let err = client.get::<String>("test").await.unwrap_err();
+        assert!(
+            matches!(err, ProxmoxError::Api { status: 401, ref message } if message.contains("after refresh"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_serves_repeated_get_until_mutation() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let config = ValidationConfig {
+            cache: Some(crate::CacheConfig {
+                ttl: Duration::from_secs(60),
+            }),
+            ..ValidationConfig::default()
+        };
+        let client = ApiClient::new(connection, config).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "node": "pve1", "status": "online" }]
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": null
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let first: CachedResponse<serde_json::Value> = client.get_cached("nodes").await.unwrap();
+        let second: CachedResponse<serde_json::Value> = client.get_cached("nodes").await.unwrap();
+        // The cached copy reports when it was originally fetched
+        assert_eq!(first, second);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+        let _: Option<String> = client
+            .post("nodes/pve1/qemu/100/status/start", &serde_json::json!({}))
+            .await
+            .unwrap();
+        let _: serde_json::Value = client.get("nodes").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extjs_style_uses_extjs_prefix() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let config = ValidationConfig {
+            api_style: ApiStyle::ExtJs,
+            ..ValidationConfig::default()
+        };
+        let client = ApiClient::new(connection, config).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/extjs/version"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": 1,
+                "data": { "version": "8.2.4" }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api2/extjs/nodes/pve1/qemu/100/config"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": 0,
+                "status": 400,
+                "message": "Parameter verification failed.\n",
+                "errors": { "memory": "value must have a minimum value of 16" },
+                "data": null
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let version: serde_json::Value = client.get("version").await.unwrap();
+        assert_eq!(version["version"], "8.2.4");
+
+        let err = client
+            .put::<_, Option<String>>(
+                "nodes/pve1/qemu/100/config",
+                &serde_json::json!({ "memory": 1 }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(400));
+        assert!(err.to_string().contains("minimum value of 16"));
+    }
+
+    #[tokio::test]
+    async fn test_extjs_style_expired_ticket_triggers_refresh() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let config = ValidationConfig {
+            api_style: ApiStyle::ExtJs,
+            ..ValidationConfig::default()
+        };
+        let client = ApiClient::new(connection, config).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        // ExtJS-style endpoints report the expired ticket in the body of a 200
+        Mock::given(method("GET"))
+            .and(path("/api2/extjs/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": 0,
+                "status": 401,
+                "message": "permission denied - invalid PVE ticket",
+                "data": null
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("testuser@pam", "new_sig"),
+                    "CSRFPreventionToken": "4EEC61E2:abc123"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/extjs/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": 1,
+                "data": "ok"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result: String = client.get("test").await.unwrap();
+        assert_eq!(result, "ok");
+        let auth = client.auth().await.unwrap();
+        assert!(auth.ticket().as_str().ends_with("::new_sig"));
+    }
+
+    #[tokio::test]
+    async fn test_extjs_style_probe_uses_extjs_prefix() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let config = ValidationConfig {
+            api_style: ApiStyle::ExtJs,
+            ..ValidationConfig::default()
+        };
+        let client = ApiClient::new(connection, config).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/api2/extjs/version"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        client.probe().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_error_exposes_status_code() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes/pve1/qemu"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .set_body_string("Permission check failed (/vms, VM.Audit)"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let err = client
+            .get::<serde_json::Value>("nodes/pve1/qemu")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), Some(403));
+        assert_eq!(
+            err.to_string(),
+            "API error (403 Forbidden): Permission check failed (/vms, VM.Audit)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics_records_every_attempt() {
+        #[derive(Default)]
+        struct Counter(std::sync::Mutex<Vec<(String, Option<u16>)>>);
+
+        impl Metrics for Counter {
+            fn record_request(
+                &self,
+                method: &reqwest::Method,
+                path: &str,
+                status: Option<StatusCode>,
+                _duration: Duration,
+            ) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((format!("{} {}", method, path), status.map(|s| s.as_u16())));
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        let counter = Arc::new(Counter::default());
+        let options = HttpOptions {
+            metrics: Some(counter.clone()),
+            ..HttpOptions::default()
+        };
+        let connection = create_test_connection(&mock_server.uri());
+        let client =
+            ApiClient::with_options(connection, ValidationConfig::default(), options).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes/pve1/status"))
+            .respond_with(ResponseTemplate::new(401))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("testuser@pam", "new_sig"),
+                    "CSRFPreventionToken": "4EEC61E2:abc123"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes/pve1/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": "ok"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let result: String = client.get("nodes/pve1/status?full=1").await.unwrap();
+        assert_eq!(result, "ok");
+
+        // The retry is recorded; the login in between is not.
+        assert_eq!(
+            *counter.0.lock().unwrap(),
+            vec![
+                ("GET nodes/pve1/status".to_string(), Some(401)),
+                ("GET nodes/pve1/status".to_string(), Some(200)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_401s_refresh_once() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+        let stale = create_test_auth();
+        let stale_cookie = stale.ticket().as_cookie_header();
+        client.set_auth(stale).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .and(wiremock::matchers::header("Cookie", stale_cookie.as_str()))
+            .respond_with(ResponseTemplate::new(401))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": "ok"
+            })))
+            .expect(10)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "data": {
+                            "ticket": fresh_ticket("testuser@pam", "new_sig"),
+                            "CSRFPreventionToken": "4EEC61E2:abc123"
+                        }
+                    }))
+                    .set_delay(std::time::Duration::from_millis(100)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let results =
+            futures::future::join_all((0..10).map(|_| client.get::<String>("test"))).await;
+        for result in results {
+            assert_eq!(result.unwrap(), "ok");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_failure_keeps_source() {
+        use std::error::Error;
+
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+        client.set_auth(create_test_auth()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>not json</html>"))
+            .mount(&mock_server)
+            .await;
+
+        let err = client.get::<String>("test").await.unwrap_err();
+        assert!(matches!(err, ProxmoxError::Http { .. }));
+        let source = err.source().expect("parse failure should carry its cause");
+        assert!(source.downcast_ref::<reqwest::Error>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_without_csrf_token() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+        client.set_auth(ProxmoxAuth::new(ticket, None)).await;
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": "ok"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result: String = client.get("test").await.unwrap();
+        assert_eq!(result, "ok");
+        let requests = mock_server.received_requests().await.unwrap();
+        assert!(!requests[0].headers.contains_key("CSRFPreventionToken"));
+    }
+
+    #[tokio::test]
+    async fn test_post_without_csrf_token_is_refused() {
+        let mock_server = MockServer::start().await;
+        let connection = create_test_connection(&mock_server.uri());
+        let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+        client.set_auth(ProxmoxAuth::new(ticket, None)).await;
+
+        Mock::given(method("POST"))
+            .and(path("/api2/json/test"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let err = client
+            .post::<_, serde_json::Value>("test", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProxmoxError::Authentication(ref msg) if msg == "read-only session"));
+    }
+
+    #[test]
+    fn test_cloudflare_client_id_sent_verbatim() {
+        let access = CloudflareAccess::from_lookup(|name| match name {
+            "CF_ACCESS_CLIENT_ID" => Some("0123abcd.access".to_string()),
+            "CF_ACCESS_CLIENT_SECRET" => Some("s3cr3t".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        let headers = access.headers().unwrap();
+        assert_eq!(headers["CF-Access-Client-Id"], "0123abcd.access");
+        assert_eq!(headers["CF-Access-Client-Secret"], "s3cr3t");
+
+        assert!(CloudflareAccess::from_lookup(|_| None).is_none());
+    }
 }
@@ -1 +1,2 @@
 pub(crate) mod api_client;
+pub(crate) mod response_cache;
@@ -0,0 +1,181 @@
+//! In-memory cache of GET responses, enabled with [`CacheConfig`](crate::CacheConfig).
+
+use crate::core::domain::model::cached_response::CachedResponse;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::RwLock;
+
+/// Listings that reflect changes anywhere in the cluster, dropped on every mutation.
+const CLUSTER_WIDE_PATHS: [&str; 2] = ["nodes", "cluster/resources"];
+
+/// Path segments of state that polling loops wait on (task status and logs, guest power
+/// state, agent processes); such responses are never cached.
+const VOLATILE_SEGMENTS: [&str; 3] = ["/tasks/", "/status/current", "/agent/exec-status"];
+
+/// Paths that are never cached, whatever their query string. A cached `cluster/nextid` would
+/// keep handing out an identifier that was taken in the meantime.
+const UNCACHED_PATHS: [&str; 1] = ["cluster/nextid"];
+
+/// A cached response body (`data` field) and when it was stored.
+#[derive(Debug)]
+struct CacheEntry {
+    data: serde_json::Value,
+    /// Monotonic time, for the TTL.
+    stored_at: Instant,
+    /// Wall-clock time, reported to callers.
+    fetched_at: SystemTime,
+}
+
+/// GET responses keyed by request path (including the query string).
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns `false` for paths whose responses must always come from the server.
+    pub(crate) fn is_cacheable(path: &str) -> bool {
+        let path = strip_query(path);
+        !UNCACHED_PATHS.contains(&path)
+            && !VOLATILE_SEGMENTS.iter().any(|segment| {
+                path.contains(segment) || path.ends_with(segment.trim_end_matches('/'))
+            })
+    }
+
+    /// Returns the cached response for `path` if it is younger than the TTL.
+    pub(crate) async fn get(&self, path: &str) -> Option<CachedResponse<serde_json::Value>> {
+        let entries = self.entries.read().await;
+        entries
+            .get(path)
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .map(|entry| CachedResponse::new(entry.data.clone(), entry.fetched_at))
+    }
+
+    /// Stores the response for `path` as fetched at `fetched_at`, dropping expired entries on
+    /// the way.
+    pub(crate) async fn insert(&self, path: &str, data: serde_json::Value, fetched_at: SystemTime) {
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, entry| entry.stored_at.elapsed() < self.ttl);
+        entries.insert(
+            path.to_string(),
+            CacheEntry {
+                data,
+                stored_at: Instant::now(),
+                fetched_at,
+            },
+        );
+    }
+
+    /// Drops the entries a mutation of `path` may have made stale.
+    ///
+    /// These are the entries under the same top-level resource (the first two path segments,
+    /// e.g. everything below `nodes/pve1`) and the cluster-wide listings.
+    pub(crate) async fn invalidate(&self, path: &str) {
+        let scope = resource_scope(path);
+        let mut entries = self.entries.write().await;
+        entries.retain(|key, _| {
+            let key = strip_query(key);
+            !(CLUSTER_WIDE_PATHS.contains(&key) || within(key, scope))
+        });
+    }
+}
+
+/// Returns the path without its query string and surrounding slashes.
+fn strip_query(path: &str) -> &str {
+    path.split_once('?')
+        .map_or(path, |(path, _)| path)
+        .trim_matches('/')
+}
+
+/// Returns the first two segments of `path` (e.g., "nodes/pve1" for a VM status path).
+fn resource_scope(path: &str) -> &str {
+    let path = strip_query(path);
+    match path.match_indices('/').nth(1) {
+        Some((end, _)) => &path[..end],
+        None => path,
+    }
+}
+
+/// Returns `true` if `path` is `scope` or lies below it.
+fn within(path: &str, scope: &str) -> bool {
+    path.strip_prefix(scope)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_scope() {
+        assert_eq!(
+            resource_scope("nodes/pve1/qemu/100/status/start"),
+            "nodes/pve1"
+        );
+        assert_eq!(
+            resource_scope("/cluster/ha/resources?type=vm"),
+            "cluster/ha"
+        );
+        assert_eq!(resource_scope("nodes"), "nodes");
+    }
+
+    #[test]
+    fn test_volatile_paths_are_not_cacheable() {
+        assert!(ResponseCache::is_cacheable("cluster/resources?type=vm"));
+        assert!(ResponseCache::is_cacheable("nodes/pve1/qemu"));
+        assert!(!ResponseCache::is_cacheable(
+            "nodes/pve1/tasks/UPID:pve1:1:2:3:qmstart:100:root@pam:/status"
+        ));
+        assert!(!ResponseCache::is_cacheable(
+            "nodes/pve1/qemu/100/status/current"
+        ));
+        assert!(!ResponseCache::is_cacheable(
+            "nodes/pve1/qemu/100/agent/exec-status?pid=42"
+        ));
+        assert!(!ResponseCache::is_cacheable("cluster/nextid"));
+        assert!(!ResponseCache::is_cacheable("/cluster/nextid?vmid=105"));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_drops_related_entries() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        for path in [
+            "nodes",
+            "cluster/resources?type=vm",
+            "nodes/pve1/qemu",
+            "nodes/pve10/qemu",
+            "nodes/pve2/qemu",
+            "version",
+        ] {
+            cache
+                .insert(path, serde_json::json!([]), SystemTime::now())
+                .await;
+        }
+
+        cache.invalidate("nodes/pve1/qemu/100/status/start").await;
+
+        assert!(cache.get("nodes").await.is_none());
+        assert!(cache.get("cluster/resources?type=vm").await.is_none());
+        assert!(cache.get("nodes/pve1/qemu").await.is_none());
+        assert!(cache.get("nodes/pve10/qemu").await.is_some());
+        assert!(cache.get("nodes/pve2/qemu").await.is_some());
+        assert!(cache.get("version").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire() {
+        let cache = ResponseCache::new(Duration::ZERO);
+        cache
+            .insert("version", serde_json::json!({}), SystemTime::now())
+            .await;
+        assert!(cache.get("version").await.is_none());
+    }
+}
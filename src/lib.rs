@@ -29,36 +29,83 @@
 //! ```
 
 mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod core;
+pub mod metrics;
+pub mod middleware;
+pub mod pool;
 
 pub use crate::core::domain::error::{ProxmoxError, ProxmoxResult, ValidationError};
+pub use crate::core::domain::model::proxmox_connection::ProxmoxConnection;
 pub use crate::core::domain::model::{
-    cluster_resource::ClusterResource,
+    agent::{
+        AgentExecPid, AgentExecResult, AgentExecStatus, AgentInterface, AgentIpAddress,
+        IpAddressType,
+    },
+    apt::{AptPackage, PackageVersion},
+    backup::{
+        BackupCompression, BackupFile, BackupItem, BackupMode, BackupParams, BackupVerification,
+        GuestType, RestoreParams,
+    },
+    cached_response::CachedResponse,
+    cluster_resource::{ClusterResource, ResourceFilter, ResourceKind},
+    cluster_status::{ClusterInfo, ClusterNodeStatus, ClusterStatusEntry},
+    console::{SpiceProxy, VncProxy},
+    ha::{HaResource, HaState},
+    lxc::{CreateLxcParams, CreateLxcParamsBuilder, LxcConfig, UpdateLxcConfig},
+    network::NetworkInterface,
     node_dns::NodeDnsConfig,
     node_list_item::NodeListItem,
-    node_status::{MemoryInfo, NodeStatus},
+    node_status::{CpuInfo, KernelInfo, KsmInfo, MemoryInfo, NodePowerAction, NodeStatus},
+    node_time::NodeTime,
+    service::{NodeService, ServiceAction},
+    status::{NodeOnlineStatus, PowerStatus},
+    storage::{Checksum, ChecksumAlgorithm, StorageVolume, UploadContentType},
+    task::{TaskId, TaskListItem, TaskListOptions, TaskOutcome, TaskStatus},
+    version::{ProxmoxVersion, VersionInfo},
     vm::*,
 };
+pub use crate::core::domain::value_object::{
+    Fingerprint, NodeName, ProxmoxCSRFToken, ProxmoxHost, ProxmoxPassword, ProxmoxPort,
+    ProxmoxRealm, ProxmoxTicket, ProxmoxUrl, ProxmoxUsername, VmId,
+};
 
-use crate::{
-    auth::application::service::login_service::LoginService,
-    core::{
-        domain::{
-            model::{proxmox_auth::ProxmoxAuth, proxmox_connection::ProxmoxConnection},
-            value_object::{
-                ProxmoxCSRFToken, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
-                ProxmoxTicket, ProxmoxUrl, ProxmoxUsername, validate_host, validate_password,
-                validate_port, validate_realm, validate_url, validate_username,
-            },
+use crate::core::{
+    domain::{
+        model::{
+            agent::AgentResponse,
+            apt::compare_kernel_releases,
+            proxmox_auth::ProxmoxAuth,
+            session::SessionFile,
+            task::{TaskLogLine, parse_progress},
+        },
+        value_object::{
+            validate_host, validate_password, validate_port, validate_realm, validate_url,
+            validate_username,
         },
-        infrastructure::api_client::ApiClient,
     },
+    infrastructure::api_client::{ApiClient, CloudflareAccess, HttpCustomizer, HttpOptions},
 };
+use crate::middleware::MiddlewareChain;
+use futures::{Stream, TryFutureExt, TryStreamExt, future, stream};
 
 use std::backtrace::Backtrace;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Interval between `exec-status` polls while waiting for a guest agent command.
+const AGENT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Interval between task status polls in [`ProxmoxClient::wait_for_task`].
+const TASK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of candidate identifiers tried by [`ProxmoxClient::next_vmid_confirmed`] and
+/// [`ProxmoxClient::create_vm_with_next_vmid`].
+const CONFIRM_VMID_ATTEMPTS: u32 = 5;
+
 /// Configuration for rate limiting.
 #[derive(Debug, Clone, Copy)]
 pub struct RateLimitConfig {
@@ -68,6 +115,40 @@ pub struct RateLimitConfig {
     pub burst_size: u32,
 }
 
+/// The API endpoint family requests are sent to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApiStyle {
+    /// `/api2/json`, which reports failures through the HTTP status (the default).
+    #[default]
+    Json,
+    /// `/api2/extjs`, as used by the web UI. Failures arrive as `200 OK` with `success: 0`
+    /// and are reported as `ProxmoxError::Api` all the same.
+    ExtJs,
+}
+
+impl ApiStyle {
+    /// Returns the URL path prefix of the endpoint family (e.g., "api2/json").
+    pub(crate) fn prefix(self) -> &'static str {
+        match self {
+            ApiStyle::Json => "api2/json",
+            ApiStyle::ExtJs => "api2/extjs",
+        }
+    }
+}
+
+/// Configuration for caching GET responses.
+///
+/// Responses are kept in memory per request path for `ttl`. A POST, PUT or DELETE drops the
+/// cached responses of the same resource (e.g., everything under `nodes/pve1`) and the
+/// cluster-wide `nodes` and `cluster/resources` listings. Changes made by other clients are
+/// only seen once an entry expires. Task status and logs, guest power state, agent process
+/// status and free VM identifiers (`cluster/nextid`) are never cached.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    /// How long a response is served from the cache.
+    pub ttl: Duration,
+}
+
 /// Configuration for validating client inputs.
 ///
 /// By default, all extra checks are disabled, meaning only basic format validation is performed.
@@ -80,12 +161,26 @@ pub struct ValidationConfig {
     pub resolve_dns: bool,
     /// If true, reserved usernames (root, admin, etc.) are rejected.
     pub block_reserved_usernames: bool,
-    /// Ticket lifetime for expiration checks (default 2 hours).
+    /// Ticket lifetime for expiration checks, counted from the issuance time embedded in the
+    /// ticket (default 2 hours, matching Proxmox).
     pub ticket_lifetime: Duration,
     /// CSRF token lifetime (default 5 minutes).
     pub csrf_lifetime: Duration,
     /// Optional rate limiting configuration. If `None`, no rate limiting is applied.
     pub rate_limit: Option<RateLimitConfig>,
+    /// How long the `*_and_wait` operations wait for their task (default 5 minutes).
+    pub task_timeout: Duration,
+    /// How many times a request answered with `401 Unauthorized` is retried after refreshing
+    /// the ticket (default 1). `0` disables the refresh.
+    pub max_auth_retries: u32,
+    /// Optional response caching. If `None` (the default), every GET reaches the server.
+    pub cache: Option<CacheConfig>,
+    /// Maximum number of API requests in flight at once, shared by all clones of the client.
+    /// If `None` (the default), concurrency is not limited; `Some(0)` is treated as 1.
+    pub max_concurrent_requests: Option<usize>,
+    /// Endpoint family for API requests (default [`ApiStyle::Json`]). Login always uses
+    /// `/api2/json`.
+    pub api_style: ApiStyle,
 }
 
 impl Default for ValidationConfig {
@@ -97,6 +192,11 @@ impl Default for ValidationConfig {
             ticket_lifetime: Duration::from_secs(7200),
             csrf_lifetime: Duration::from_secs(300),
             rate_limit: None, // default: no limiting
+            task_timeout: Duration::from_secs(300),
+            max_auth_retries: 1,
+            cache: None,
+            max_concurrent_requests: None,
+            api_style: ApiStyle::Json,
         }
     }
 }
@@ -104,7 +204,11 @@ impl Default for ValidationConfig {
 /// A strongly-typed client for the Proxmox VE API.
 ///
 /// Use the builder to configure connection settings and validation rules.
-#[derive(Debug)]
+///
+/// Cloning is cheap: clones share the ticket, HTTP connection pool, rate limiter and
+/// middleware, so a login or ticket refresh through one clone is seen by all of them. Hand a
+/// clone to each spawned task instead of wrapping the client in a lock.
+#[derive(Debug, Clone)]
 pub struct ProxmoxClient {
     api_client: ApiClient,
     config: ValidationConfig,
@@ -122,6 +226,12 @@ pub struct ProxmoxClientBuilder {
     accept_invalid_certs: bool,
     config: ValidationConfig,
     initial_auth: Option<ProxmoxAuth>,
+    verify_on_build: bool,
+    user_agent: Option<String>,
+    cloudflare_access: Option<CloudflareAccess>,
+    http_customizer: Option<HttpCustomizer>,
+    middleware: MiddlewareChain,
+    metrics: Option<Arc<dyn metrics::Metrics>>,
 }
 
 impl Default for ProxmoxClientBuilder {
@@ -136,11 +246,100 @@ impl Default for ProxmoxClientBuilder {
             accept_invalid_certs: false,
             config: ValidationConfig::default(),
             initial_auth: None,
+            verify_on_build: false,
+            user_agent: None,
+            cloudflare_access: None,
+            http_customizer: None,
+            middleware: MiddlewareChain::default(),
+            metrics: None,
         }
     }
 }
 
 impl ProxmoxClientBuilder {
+    /// Creates a builder pre-populated from the standard `PROXMOX_*` environment variables.
+    ///
+    /// Required: `PROXMOX_HOST`, `PROXMOX_PORT`, `PROXMOX_USERNAME`, `PROXMOX_PASSWORD` and
+    /// `PROXMOX_REALM`. Optional: `PROXMOX_SECURE` and `PROXMOX_ACCEPT_INVALID_CERTS`
+    /// (`true`/`false`, `1`/`0`, `yes`/`no`). The builder does not support API token
+    /// authentication yet, so `PROXMOX_TOKEN` is not read.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError::Validation`] listing every missing required variable, or naming
+    /// the first variable with an unparseable value.
+    pub fn from_env() -> ProxmoxResult<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Builds from an arbitrary variable lookup; see [`from_env`](Self::from_env).
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> ProxmoxResult<Self> {
+        let invalid = |field: &str, message: String| ProxmoxError::Validation {
+            source: ValidationError::Field {
+                field: field.to_string(),
+                message,
+            },
+            backtrace: Backtrace::capture(),
+        };
+        let required = |name: &str| lookup(name).filter(|v| !v.is_empty());
+
+        let host = required("PROXMOX_HOST");
+        let port = required("PROXMOX_PORT");
+        let username = required("PROXMOX_USERNAME");
+        let password = required("PROXMOX_PASSWORD");
+        let realm = required("PROXMOX_REALM");
+        let (Some(host), Some(port), Some(username), Some(password), Some(realm)) =
+            (&host, &port, &username, &password, &realm)
+        else {
+            let missing: Vec<&str> = [
+                ("PROXMOX_HOST", host.is_none()),
+                ("PROXMOX_PORT", port.is_none()),
+                ("PROXMOX_USERNAME", username.is_none()),
+                ("PROXMOX_PASSWORD", password.is_none()),
+                ("PROXMOX_REALM", realm.is_none()),
+            ]
+            .into_iter()
+            .filter(|(_, is_missing)| *is_missing)
+            .map(|(name, _)| name)
+            .collect();
+            return Err(invalid(
+                "environment",
+                format!(
+                    "Missing required environment variables: {}",
+                    missing.join(", ")
+                ),
+            ));
+        };
+
+        let port = port.parse::<u16>().map_err(|_| {
+            invalid(
+                "PROXMOX_PORT",
+                format!("'{}' is not a valid port number", port),
+            )
+        })?;
+        let parse_flag = |name: &str| -> ProxmoxResult<Option<bool>> {
+            lookup(name)
+                .filter(|v| !v.is_empty())
+                .map(|v| match v.to_ascii_lowercase().as_str() {
+                    "1" | "true" | "yes" => Ok(true),
+                    "0" | "false" | "no" => Ok(false),
+                    _ => Err(invalid(name, format!("'{}' is not a boolean", v))),
+                })
+                .transpose()
+        };
+
+        let mut builder = Self::default()
+            .host(host)
+            .port(port)
+            .credentials(username, password, realm);
+        if let Some(secure) = parse_flag("PROXMOX_SECURE")? {
+            builder = builder.secure(secure);
+        }
+        if let Some(accept) = parse_flag("PROXMOX_ACCEPT_INVALID_CERTS")? {
+            builder = builder.accept_invalid_certs(accept);
+        }
+        Ok(builder)
+    }
+
     /// Sets the Proxmox VE host address.
     #[must_use]
     pub fn host(mut self, host: impl Into<String>) -> Self {
@@ -233,62 +432,236 @@ impl ProxmoxClientBuilder {
         self
     }
 
+    /// Limits how many API requests may be in flight at once; further requests wait for a
+    /// free slot. Unlike [`rate_limit`](Self::rate_limit), this bounds the load a slow node
+    /// sees regardless of how quickly it answers.
+    #[must_use]
+    pub fn max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.config.max_concurrent_requests = Some(limit);
+        self
+    }
+
+    /// Sends API requests to the given endpoint family (see [`ApiStyle`]).
+    #[must_use]
+    pub fn api_style(mut self, style: ApiStyle) -> Self {
+        self.config.api_style = style;
+        self
+    }
+
+    /// Caches GET responses in memory for `ttl` (see [`CacheConfig`]).
+    #[must_use]
+    pub fn cache(mut self, ttl: Duration) -> Self {
+        self.config.cache = Some(CacheConfig { ttl });
+        self
+    }
+
+    /// Sets how long `*_and_wait` operations such as
+    /// [`ProxmoxClient::start_vm_and_wait`] wait for their task.
+    #[must_use]
+    pub fn task_timeout(mut self, timeout: Duration) -> Self {
+        self.config.task_timeout = timeout;
+        self
+    }
+
+    /// Sets how long a ticket is treated as valid after its issuance (default 2 hours).
+    ///
+    /// Match this to the cluster's session lifetime if it was shortened, so the client logs
+    /// in again before the server starts rejecting the ticket.
+    #[must_use]
+    pub fn ticket_lifetime(mut self, lifetime: Duration) -> Self {
+        self.config.ticket_lifetime = lifetime;
+        self
+    }
+
+    /// Sets how many refresh-and-retry cycles a `401 Unauthorized` response triggers
+    /// (default 1). From the second retry on, a short, growing delay is added between
+    /// attempts.
+    #[must_use]
+    pub fn max_auth_retries(mut self, retries: u32) -> Self {
+        self.config.max_auth_retries = retries;
+        self
+    }
+
+    /// Sets the User-Agent sent with login and API requests
+    /// (default `leeca_proxmox/{version}`).
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sends a Cloudflare Access service token (`CF-Access-Client-Id` and
+    /// `CF-Access-Client-Secret`) with every request, for servers behind Cloudflare Access.
+    ///
+    /// Without this, the token is read from the `CF_ACCESS_CLIENT_ID` and
+    /// `CF_ACCESS_CLIENT_SECRET` environment variables when they are set.
+    #[must_use]
+    pub fn cloudflare_access(
+        mut self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        self.cloudflare_access = Some(CloudflareAccess::new(
+            client_id.into(),
+            client_secret.into(),
+        ));
+        self
+    }
+
+    /// Customizes the underlying `reqwest` HTTP client used for API requests.
+    ///
+    /// The closure receives the `reqwest::ClientBuilder` after the crate has applied its own
+    /// settings (such as [`accept_invalid_certs`](Self::accept_invalid_certs)), so it can set a
+    /// proxy, a custom DNS resolver or connection pool limits, or override those defaults.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+    /// # async fn run() -> ProxmoxResult<()> {
+    /// let client = ProxmoxClient::builder()
+    ///     .host("192.168.1.182")
+    ///     .port(8006)
+    ///     .credentials("leeca", "password", "pam")
+    ///     .configure_http(|http| {
+    ///         http.proxy(reqwest::Proxy::https("http://proxy.corp:3128").unwrap())
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn configure_http(
+        mut self,
+        configure: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder + Send + 'static,
+    ) -> Self {
+        self.http_customizer = Some(HttpCustomizer::new(configure));
+        self
+    }
+
+    /// Registers a [`Middleware`](middleware::Middleware) that runs around every API request.
+    ///
+    /// Middleware runs in the order it is registered; see the [`middleware`] module.
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: Arc<dyn middleware::Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Reports every API request to `metrics` (see the [`metrics`] module).
+    ///
+    /// Registering again replaces the previous sink.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<dyn metrics::Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Probes the server during [`build`](Self::build) so an unreachable host fails fast.
+    ///
+    /// When enabled, `build()` sends an unauthenticated request to `/version` and returns a
+    /// [`ProxmoxError::Connection`] if the server cannot be reached. Credentials are not checked.
+    #[must_use]
+    pub fn verify_on_build(mut self, verify: bool) -> Self {
+        self.verify_on_build = verify;
+        self
+    }
+
     /// Load an authentication state from a reader and use it as the initial auth.
     /// The tokens will be validated for expiration. Returns an error if the data is malformed
     /// or if the tokens are already expired according to the client's validation config.
-    pub async fn with_session<R: Read>(mut self, mut reader: R) -> ProxmoxResult<Self> {
+    ///
+    /// Call this after [`host`](Self::host) and [`port`](Self::port): a session saved for a
+    /// different host or port is rejected with [`ProxmoxError::Validation`]. Host, port,
+    /// username and realm that were not configured are taken from the session file.
+    ///
+    /// Credentials are optional when a session is loaded: without them the username and realm
+    /// come from the session, and once the server rejects the ticket requests fail with
+    /// [`ProxmoxError::Authentication`] instead of logging in again.
+    pub async fn with_session<R: Read>(self, mut reader: R) -> ProxmoxResult<Self> {
         let mut data = String::new();
         reader.read_to_string(&mut data)?;
-        let auth: ProxmoxAuth = serde_json::from_str(&data)?;
-        // Validate expiration
-        if let Some(csrf) = auth.csrf_token()
-            && csrf.is_expired(self.config.csrf_lifetime)
-        {
-            return Err(ProxmoxError::Session(
-                "Loaded CSRF token is expired".to_string(),
-            ));
-        }
-        if auth.ticket().is_expired(self.config.ticket_lifetime) {
-            return Err(ProxmoxError::Session(
-                "Loaded ticket is expired".to_string(),
-            ));
+        let session: SessionFile = serde_json::from_str(&data)?;
+        self.apply_session(session)
+    }
+
+    /// Like [`with_session`](Self::with_session), but reads a session written by
+    /// [`ProxmoxClient::save_session_encrypted`].
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::SessionDecryption` if the passphrase is wrong or the file was
+    /// tampered with, and the same errors as [`with_session`](Self::with_session) otherwise.
+    pub async fn with_encrypted_session<R: Read>(
+        self,
+        mut reader: R,
+        passphrase: &str,
+    ) -> ProxmoxResult<Self> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        let session = SessionFile::decrypt(&data, passphrase)?;
+        self.apply_session(session)
+    }
+
+    /// Validates a loaded session and fills unset connection settings from it.
+    fn apply_session(mut self, session: SessionFile) -> ProxmoxResult<Self> {
+        session.check_expiry(&self.config)?;
+        session.check_target(self.host.as_deref(), self.port)?;
+
+        self.host = self.host.or(session.host);
+        self.port = self.port.or(session.port);
+        if self.password.is_none() {
+            self.username = self.username.or(session.username);
+            self.realm = self.realm.or(session.realm);
         }
-        self.initial_auth = Some(auth);
+        self.initial_auth = Some(session.auth);
         Ok(self)
     }
 
     /// Constructs a [`ProxmoxClient`] after validating all inputs according to the configuration.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` with [`ValidationError::MissingFields`] naming every
+    /// missing required input (host, username, password and realm), or with the first
+    /// validation failure once all are present.
     pub async fn build(self) -> ProxmoxResult<ProxmoxClient> {
-        // Extract required fields
-        let host_str = self.host.ok_or_else(|| ProxmoxError::Validation {
-            source: ValidationError::Field {
-                field: "host".to_string(),
-                message: "Host is required".to_string(),
-            },
-            backtrace: Backtrace::capture(),
-        })?;
         let port_num = self.port.unwrap_or(8006);
-        let username_str = self.username.ok_or_else(|| ProxmoxError::Validation {
-            source: ValidationError::Field {
-                field: "username".to_string(),
-                message: "Username is required".to_string(),
-            },
-            backtrace: Backtrace::capture(),
-        })?;
-        let password_str = self.password.ok_or_else(|| ProxmoxError::Validation {
-            source: ValidationError::Field {
-                field: "password".to_string(),
-                message: "Password is required".to_string(),
-            },
-            backtrace: Backtrace::capture(),
-        })?;
-        let realm_str = self.realm.ok_or_else(|| ProxmoxError::Validation {
-            source: ValidationError::Field {
-                field: "realm".to_string(),
-                message: "Realm is required".to_string(),
-            },
-            backtrace: Backtrace::capture(),
-        })?;
+
+        // A session restored without a password supplies the user and realm from its ticket.
+        let session_only = self.password.is_none() && self.initial_auth.is_some();
+        let (ticket_user, ticket_realm) = self
+            .initial_auth
+            .as_ref()
+            .filter(|_| session_only)
+            .and_then(|auth| auth.ticket().user_and_realm())
+            .map(|(user, realm)| (user.to_string(), realm.to_string()))
+            .unzip();
+
+        // Report every missing field at once, before any other validation.
+        let has_password = self.password.is_some() || session_only;
+        let (host_str, username_str, realm_str) = match (
+            self.host,
+            self.username.or(ticket_user),
+            self.realm.or(ticket_realm),
+        ) {
+            (Some(host), Some(username), Some(realm)) if has_password => (host, username, realm),
+            (host, username, realm) => {
+                let missing = [
+                    ("host", host.is_none()),
+                    ("username", username.is_none()),
+                    ("password", !has_password),
+                    ("realm", realm.is_none()),
+                ]
+                .into_iter()
+                .filter(|(_, missing)| *missing)
+                .map(|(field, _)| field.to_string())
+                .collect();
+                return Err(ProxmoxError::Validation {
+                    source: ValidationError::MissingFields(missing),
+                    backtrace: Backtrace::capture(),
+                });
+            }
+        };
+        let password_str = self.password;
 
         // Perform validation
         validate_host(&host_str, self.config.resolve_dns).map_err(|e| {
@@ -307,12 +680,14 @@ impl ProxmoxClientBuilder {
                 backtrace: Backtrace::capture(),
             }
         })?;
-        validate_password(&password_str, self.config.password_min_score).map_err(|e| {
-            ProxmoxError::Validation {
-                source: e,
-                backtrace: Backtrace::capture(),
-            }
-        })?;
+        if let Some(password_str) = &password_str {
+            validate_password(password_str, self.config.password_min_score).map_err(|e| {
+                ProxmoxError::Validation {
+                    source: e,
+                    backtrace: Backtrace::capture(),
+                }
+            })?;
+        }
         validate_realm(&realm_str).map_err(|e| ProxmoxError::Validation {
             source: e,
             backtrace: Backtrace::capture(),
@@ -330,22 +705,42 @@ impl ProxmoxClientBuilder {
         let host = ProxmoxHost::new_unchecked(host_str);
         let port = ProxmoxPort::new_unchecked(port_num);
         let username = ProxmoxUsername::new_unchecked(username_str);
-        let password = ProxmoxPassword::new_unchecked(password_str);
         let realm = ProxmoxRealm::new_unchecked(realm_str);
         let url = ProxmoxUrl::new_unchecked(url_str);
 
-        let connection = ProxmoxConnection::new(
-            host,
-            port,
-            username,
-            password,
-            realm,
-            self.secure,
-            self.accept_invalid_certs,
-            url,
-        );
+        let connection = match password_str {
+            Some(password_str) => ProxmoxConnection::new(
+                host,
+                port,
+                username,
+                ProxmoxPassword::new_unchecked(password_str),
+                realm,
+                self.secure,
+                self.accept_invalid_certs,
+                url,
+            ),
+            None => ProxmoxConnection::new_without_password(
+                host,
+                port,
+                username,
+                realm,
+                self.secure,
+                self.accept_invalid_certs,
+                url,
+            ),
+        };
 
-        let api_client = ApiClient::new(connection, self.config.clone())?;
+        let http = HttpOptions {
+            user_agent: self.user_agent,
+            cloudflare_access: self.cloudflare_access,
+            customizer: self.http_customizer,
+            middleware: self.middleware,
+            metrics: self.metrics,
+        };
+        let api_client = ApiClient::with_options(connection, self.config.clone(), http)?;
+        if self.verify_on_build {
+            api_client.probe().await?;
+        }
         if let Some(auth) = self.initial_auth {
             api_client.set_auth(auth).await;
         }
@@ -364,27 +759,92 @@ impl ProxmoxClient {
         ProxmoxClientBuilder::default()
     }
 
+    /// Creates an unauthenticated client from a pre-validated [`ProxmoxConnection`].
+    ///
+    /// This is the low-level alternative to [`builder`](Self::builder) for callers that
+    /// assemble and validate the connection parts themselves. The client uses the default
+    /// User-Agent and no middleware; call [`login`](Self::login) before making requests.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use leeca_proxmox::{
+    ///     ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort,
+    ///     ProxmoxRealm, ProxmoxResult, ProxmoxUrl, ProxmoxUsername, ValidationConfig,
+    /// };
+    ///
+    /// # async fn run() -> ProxmoxResult<()> {
+    /// let connection = ProxmoxConnection::new(
+    ///     ProxmoxHost::new("pve.example.com")?,
+    ///     ProxmoxPort::new(8006)?,
+    ///     ProxmoxUsername::new("automation")?,
+    ///     ProxmoxPassword::new("correct-horse-battery")?,
+    ///     ProxmoxRealm::new("pve")?,
+    ///     true,
+    ///     false,
+    ///     ProxmoxUrl::new("https://pve.example.com:8006/")?,
+    /// );
+    /// let mut client = ProxmoxClient::from_connection(connection, ValidationConfig::default())?;
+    /// client.login().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Connection` if the HTTP client cannot be built.
+    pub fn from_connection(
+        connection: ProxmoxConnection,
+        config: ValidationConfig,
+    ) -> ProxmoxResult<Self> {
+        let api_client =
+            ApiClient::with_options(connection, config.clone(), HttpOptions::default())?;
+        Ok(Self { api_client, config })
+    }
+
     /// Authenticates with the Proxmox VE server.
     ///
     /// This method performs a login using the credentials provided during builder construction
     /// and stores the obtained ticket and CSRF token inside the client.
     pub async fn login(&mut self) -> ProxmoxResult<()> {
-        let service = LoginService::new();
-        let auth = service.execute(self.api_client.connection()).await?;
+        let auth = self.api_client.login().await?;
         self.api_client.set_auth(auth).await;
         Ok(())
     }
 
+    /// Ends the local session by discarding the ticket and CSRF token.
+    ///
+    /// Proxmox has no server-side logout, so the ticket stays valid on the server until it
+    /// expires, but the in-memory copies are zeroized and [`is_authenticated`](Self::is_authenticated)
+    /// returns `false` afterwards. A later API call logs in again with the stored credentials.
+    pub async fn logout(&self) {
+        self.api_client.clear_auth().await;
+    }
+
     /// Returns `true` if the client has a valid (non‑expired) authentication ticket.
     pub async fn is_authenticated(&self) -> bool {
         self.api_client.is_authenticated().await
     }
 
+    /// Returns `true` if the session carries a ticket but no CSRF token.
+    ///
+    /// Such sessions (e.g., restored with [`with_session`](ProxmoxClientBuilder::with_session)
+    /// from a file saved without a CSRF token) can make GET requests with the ticket alone;
+    /// mutating calls fail with `ProxmoxError::Authentication("read-only session")`.
+    pub async fn is_read_only(&self) -> bool {
+        self.api_client.is_read_only().await
+    }
+
     /// Returns the authentication ticket, if any.
     pub async fn auth_token(&self) -> Option<ProxmoxTicket> {
         self.api_client.auth().await.map(|a| a.ticket().clone())
     }
 
+    /// Returns the `user@realm` the current ticket was issued for, or `None` before login.
+    pub async fn whoami(&self) -> Option<String> {
+        let auth = self.api_client.auth().await?;
+        let (user, realm) = auth.ticket().user_and_realm()?;
+        Some(format!("{}@{}", user, realm))
+    }
+
     /// Returns the CSRF token, if any.
     pub async fn csrf_token(&self) -> Option<ProxmoxCSRFToken> {
         self.api_client
@@ -414,6 +874,9 @@ impl ProxmoxClient {
     }
 
     /// Serializes the current authentication state (if any) and saves it to a file.
+    ///
+    /// The file also records the host, port, scheme, username and realm of the connection,
+    /// which are checked when the session is loaded again.
     /// Returns the number of bytes written.
     pub async fn save_session_to_file<P: AsRef<std::path::Path>>(
         &self,
@@ -423,34 +886,53 @@ impl ProxmoxClient {
             Some(auth) => auth,
             None => return Ok(0), // no auth to save
         };
-        let json = serde_json::to_string(&auth)?;
+        let session = SessionFile::new(self.api_client.connection(), auth);
+        let json = serde_json::to_string(&session)?;
         tokio::fs::write(path, &json).await?;
         Ok(json.len())
     }
 
+    /// Saves the current authentication state to a file, encrypted with `passphrase`.
+    ///
+    /// The key is derived from the passphrase with Argon2id and the session is sealed with
+    /// ChaCha20-Poly1305. Load it with [`ProxmoxClientBuilder::with_encrypted_session`].
+    /// Returns the number of bytes written (0 if not authenticated, in which case nothing is
+    /// written).
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Session` if encryption or writing the file fails.
+    pub async fn save_session_encrypted<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        passphrase: &str,
+    ) -> ProxmoxResult<usize> {
+        let auth = match self.api_client.auth().await {
+            Some(auth) => auth,
+            None => return Ok(0),
+        };
+        let session = SessionFile::new(self.api_client.connection(), auth);
+        let data = session.encrypt(passphrase)?;
+        tokio::fs::write(path, &data).await?;
+        Ok(data.len())
+    }
+
     /// Loads an authentication state from a file and sets it as the current auth.
-    /// Returns an error if the data is malformed or if the tokens are already expired
-    /// (according to the client's validation config).
+    /// Returns an error if the data is malformed, if the tokens are already expired
+    /// (according to the client's validation config), or if the session was saved for
+    /// a different host or port.
     pub async fn load_session_from_file<P: AsRef<std::path::Path>>(
         &mut self,
         path: P,
     ) -> ProxmoxResult<()> {
         let data = tokio::fs::read_to_string(path).await?;
-        let auth: ProxmoxAuth = serde_json::from_str(&data)?;
-        // Validate expiration
-        if let Some(csrf) = auth.csrf_token()
-            && csrf.is_expired(self.config.csrf_lifetime)
-        {
-            return Err(ProxmoxError::Session(
-                "Loaded CSRF token is expired".to_string(),
-            ));
-        }
-        if auth.ticket().is_expired(self.config.ticket_lifetime) {
-            return Err(ProxmoxError::Session(
-                "Loaded ticket is expired".to_string(),
-            ));
-        }
-        self.api_client.set_auth(auth).await;
+        let session: SessionFile = serde_json::from_str(&data)?;
+        session.check_expiry(&self.config)?;
+        let connection = self.api_client.connection();
+        session.check_target(
+            Some(connection.host().as_str()),
+            Some(connection.port().get()),
+        )?;
+        self.api_client.set_auth(session.auth).await;
         Ok(())
     }
 
@@ -492,80 +974,182 @@ impl ProxmoxClient {
         self.api_client.get("cluster/resources").await
     }
 
-    /// Lists all nodes in the cluster.
+    /// Retrieves all cluster resources together with the time they were fetched.
     ///
-    /// This method calls the `/nodes` endpoint and returns a list of nodes
-    /// with their basic information and resource usage statistics.
+    /// With a [`CacheConfig`], the list may come from the cache; `fetched_at` then tells how
+    /// stale it is, e.g. to show "as of" on a dashboard.
     ///
     /// # Errors
     /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn cluster_resources_cached(
+        &self,
+    ) -> ProxmoxResult<CachedResponse<Vec<ClusterResource>>> {
+        self.api_client.get_cached("cluster/resources").await
+    }
+
+    /// Retrieves the cluster resources that match `filter`.
+    ///
+    /// The kind is passed to the server as `type` where the API supports it, so large clusters
+    /// send only the relevant entries; the node (and the QEMU/LXC distinction) is applied to
+    /// the response.
     ///
     /// # Example
-    /// ```
-    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
-    /// # use leeca_proxmox::NodeListItem;
-    /// #
-    /// # #[tokio::main]
-    /// # async fn run() -> ProxmoxResult<()> {
+    /// ```no_run
+    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult, ResourceFilter, ResourceKind};
+    /// # async fn example() -> ProxmoxResult<()> {
     /// # let mut client = ProxmoxClient::builder()
-    /// #     .host("example.com")
+    /// #     .host("192.168.1.182")
     /// #     .port(8006)
-    /// #     .credentials("user", "pass", "pam")
+    /// #     .credentials("leeca", "password", "pam")
     /// #     .build().await?;
     /// # client.login().await?;
-    /// let nodes = client.nodes().await?;
-    /// for node in nodes {
-    ///     println!("Node: {} (status: {})", node.node, node.status);
-    /// }
+    /// let filter = ResourceFilter::new().kind(ResourceKind::Qemu).node("pve1");
+    /// let vms = client.cluster_resources_filtered(&filter).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn nodes(&self) -> ProxmoxResult<Vec<NodeListItem>> {
-        self.api_client.get("nodes").await
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn cluster_resources_filtered(
+        &self,
+        filter: &ResourceFilter,
+    ) -> ProxmoxResult<Vec<ClusterResource>> {
+        let path = match filter.kind.and_then(ResourceKind::server_type) {
+            Some(kind) => format!("cluster/resources?type={}", kind),
+            None => "cluster/resources".to_string(),
+        };
+        let mut resources: Vec<ClusterResource> = self.api_client.get(&path).await?;
+        resources.retain(|resource| filter.matches(resource));
+        Ok(resources)
     }
 
-    /// Retrieves detailed status information for a specific node.
-    ///
-    /// This method calls the `/nodes/{node}/status` endpoint and returns
-    /// CPU, memory, swap, uptime, load average, and IO delay information.
+    /// Retrieves cluster membership and quorum information.
     ///
-    /// # Arguments
-    /// * `node` - The name of the node (e.g., "pve1").
+    /// On a standalone node the result holds only the local node entry and no
+    /// [`ClusterStatusEntry::Cluster`] entry; this is not an error.
     ///
     /// # Errors
-    /// Returns [`ProxmoxError`] if the request fails, the node doesn't exist,
-    /// or the response cannot be parsed.
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn cluster_status(&self) -> ProxmoxResult<Vec<ClusterStatusEntry>> {
+        self.api_client.get("cluster/status").await
+    }
+
+    /// Lists the guests managed by the HA stack.
     ///
-    /// # Example
-    /// ```
-    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
-    /// # use leeca_proxmox::NodeStatus;
-    /// #
-    /// # #[tokio::main]
-    /// # async fn run() -> ProxmoxResult<()> {
-    /// # let mut client = ProxmoxClient::builder()
-    /// #     .host("example.com")
-    /// #     .port(8006)
-    /// #     .credentials("user", "pass", "pam")
-    /// #     .build().await?;
-    /// # client.login().await?;
-    /// let status = client.node_status("pve1").await?;
-    /// println!("CPU: {:.2}%, IO Delay: {:.2}%", status.cpu * 100.0, status.wait.unwrap_or(0.0) * 100.0);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn node_status(&self, node: &str) -> ProxmoxResult<NodeStatus> {
-        let path = format!("nodes/{}/status", node);
-        self.api_client.get(&path).await
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn ha_resources(&self) -> ProxmoxResult<Vec<HaResource>> {
+        self.api_client.get("cluster/ha/resources").await
     }
 
-    /// Retrieves DNS configuration for a specific node.
+    /// Sets the requested state of an HA resource.
     ///
-    /// This method calls the `/nodes/{node}/dns` endpoint and returns
-    /// the DNS search domain and list of DNS servers.
+    /// The HA manager applies the change asynchronously; poll
+    /// [`ha_resources`](Self::ha_resources) or the guest status to follow it.
     ///
     /// # Arguments
-    /// * `node` - The name of the node (e.g., "pve1").
+    /// * `sid` - The service identifier (e.g., "vm:100"), percent-encoded into the path.
+    /// * `state` - The state to request.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails (e.g., the resource is not HA-managed).
+    pub async fn set_ha_state(&self, sid: &str, state: HaState) -> ProxmoxResult<()> {
+        let path = format!("cluster/ha/resources/{}", encode_path_segment(sid));
+        self.api_client
+            .put_form(&path, &[("state", state.as_str())])
+            .await
+    }
+
+    /// Returns `true` if the node is part of a cluster, `false` on a standalone node.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn is_cluster(&self) -> ProxmoxResult<bool> {
+        let status = self.cluster_status().await?;
+        Ok(status
+            .iter()
+            .any(|entry| matches!(entry, ClusterStatusEntry::Cluster(_))))
+    }
+
+    /// Returns the next free VM identifier suggested by `/cluster/nextid`.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn next_vmid(&self) -> ProxmoxResult<u32> {
+        let NextVmid(vmid) = self.api_client.get("cluster/nextid").await?;
+        Ok(vmid)
+    }
+
+    /// Returns the lowest free VM identifier at or above `min`, which is raised to
+    /// [`MIN_VMID`] if lower.
+    ///
+    /// `/cluster/nextid?vmid=` only confirms whether a single identifier is free, so the
+    /// candidate is picked from the guests listed in `/cluster/resources` and then confirmed,
+    /// moving on to the following identifier if it is taken.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if no identifier up to [`MAX_VMID`] is free or every
+    /// confirmation attempt collided, or another [`ProxmoxError`] if a request fails.
+    pub async fn next_vmid_from(&self, min: u32) -> ProxmoxResult<u32> {
+        let min = min.max(MIN_VMID);
+        let used: HashSet<u32> = self
+            .cluster_resources()
+            .await?
+            .iter()
+            .filter_map(|resource| match resource {
+                ClusterResource::Qemu(vm) => Some(vm.vmid),
+                ClusterResource::Lxc(ct) => Some(ct.vmid),
+                _ => None,
+            })
+            .collect();
+        let candidate = (min..=MAX_VMID)
+            .find(|vmid| !used.contains(vmid))
+            .ok_or_else(|| no_free_vmid(min))?;
+        self.confirm_free_vmid(candidate).await
+    }
+
+    /// Returns a VM identifier that was free when checked, moving on to the next one if the
+    /// suggestion from `/cluster/nextid` was taken in the meantime.
+    ///
+    /// Nothing is reserved: another client can still create a guest with the same identifier
+    /// before this one does. Parallel provisioners should use
+    /// [`create_vm_with_next_vmid`](Self::create_vm_with_next_vmid), which claims the
+    /// identifier by creating the VM.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if every attempt collided, or another
+    /// [`ProxmoxError`] if a request fails.
+    pub async fn next_vmid_confirmed(&self) -> ProxmoxResult<u32> {
+        let candidate = self.next_vmid().await?;
+        self.confirm_free_vmid(candidate).await
+    }
+
+    /// Confirms `candidate` is free, moving on to the following identifier on collision.
+    async fn confirm_free_vmid(&self, mut candidate: u32) -> ProxmoxResult<u32> {
+        for _ in 0..CONFIRM_VMID_ATTEMPTS {
+            let path = format!("cluster/nextid?vmid={}", candidate);
+            match self.api_client.get::<NextVmid>(&path).await {
+                Ok(NextVmid(vmid)) => return Ok(vmid),
+                Err(e) if is_vmid_collision(&e) => {
+                    if candidate >= MAX_VMID {
+                        return Err(no_free_vmid(candidate));
+                    }
+                    candidate += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(ProxmoxError::Unexpected(format!(
+            "No free VM identifier found after {} attempts",
+            CONFIRM_VMID_ATTEMPTS
+        )))
+    }
+
+    /// Lists all nodes in the cluster.
+    ///
+    /// This method calls the `/nodes` endpoint and returns a list of nodes
+    /// with their basic information and resource usage statistics.
     ///
     /// # Errors
     /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
@@ -573,7 +1157,7 @@ impl ProxmoxClient {
     /// # Example
     /// ```
     /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
-    /// # use leeca_proxmox::NodeDnsConfig;
+    /// # use leeca_proxmox::NodeListItem;
     /// #
     /// # #[tokio::main]
     /// # async fn run() -> ProxmoxResult<()> {
@@ -583,29 +1167,33 @@ impl ProxmoxClient {
     /// #     .credentials("user", "pass", "pam")
     /// #     .build().await?;
     /// # client.login().await?;
-    /// let dns = client.node_dns("pve1").await?;
-    /// println!("Search domain: {}", dns.domain);
-    /// println!("DNS servers: {:?}", dns.servers);
+    /// let nodes = client.nodes().await?;
+    /// for node in nodes {
+    ///     println!("Node: {} (status: {})", node.node, node.status);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn node_dns(&self, node: &str) -> ProxmoxResult<NodeDnsConfig> {
-        let path = format!("nodes/{}/dns", node);
-        self.api_client.get(&path).await
+    pub async fn nodes(&self) -> ProxmoxResult<Vec<NodeListItem>> {
+        self.api_client.get("nodes").await
     }
 
-    /// Lists all QEMU virtual machines on a specific node.
+    /// Retrieves detailed status information for a specific node.
+    ///
+    /// This method calls the `/nodes/{node}/status` endpoint and returns
+    /// CPU, memory, swap, uptime, load average, and IO delay information.
     ///
     /// # Arguments
     /// * `node` - The name of the node (e.g., "pve1").
     ///
     /// # Errors
-    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    /// Returns [`ProxmoxError`] if the request fails, the node doesn't exist,
+    /// or the response cannot be parsed.
     ///
     /// # Example
     /// ```
     /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
-    /// # use leeca_proxmox::VmListItem;
+    /// # use leeca_proxmox::NodeStatus;
     /// #
     /// # #[tokio::main]
     /// # async fn run() -> ProxmoxResult<()> {
@@ -615,23 +1203,137 @@ impl ProxmoxClient {
     /// #     .credentials("user", "pass", "pam")
     /// #     .build().await?;
     /// # client.login().await?;
-    /// let vms = client.vms("pve1").await?;
-    /// for vm in vms {
-    ///     println!("VM {} (ID: {}): {}", vm.name, vm.vmid, vm.status);
-    /// }
+    /// let status = client.node_status("pve1").await?;
+    /// println!("CPU: {:.2}%, IO Delay: {:.2}%", status.cpu * 100.0, status.wait.unwrap_or(0.0) * 100.0);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn vms(&self, node: &str) -> ProxmoxResult<Vec<VmListItem>> {
-        let path = format!("nodes/{}/qemu", node);
+    pub async fn node_status(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<NodeStatus> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/status", node);
         self.api_client.get(&path).await
     }
 
-    /// Retrieves detailed current status of a specific VM.
+    /// Reboots or shuts down a node.
+    ///
+    /// Every guest on the node is stopped or migrated according to its HA and startup
+    /// settings, so the call must be confirmed explicitly.
     ///
     /// # Arguments
-    /// * `node` - The name of the node where the VM resides.
-    /// * `vmid` - The VM identifier.
+    /// * `node` - The name of the node (e.g., "pve1").
+    /// * `action` - Whether to reboot or shut down.
+    /// * `confirm` - Must be `true`; otherwise nothing is sent.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if `confirm` is `false`, or another
+    /// [`ProxmoxError`] if the request fails.
+    pub async fn node_power(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        action: NodePowerAction,
+        confirm: bool,
+    ) -> ProxmoxResult<()> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        if !confirm {
+            return Err(ProxmoxError::Validation {
+                source: ValidationError::Field {
+                    field: "confirm".to_string(),
+                    message: format!(
+                        "Refusing to {} node '{}' without confirmation",
+                        action, node
+                    ),
+                },
+                backtrace: Backtrace::capture(),
+            });
+        }
+        let path = format!("nodes/{}/status", node);
+        self.api_client
+            .post_form(&path, &[("command", action.as_str())])
+            .await
+    }
+
+    /// Lists the package updates available on a node.
+    ///
+    /// The list reflects the node's last package index refresh; it does not trigger one.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn node_apt_updates(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<AptPackage>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/apt/update", node);
+        self.api_client.get(&path).await
+    }
+
+    /// Lists the versions of the Proxmox-related packages installed on a node.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn node_apt_versions(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<PackageVersion>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/apt/versions", node);
+        self.api_client.get(&path).await
+    }
+
+    /// Reports whether a node runs an older kernel than the newest one installed,
+    /// i.e. a kernel update is waiting for a reboot.
+    ///
+    /// The running kernel comes from [`node_status`](Self::node_status) and the installed
+    /// kernels from [`node_apt_versions`](Self::node_apt_versions). A node without
+    /// recognizable kernel packages is reported as not needing a reboot.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if the running kernel cannot be determined, or
+    /// another [`ProxmoxError`] if a request fails.
+    pub async fn node_needs_reboot(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<bool> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let status = self.node_status(&node).await?;
+        let packages = self.node_apt_versions(&node).await?;
+        let running = status
+            .running_kernel_release()
+            .or_else(|| packages.iter().find_map(|p| p.running_kernel.as_deref()))
+            .ok_or_else(|| {
+                ProxmoxError::Unexpected(format!(
+                    "Node '{}' did not report its running kernel",
+                    node
+                ))
+            })?;
+        let latest = packages
+            .iter()
+            .filter_map(PackageVersion::kernel_release)
+            .max_by(|a, b| compare_kernel_releases(a, b));
+        Ok(latest.is_some_and(|latest| {
+            compare_kernel_releases(latest, running) == std::cmp::Ordering::Greater
+        }))
+    }
+
+    /// Retrieves DNS configuration for a specific node.
+    ///
+    /// This method calls the `/nodes/{node}/dns` endpoint and returns
+    /// the DNS search domain and list of DNS servers.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
     ///
     /// # Errors
     /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
@@ -639,7 +1341,7 @@ impl ProxmoxClient {
     /// # Example
     /// ```
     /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
-    /// # use leeca_proxmox::VmStatusCurrent;
+    /// # use leeca_proxmox::NodeDnsConfig;
     /// #
     /// # #[tokio::main]
     /// # async fn run() -> ProxmoxResult<()> {
@@ -649,147 +1351,1692 @@ impl ProxmoxClient {
     /// #     .credentials("user", "pass", "pam")
     /// #     .build().await?;
     /// # client.login().await?;
-    /// let status = client.vm_status("pve1", 100).await?;
-    /// println!("VM {} is {}", status.name, status.status);
+    /// let dns = client.node_dns("pve1").await?;
+    /// println!("Search domain: {}", dns.domain);
+    /// println!("DNS servers: {:?}", dns.servers);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn vm_status(&self, node: &str, vmid: u32) -> ProxmoxResult<VmStatusCurrent> {
-        let path = format!("nodes/{}/qemu/{}/status/current", node, vmid);
+    pub async fn node_dns(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<NodeDnsConfig> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/dns", node);
         self.api_client.get(&path).await
     }
 
-    /// Starts a VM.
+    /// Retrieves the clock and timezone of a node.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
     ///
-    /// Returns a task ID (UPID) that can be used to track the operation.
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn node_time(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<NodeTime> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/time", node);
+        self.api_client.get(&path).await
+    }
+
+    /// Sets the timezone of a node.
     ///
     /// # Arguments
-    /// * `node` - The node where the VM resides.
-    /// * `vmid` - The VM identifier.
+    /// * `node` - The name of the node (e.g., "pve1").
+    /// * `timezone` - A zone name from the tz database (e.g., "Europe/Madrid").
     ///
     /// # Errors
-    /// Returns [`ProxmoxError`] if the request fails.
-    pub async fn start_vm(&self, node: &str, vmid: u32) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}/status/start", node, vmid);
-        self.api_client.post(&path, &serde_json::json!({})).await
+    /// Returns [`ProxmoxError`] if the request fails (e.g., the zone is unknown to the node).
+    pub async fn set_node_timezone(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        timezone: &str,
+    ) -> ProxmoxResult<()> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/time", node);
+        self.api_client
+            .put(&path, &serde_json::json!({ "timezone": timezone }))
+            .await
     }
 
-    /// Stops a VM immediately (like pulling the plug).
+    /// Aborts a running task (e.g., a stuck migration).
     ///
-    /// Returns a task ID.
-    pub async fn stop_vm(&self, node: &str, vmid: u32) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}/status/stop", node, vmid);
-        self.api_client.post(&path, &serde_json::json!({})).await
+    /// # Arguments
+    /// * `node` - The node running the task.
+    /// * `upid` - The task ID returned by the operation.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the UPID is malformed, or another
+    /// [`ProxmoxError`] if the request fails.
+    pub async fn stop_task(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        upid: &str,
+    ) -> ProxmoxResult<()> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let task = TaskId::parse(upid)?;
+        let path = format!("nodes/{}/tasks/{}", node, task.as_str());
+        self.api_client.delete(&path).await
     }
 
-    /// Shuts down a VM gracefully (ACPI signal).
+    /// Aborts a running task, using the node encoded in its UPID.
     ///
-    /// Returns a task ID.
-    pub async fn shutdown_vm(&self, node: &str, vmid: u32) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}/status/shutdown", node, vmid);
-        self.api_client.post(&path, &serde_json::json!({})).await
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn stop_task_id(&self, task: &TaskId) -> ProxmoxResult<()> {
+        self.stop_task(task.node(), task.as_str()).await
     }
 
-    /// Reboots a VM (like pressing reset button).
+    /// Retrieves the current status of a task.
     ///
-    /// Returns a task ID.
-    pub async fn reboot_vm(&self, node: &str, vmid: u32) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}/status/reboot", node, vmid);
-        self.api_client.post(&path, &serde_json::json!({})).await
+    /// # Arguments
+    /// * `node` - The node running the task.
+    /// * `upid` - The task ID returned by the operation.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the UPID is malformed, or another
+    /// [`ProxmoxError`] if the request fails.
+    pub async fn task_status(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        upid: &str,
+    ) -> ProxmoxResult<TaskStatus> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let task = TaskId::parse(upid)?;
+        let path = format!("nodes/{}/tasks/{}/status", node, task.as_str());
+        self.api_client.get(&path).await
     }
 
-    /// Hard resets a VM.
+    /// Polls a task until it stops.
     ///
-    /// Returns a task ID.
-    pub async fn reset_vm(&self, node: &str, vmid: u32) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}/status/reset", node, vmid);
-        self.api_client.post(&path, &serde_json::json!({})).await
+    /// The final status is returned whether or not the task succeeded; check
+    /// [`TaskStatus::is_success`].
+    ///
+    /// # Arguments
+    /// * `node` - The node running the task.
+    /// * `upid` - The task ID returned by the operation.
+    /// * `timeout` - Maximum time to wait for the task to stop.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task is still running after `timeout`, or
+    /// another [`ProxmoxError`] if a status request fails.
+    pub async fn wait_for_task(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        upid: &str,
+        timeout: Duration,
+    ) -> ProxmoxResult<TaskStatus> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let poll = async {
+            loop {
+                let status = self.task_status(&node, upid).await?;
+                if !status.is_running() {
+                    return Ok(status);
+                }
+                tokio::time::sleep(TASK_POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(timeout, poll).await.map_err(|_| {
+            ProxmoxError::Timeout(format!("Task {} did not finish within {:?}", upid, timeout))
+        })?
     }
 
-    /// Deletes a VM.
+    /// Polls a task until it stops, calling `on_progress` after every poll.
     ///
-    /// By default, this also removes associated disks. Use `purge: false` to keep disks.
+    /// Each status passed to `on_progress` (and the returned one) carries
+    /// [`TaskStatus::progress`], the latest percentage printed in the task log so far, if the
+    /// task reports one (e.g., backups, disk moves and storage downloads).
     ///
     /// # Arguments
-    /// * `node` - The node where the VM resides.
-    /// * `vmid` - The VM identifier.
-    /// * `purge` - If `true` (default), remove VM from configuration and all related data (disks, snapshots, backups). If `false`, only remove the configuration, keeping disks.
+    /// * `node` - The node running the task.
+    /// * `upid` - The task ID returned by the operation.
+    /// * `timeout` - Maximum time to wait for the task to stop.
+    /// * `on_progress` - Called with the current status on each poll, including the final one.
     ///
-    /// Returns a task ID.
-    pub async fn delete_vm(&self, node: &str, vmid: u32, purge: bool) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}", node, vmid);
-        // For DELETE with query parameters, we need to construct URL with params.
-        // Proxmox API accepts query parameters for DELETE requests.
-        let url = if purge {
-            format!("{}?purge=1", path)
-        } else {
-            format!("{}?purge=0", path)
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task is still running after `timeout`, or
+    /// another [`ProxmoxError`] if a status or log request fails.
+    pub async fn wait_for_task_with_progress<F>(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        upid: &str,
+        timeout: Duration,
+        mut on_progress: F,
+    ) -> ProxmoxResult<TaskStatus>
+    where
+        F: FnMut(&TaskStatus),
+    {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let log_path = format!("nodes/{}/tasks/{}/log", node, TaskId::parse(upid)?.as_str());
+        let poll = async {
+            let mut next_line = 0;
+            let mut progress = None;
+            loop {
+                let mut status = self.task_status(&node, upid).await?;
+                let lines: Vec<TaskLogLine> = self
+                    .api_client
+                    .get(&format!("{}?start={}&limit=500", log_path, next_line))
+                    .await?;
+                for line in &lines {
+                    next_line = next_line.max(line.n);
+                    progress = parse_progress(&line.t).or(progress);
+                }
+                status.progress = progress;
+                on_progress(&status);
+                if !status.is_running() {
+                    return Ok(status);
+                }
+                tokio::time::sleep(TASK_POLL_INTERVAL).await;
+            }
         };
-        self.api_client.delete(&url).await
+        tokio::time::timeout(timeout, poll).await.map_err(|_| {
+            ProxmoxError::Timeout(format!("Task {} did not finish within {:?}", upid, timeout))
+        })?
     }
 
-    /// Creates a new VM.
+    /// Streams a node's task history, newest first, fetching `options.limit` tasks per request.
+    ///
+    /// Pages are requested with `start += limit` until a page comes back with fewer than
+    /// `limit` tasks. The stream ends after the first error.
     ///
     /// # Arguments
-    /// * `node` - The node where to create the VM.
-    /// * `params` - Creation parameters (see [`CreateVmParams`]).
+    /// * `node` - The node whose tasks are listed.
+    /// * `options` - Page size and filters (see [`TaskListOptions`]).
     ///
-    /// Returns a task ID.
+    /// # Example
+    /// ```no_run
+    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult, TaskListOptions};
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn run(client: &ProxmoxClient) -> ProxmoxResult<()> {
+    /// let options = TaskListOptions::new().typefilter("vzdump").errors(true);
+    /// let failed: Vec<_> = client.node_tasks_stream("pve1", &options).try_collect().await?;
+    /// println!("{} failed backups", failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn node_tasks_stream<'a>(
+        &'a self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        options: &TaskListOptions,
+    ) -> impl Stream<Item = ProxmoxResult<TaskListItem>> + 'a {
+        let node: ProxmoxResult<NodeName> = node.try_into().map_err(Into::into);
+        let options = TaskListOptions {
+            limit: options.limit.max(1),
+            ..options.clone()
+        };
+        future::ready(node)
+            .map_ok(move |node| {
+                stream::try_unfold(Some(0), move |start: Option<u64>| {
+                    let request = start.map(|start| {
+                        let path = format!("nodes/{}/tasks?{}", node, options.to_query(start));
+                        (start + options.limit, path)
+                    });
+                    let limit = options.limit;
+                    async move {
+                        let Some((next_start, path)) = request else {
+                            return ProxmoxResult::Ok(None);
+                        };
+                        let page: Vec<TaskListItem> = self.api_client.get(&path).await?;
+                        let next = (page.len() as u64 >= limit).then_some(next_start);
+                        Ok(Some((stream::iter(page.into_iter().map(Ok)), next)))
+                    }
+                })
+                .try_flatten()
+            })
+            .try_flatten_stream()
+    }
+
+    /// Retrieves the Proxmox VE version of the server.
+    ///
+    /// Use [`VersionInfo::parsed`] to compare against a minimum version.
     ///
     /// # Errors
-    /// Returns [`ProxmoxError`] if validation fails or the request cannot be sent.
-    pub async fn create_vm(&self, node: &str, params: &CreateVmParams) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu", node);
-        self.api_client.post(&path, params).await
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn version(&self) -> ProxmoxResult<VersionInfo> {
+        self.api_client.get("version").await
     }
 
-    /// Retrieves the full configuration of a VM.
+    /// Retrieves the effective permissions of the current user.
+    ///
+    /// The result maps each ACL path (e.g., "/vms/100") to its privileges (e.g., "VM.PowerMgmt"),
+    /// with `1` for privileges that may be propagated further and `0` otherwise; privileges the
+    /// user lacks are absent.
     ///
     /// # Arguments
-    /// * `node` - The node where the VM resides.
-    /// * `vmid` - The VM identifier.
+    /// * `path` - Only report this ACL path (and the privileges inherited on it); `None` reports
+    ///   every path the user has permissions on.
     ///
-    /// Returns the current configuration.
-    pub async fn vm_config(&self, node: &str, vmid: u32) -> ProxmoxResult<VmConfig> {
-        let path = format!("nodes/{}/qemu/{}/config", node, vmid);
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn access_permissions(
+        &self,
+        path: Option<&str>,
+    ) -> ProxmoxResult<HashMap<String, HashMap<String, u8>>> {
+        let request = match path {
+            Some(path) => format!("access/permissions?path={}", encode_path_segment(path)),
+            None => "access/permissions".to_string(),
+        };
+        self.api_client.get(&request).await
+    }
+
+    /// Checks that the server is reachable and accepts the current session.
+    ///
+    /// Unlike [`is_authenticated`](Self::is_authenticated), which only inspects the local
+    /// ticket, this makes an authenticated `GET /version` round trip (logging in again first if
+    /// the ticket has expired). The response body is not parsed.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Http` if the server cannot be reached,
+    /// `ProxmoxError::Authentication` if the session is rejected and cannot be refreshed, or
+    /// another [`ProxmoxError`] if the server answers with an error status.
+    pub async fn ping(&self) -> ProxmoxResult<()> {
+        self.api_client
+            .get::<serde::de::IgnoredAny>("version")
+            .await
+            .map(|_| ())
+    }
+
+    /// Lists the network interfaces configured on a node.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn node_networks(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<NetworkInterface>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/network", node);
         self.api_client.get(&path).await
     }
 
-    /// Updates the configuration of a VM.
+    /// Applies pending network configuration changes on a node.
     ///
     /// # Arguments
-    /// * `node` - The node where the VM resides.
-    /// * `vmid` - The VM identifier.
-    /// * `params` - Parameters to update (fields with `Some` value will be updated, `None` leaves unchanged).
+    /// * `node` - The name of the node (e.g., "pve1").
     ///
     /// Returns a task ID.
     ///
-    /// # Note
-    /// To delete a parameter, you need to set it to `null` or use a special value. The Proxmox API uses
-    /// the `delete` query parameter for that. This is not yet supported; for now only setting values is possible.
-    pub async fn update_vm_config(
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn reload_node_network(
         &self,
-        node: &str,
-        vmid: u32,
-        params: &CreateVmParams, // Reusing CreateVmParams with Option fields works for updates
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
     ) -> ProxmoxResult<String> {
-        let path = format!("nodes/{}/qemu/{}/config", node, vmid);
-        self.api_client.put(&path, params).await
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/network", node);
+        self.api_client.put(&path, &serde_json::json!({})).await
     }
-}
-
-#[cfg(test)]
-mod tests {
-    mod integration;
-    mod resources;
-    use super::*;
-    use std::time::Duration;
 
-    #[test]
-    fn test_builder_default_secure() {
-        let builder = ProxmoxClientBuilder::default();
-        assert!(builder.secure);
-        assert!(!builder.accept_invalid_certs);
+    /// Lists the system services of a specific node.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn node_services(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<NodeService>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/services", node);
+        self.api_client.get(&path).await
+    }
+
+    /// Starts, stops, restarts or reloads a system service on a node.
+    ///
+    /// Useful for restarting daemons such as `pvestatd` after configuration changes.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    /// * `service` - The service identifier (e.g., "pveproxy").
+    /// * `action` - The action to perform.
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if `service` is not made of lowercase letters,
+    /// digits and hyphens (nothing is sent then), or another [`ProxmoxError`] if the request
+    /// fails.
+    pub async fn node_service_action(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        service: &str,
+        action: ServiceAction,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let valid = !service.is_empty()
+            && service
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+        if !valid {
+            return Err(ProxmoxError::Validation {
+                source: ValidationError::Field {
+                    field: "service".to_string(),
+                    message: format!("Invalid service name '{}'", service),
+                },
+                backtrace: Backtrace::capture(),
+            });
+        }
+        let path = format!("nodes/{}/services/{}/{}", node, service, action.as_str());
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Lists all QEMU virtual machines on a specific node.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node (e.g., "pve1").
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+    /// # use leeca_proxmox::VmListItem;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn run() -> ProxmoxResult<()> {
+    /// # let mut client = ProxmoxClient::builder()
+    /// #     .host("example.com")
+    /// #     .port(8006)
+    /// #     .credentials("user", "pass", "pam")
+    /// #     .build().await?;
+    /// # client.login().await?;
+    /// let vms = client.vms("pve1").await?;
+    /// for vm in vms {
+    ///     println!("VM {} (ID: {}): {}", vm.name, vm.vmid, vm.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vms(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<VmListItem>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu", node);
+        self.api_client.get(&path).await
+    }
+
+    /// Retrieves detailed current status of a specific VM.
+    ///
+    /// # Arguments
+    /// * `node` - The name of the node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// # use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+    /// # use leeca_proxmox::VmStatusCurrent;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn run() -> ProxmoxResult<()> {
+    /// # let mut client = ProxmoxClient::builder()
+    /// #     .host("example.com")
+    /// #     .port(8006)
+    /// #     .credentials("user", "pass", "pam")
+    /// #     .build().await?;
+    /// # client.login().await?;
+    /// let status = client.vm_status("pve1", 100).await?;
+    /// println!("VM {} is {}", status.name, status.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vm_status(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<VmStatusCurrent> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/status/current", node, vmid);
+        self.api_client.get(&path).await
+    }
+
+    /// Starts a VM.
+    ///
+    /// Returns the task ID (UPID) that can be used to track the operation, or
+    /// [`TaskOutcome::Sync`] if the server answered with `{"data": null}`.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn start_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/status/start", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Starts a VM and waits for the task to finish, using
+    /// [`ValidationConfig::task_timeout`].
+    ///
+    /// Returns the final task status; check [`TaskStatus::is_success`]. Returns `None` without
+    /// polling if the server completed the operation synchronously.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task does not finish in time, or another
+    /// [`ProxmoxError`] if a request fails.
+    pub async fn start_vm_and_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Option<TaskStatus>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let outcome = self.start_vm(&node, vmid).await?;
+        self.wait_for_outcome(&node, outcome).await
+    }
+
+    /// Stops a VM immediately (like pulling the plug).
+    ///
+    /// Returns a task ID, or [`TaskOutcome::Sync`] if the server answered synchronously.
+    pub async fn stop_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/status/stop", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Stops a VM and waits for the task to finish, using
+    /// [`ValidationConfig::task_timeout`].
+    ///
+    /// Returns the final task status; check [`TaskStatus::is_success`]. Returns `None` without
+    /// polling if the server completed the operation synchronously.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task does not finish in time, or another
+    /// [`ProxmoxError`] if a request fails.
+    pub async fn stop_vm_and_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Option<TaskStatus>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let outcome = self.stop_vm(&node, vmid).await?;
+        self.wait_for_outcome(&node, outcome).await
+    }
+
+    /// Stops every running VM on a node.
+    ///
+    /// The stop requests are issued concurrently (still subject to the configured rate
+    /// limit), and a failure for one VM does not prevent the others from being stopped.
+    /// VMs that are not running are skipped.
+    ///
+    /// Returns one `(vmid, result)` pair per running VM, in the order the node listed them.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] only if the VM list cannot be retrieved; per-VM failures are
+    /// reported in the returned vector.
+    pub async fn stop_all_vms(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<(u32, ProxmoxResult<TaskOutcome>)>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let running: Vec<u32> = self
+            .vms(&node)
+            .await?
+            .into_iter()
+            .filter(|vm| vm.status == PowerStatus::Running)
+            .map(|vm| vm.vmid)
+            .collect();
+        let node = &node;
+        let stops = running
+            .iter()
+            .map(|&vmid| async move { (vmid, self.stop_vm(node, vmid).await) });
+        Ok(futures::future::join_all(stops).await)
+    }
+
+    /// Shuts down a VM gracefully (ACPI signal).
+    ///
+    /// Returns a task ID, or [`TaskOutcome::Sync`] if the server answered synchronously.
+    pub async fn shutdown_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/status/shutdown", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Shuts down a VM and waits for the task to finish, using
+    /// [`ValidationConfig::task_timeout`].
+    ///
+    /// Returns the final task status; check [`TaskStatus::is_success`]. Returns `None` without
+    /// polling if the server completed the operation synchronously.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task does not finish in time, or another
+    /// [`ProxmoxError`] if a request fails.
+    pub async fn shutdown_vm_and_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Option<TaskStatus>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let outcome = self.shutdown_vm(&node, vmid).await?;
+        self.wait_for_outcome(&node, outcome).await
+    }
+
+    /// Shuts down a VM gracefully, stopping it if it is still up after `grace`.
+    ///
+    /// The VM status is polled every second. When the grace period expires, the pending
+    /// shutdown task is aborted (it holds the VM lock) and a hard stop is issued without
+    /// waiting for it to finish.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `grace` - How long the guest may take to shut down on its own.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the shutdown, a status request or the stop request fails.
+    pub async fn shutdown_vm_with_fallback(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        grace: Duration,
+    ) -> ProxmoxResult<ShutdownOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let shutdown = self.shutdown_vm(&node, vmid).await?;
+        let stopped = async {
+            loop {
+                if self.vm_status(&node, vmid).await?.status == PowerStatus::Stopped {
+                    return ProxmoxResult::Ok(());
+                }
+                tokio::time::sleep(TASK_POLL_INTERVAL).await;
+            }
+        };
+        match tokio::time::timeout(grace, stopped).await {
+            Ok(result) => result.map(|()| ShutdownOutcome::Graceful),
+            Err(_) => {
+                // The shutdown task may have ended on its own meanwhile; the stop is what counts.
+                if let Some(task) = shutdown.task_id() {
+                    let _ = self.stop_task_id(task).await;
+                }
+                self.stop_vm(&node, vmid).await.map(ShutdownOutcome::Forced)
+            }
+        }
+    }
+
+    /// Reboots a VM (like pressing reset button).
+    ///
+    /// Returns a task ID, or [`TaskOutcome::Sync`] if the server answered synchronously.
+    pub async fn reboot_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/status/reboot", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Reboots a VM and waits for the task to finish, using
+    /// [`ValidationConfig::task_timeout`].
+    ///
+    /// Returns the final task status; check [`TaskStatus::is_success`]. Returns `None` without
+    /// polling if the server completed the operation synchronously.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task does not finish in time, or another
+    /// [`ProxmoxError`] if a request fails.
+    pub async fn reboot_vm_and_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Option<TaskStatus>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let outcome = self.reboot_vm(&node, vmid).await?;
+        self.wait_for_outcome(&node, outcome).await
+    }
+
+    /// Hard resets a VM.
+    ///
+    /// Returns a task ID, or [`TaskOutcome::Sync`] if the server answered synchronously.
+    pub async fn reset_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/status/reset", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Resets a VM and waits for the task to finish, using
+    /// [`ValidationConfig::task_timeout`].
+    ///
+    /// Returns the final task status; check [`TaskStatus::is_success`]. Returns `None` without
+    /// polling if the server completed the operation synchronously.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Timeout` if the task does not finish in time, or another
+    /// [`ProxmoxError`] if a request fails.
+    pub async fn reset_vm_and_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Option<TaskStatus>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let outcome = self.reset_vm(&node, vmid).await?;
+        self.wait_for_outcome(&node, outcome).await
+    }
+
+    async fn wait_for_outcome(
+        &self,
+        node: &NodeName,
+        outcome: TaskOutcome,
+    ) -> ProxmoxResult<Option<TaskStatus>> {
+        match outcome {
+            TaskOutcome::Sync => Ok(None),
+            TaskOutcome::Async(task) => self
+                .wait_for_task(node, task.as_str(), self.config.task_timeout)
+                .await
+                .map(Some),
+        }
+    }
+
+    /// Deletes a VM with the given [`DeleteVmOptions`].
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `options` - Flags sent as query parameters (`purge`, `destroy-unreferenced-disks`,
+    ///   `skiplock`).
+    ///
+    /// Returns a task ID.
+    pub async fn delete_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        options: &DeleteVmOptions,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}?{}", node, vmid, options.to_query());
+        self.api_client.delete(&path).await
+    }
+
+    /// Deletes a VM, only choosing whether to purge it.
+    ///
+    /// The VM's disks are always removed. With `purge: true` it is also removed from backup
+    /// jobs, replication jobs and HA resources. Use [`delete_vm`](Self::delete_vm) for the
+    /// remaining flags.
+    ///
+    /// Returns a task ID.
+    pub async fn delete_vm_simple(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        purge: bool,
+    ) -> ProxmoxResult<String> {
+        self.delete_vm(node, vmid, &DeleteVmOptions::new().purge(purge))
+            .await
+    }
+
+    /// Creates a new VM.
+    ///
+    /// # Arguments
+    /// * `node` - The node where to create the VM.
+    /// * `params` - Creation parameters (see [`CreateVmParams`]), sent form-encoded like
+    ///   `pvesh` does.
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if [`CreateVmParams::validate`] rejects the
+    /// parameters (nothing is sent then), or another [`ProxmoxError`] if the request fails.
+    pub async fn create_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &CreateVmParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        params.validate()?;
+        let path = format!("nodes/{}/qemu", node);
+        self.api_client.post_form(&path, params).await
+    }
+
+    /// Creates a VM under the next free identifier, using the creation itself as the claim.
+    ///
+    /// `params.vmid` is replaced by the identifier suggested by `/cluster/nextid`. The server
+    /// refuses to create a VM whose identifier is already in use, so when another client
+    /// takes the identifier first the creation fails with "already exists" and is retried
+    /// with the next free identifier (up to 5 attempts). Two parallel callers therefore never
+    /// end up with the same identifier.
+    ///
+    /// Returns the identifier that was claimed and the creation task ID.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if every attempt collided, `ProxmoxError::Validation`
+    /// if the parameters are invalid, or another [`ProxmoxError`] if a request fails.
+    pub async fn create_vm_with_next_vmid(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &CreateVmParams,
+    ) -> ProxmoxResult<(u32, String)> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let mut params = params.clone();
+        let mut previous = None;
+        for _ in 0..CONFIRM_VMID_ATTEMPTS {
+            let suggested = self.next_vmid().await?;
+            params.vmid = match previous {
+                // `/cluster/nextid` may not see the colliding guest yet.
+                Some(taken) if suggested <= taken => taken + 1,
+                _ => suggested,
+            };
+            match self.create_vm(&node, &params).await {
+                Ok(upid) => return Ok((params.vmid, upid)),
+                Err(e) if is_vmid_collision(&e) => previous = Some(params.vmid),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(ProxmoxError::Unexpected(format!(
+            "No free VM identifier claimed after {} attempts",
+            CONFIRM_VMID_ATTEMPTS
+        )))
+    }
+
+    /// Creates a new container.
+    ///
+    /// # Arguments
+    /// * `node` - The node where to create the container.
+    /// * `params` - Creation parameters (see [`CreateLxcParams`]).
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if [`CreateLxcParams::validate`] rejects the
+    /// parameters (nothing is sent then), or another [`ProxmoxError`] if the request fails.
+    pub async fn create_container(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &CreateLxcParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        params.validate()?;
+        let path = format!("nodes/{}/lxc", node);
+        self.api_client.post_form(&path, params).await
+    }
+
+    /// Retrieves the configuration of a container.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the container resides.
+    /// * `vmid` - The container identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn container_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<LxcConfig> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/lxc/{}/config", node, vmid);
+        self.api_client.get(&path).await
+    }
+
+    /// Updates the configuration of a container.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the container resides.
+    /// * `vmid` - The container identifier.
+    /// * `params` - The keys to set and to delete (see [`UpdateLxcConfig`]).
+    ///
+    /// Returns [`TaskOutcome::Sync`] when the change was applied immediately, or the task ID
+    /// if the server deferred it to a task.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Conflict` if [`UpdateLxcConfig::digest`] no longer matches, or
+    /// another [`ProxmoxError`] if the request fails.
+    pub async fn update_container_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &UpdateLxcConfig,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/lxc/{}/config", node, vmid);
+        self.api_client.put_form(&path, params).await
+    }
+
+    /// Creates a VM unless one with the same identifier already exists on the node.
+    ///
+    /// The configuration is read first; only when the server reports that the VM does not
+    /// exist is [`create_vm`](Self::create_vm) called, so retrying after a network error does
+    /// not fail with "already exists". An existing VM is returned as is, even if its
+    /// configuration differs from `params`.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM should exist.
+    /// * `params` - Creation parameters (see [`CreateVmParams`]).
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the parameters are invalid, or another
+    /// [`ProxmoxError`] if a request fails for any reason other than the VM being absent.
+    pub async fn ensure_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &CreateVmParams,
+    ) -> ProxmoxResult<EnsuredVm> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        params.validate()?;
+        match self.vm_config(&node, params.vmid).await {
+            Ok(config) => Ok(EnsuredVm::Existing(Box::new(config))),
+            Err(e) if is_missing_vm(&e) => {
+                Ok(EnsuredVm::Created(self.create_vm(&node, params).await?))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves a VM's configuration with the changes that wait for the next restart.
+    ///
+    /// Changes to a running VM that cannot be hot-plugged are stored as pending; use
+    /// [`PendingConfigItem::requires_restart`] to find them.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn vm_pending_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<PendingConfigItem>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/pending", node, vmid);
+        self.api_client.get(&path).await
+    }
+
+    /// Retrieves the full configuration of a VM.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// Returns the current configuration.
+    pub async fn vm_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<VmConfig> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/config", node, vmid);
+        let mut config: VmConfig = self.api_client.get(&path).await?;
+        config.vmid = vmid.get();
+        Ok(config)
+    }
+
+    /// Requests a VNC proxy ticket for the VM console.
+    ///
+    /// Tickets are short-lived and are not cached; call this for every new connection.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn vm_vncproxy(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<VncProxy> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/vncproxy", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Requests SPICE connection settings for the VM console.
+    ///
+    /// Tickets are short-lived and are not cached; call this for every new connection.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn vm_spiceproxy(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<SpiceProxy> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/spiceproxy", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Checks whether a VM can be migrated, and to which nodes.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `target` - The intended target node, whose storage is checked.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn migrate_vm_preconditions(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        target: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<MigratePreconditions> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let target: NodeName = target.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/migrate?target={}", node, vmid, target);
+        self.api_client.get(&path).await
+    }
+
+    /// Converts a VM into a template.
+    ///
+    /// Templates cannot be started; clone them with [`vm_clone`](Self::vm_clone). The VM
+    /// must be stopped. The conversion cannot be undone through the API.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// Returns [`TaskOutcome::Sync`] if the conversion finished immediately, or the task ID
+    /// if the server runs it as a task.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails (e.g., the VM is running).
+    pub async fn template_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/template", node, vmid);
+        self.api_client.post(&path, &serde_json::json!({})).await
+    }
+
+    /// Clones a VM.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the source VM resides.
+    /// * `vmid` - The source VM identifier.
+    /// * `params` - Clone parameters, including an optional `bwlimit` for full clones.
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn vm_clone(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &CloneVmParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/clone", node, vmid);
+        self.api_client.post(&path, params).await
+    }
+
+    /// Creates a full clone of a VM, waits for it to finish and returns the new VM's
+    /// identifier and configuration.
+    ///
+    /// `params.full` is forced on. When `params.target` names another node, the
+    /// configuration is read from that node.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the source VM resides.
+    /// * `vmid` - The source VM identifier.
+    /// * `params` - Clone parameters.
+    /// * `timeout` - Maximum time to wait for the clone task.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::TaskFailed` if the clone task fails, `ProxmoxError::Timeout` if it
+    /// is still running after `timeout`, or another [`ProxmoxError`] if a request fails.
+    pub async fn clone_vm_full(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &CloneVmParams,
+        timeout: Duration,
+    ) -> ProxmoxResult<(u32, VmConfig)> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let params = CloneVmParams {
+            full: Some(1),
+            ..params.clone()
+        };
+        let upid = self.vm_clone(&node, vmid, &params).await?;
+        let status = self.wait_for_task(&node, &upid, timeout).await?;
+        if !status.is_success() {
+            return Err(ProxmoxError::TaskFailed(format!(
+                "Cloning VM {} to {} failed ({}): {}",
+                vmid,
+                params.newid,
+                upid,
+                status.exitstatus.as_deref().unwrap_or("unknown error")
+            )));
+        }
+        let target = params.target.as_deref().unwrap_or(node.as_str());
+        let config = self.vm_config(target, params.newid).await?;
+        Ok((params.newid, config))
+    }
+
+    /// Moves a VM disk to another storage.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `params` - The disk, target storage and move options.
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn move_disk(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &MoveDiskParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/move_disk", node, vmid);
+        self.api_client.post(&path, params).await
+    }
+
+    /// Moves a VM disk and waits for the move to finish.
+    ///
+    /// If the move task fails and the VM is left locked, the lock is released with
+    /// [`unlock_vm`](Self::unlock_vm) before the failure is reported.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `params` - The disk, target storage and move options.
+    /// * `timeout` - Maximum time to wait for the move task.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::TaskFailed` with the task's exit status if the move fails (noting
+    /// whether the lock could be released), `ProxmoxError::Timeout` if the task is still running
+    /// after `timeout` (the VM is not unlocked then), or another [`ProxmoxError`] if a request fails.
+    pub async fn move_disk_and_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &MoveDiskParams,
+        timeout: Duration,
+    ) -> ProxmoxResult<TaskStatus> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let upid = self.move_disk(&node, vmid, params).await?;
+        let status = self.wait_for_task(&node, &upid, timeout).await?;
+        if status.is_success() {
+            return Ok(status);
+        }
+
+        let exit = status.exitstatus.as_deref().unwrap_or("unknown error");
+        let lock_note = match self.vm_config(&node, vmid).await {
+            Ok(config) => match config.lock {
+                Some(lock) => match self.unlock_vm(&node, vmid).await {
+                    Ok(()) => format!("; released leftover '{}' lock", lock),
+                    Err(e) => format!("; VM is still locked ('{}'): {}", lock, e),
+                },
+                None => String::new(),
+            },
+            Err(e) => format!("; could not check VM lock: {}", e),
+        };
+        Err(ProxmoxError::TaskFailed(format!(
+            "Moving {} of VM {} to '{}' failed ({}): {}{}",
+            params.disk, vmid, params.storage, upid, exit, lock_note
+        )))
+    }
+
+    /// Removes the lock from a VM (equivalent to `qm unlock`).
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn unlock_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<()> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/config", node, vmid);
+        self.api_client
+            .put_form(&path, &[("delete", "lock"), ("skiplock", "1")])
+            .await
+    }
+
+    /// Updates the configuration of a VM.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `params` - The keys to set and to delete (see [`UpdateVmConfig`]). Set
+    ///   [`UpdateVmConfig::digest`] to the digest from [`vm_config`](Self::vm_config) to
+    ///   reject the update if someone else changed the configuration in the meantime.
+    ///
+    /// Returns [`TaskOutcome::Sync`] when the change was applied immediately, which is the
+    /// usual case, or the task ID if the server deferred it to a task.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Conflict` if the digest no longer matches, or another
+    /// [`ProxmoxError`] if the request fails.
+    pub async fn update_vm_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &UpdateVmConfig,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/config", node, vmid);
+        self.api_client.put_form(&path, params).await
+    }
+
+    /// Converges a VM configuration to exactly `desired`.
+    ///
+    /// Unlike [`update_vm_config`](Self::update_vm_config), which merges, this fetches the
+    /// current configuration and sends a single update that sets every changed key and
+    /// deletes every key the server has but `desired` lacks (see [`UpdateVmConfig::diff`]).
+    /// Nothing is sent if the configuration already matches. The update carries the digest
+    /// that was read, so a concurrent change makes it fail with `ProxmoxError::Conflict`
+    /// instead of being overwritten.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `desired` - The complete configuration the VM should end up with.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if a request fails.
+    pub async fn replace_vm_config(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        desired: &VmConfig,
+    ) -> ProxmoxResult<TaskOutcome> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let current = self.vm_config(&node, vmid).await?;
+        let mut update = UpdateVmConfig::diff(&current, desired)?;
+        if update.is_empty() {
+            return Ok(TaskOutcome::Sync);
+        }
+        update.digest = current.digest;
+        self.update_vm_config(&node, vmid, &update).await
+    }
+
+    /// Retrieves the metadata of a storage volume.
+    ///
+    /// # Arguments
+    /// * `node` - The node the storage is attached to.
+    /// * `storage` - The storage identifier (e.g., "local").
+    /// * `volid` - The volume identifier (e.g., "local:iso/debian-12.iso").
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn storage_volume(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        storage: &str,
+        volid: &str,
+    ) -> ProxmoxResult<StorageVolume> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = format!(
+            "nodes/{}/storage/{}/content/{}",
+            node,
+            storage,
+            encode_path_segment(volid)
+        );
+        self.api_client.get(&path).await
+    }
+
+    /// Uploads an ISO image or container template to a storage.
+    ///
+    /// The data is streamed from `data` without being buffered in memory. `len` must be the
+    /// exact number of bytes `data` yields: the Proxmox API proxy does not accept chunked
+    /// uploads, so the part length is sent up front.
+    ///
+    /// # Arguments
+    /// * `node` - The node the storage is attached to.
+    /// * `storage` - The storage identifier (e.g., "local").
+    /// * `content_type` - Whether the file is an ISO image or a container template.
+    /// * `filename` - The name the file gets on the storage.
+    /// * `data` - The file contents.
+    /// * `len` - The size of the file in bytes.
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Authentication` if the ticket expired during the upload (it is
+    /// refreshed, so retrying works), or another [`ProxmoxError`] if the request fails.
+    pub async fn upload_to_storage<R>(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        storage: &str,
+        content_type: UploadContentType,
+        filename: &str,
+        data: R,
+        len: u64,
+    ) -> ProxmoxResult<String>
+    where
+        R: tokio::io::AsyncRead + Send + Sync + 'static,
+    {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let body = reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(data));
+        let file = reqwest::multipart::Part::stream_with_length(body, len)
+            .file_name(filename.to_string())
+            .mime_str("application/octet-stream")
+            .map_err(|e| ProxmoxError::Unexpected(format!("Invalid upload MIME type: {}", e)))?;
+        let form = reqwest::multipart::Form::new()
+            .text("content", content_type.as_str())
+            .part("filename", file);
+        let path = format!("nodes/{}/storage/{}/upload", node, storage);
+        self.api_client.post_multipart(&path, form).await
+    }
+
+    /// Checks a storage volume against an expected checksum.
+    ///
+    /// The digest is taken from the volume metadata ([`storage_volume`](Self::storage_volume)).
+    /// Storages that do not record checksums cannot be verified this way.
+    ///
+    /// # Arguments
+    /// * `node` - The node the storage is attached to.
+    /// * `storage` - The storage identifier (e.g., "local").
+    /// * `volid` - The volume identifier.
+    /// * `expected` - The checksum the volume should have.
+    ///
+    /// Returns `true` if the digests match.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Unexpected` if the storage reports no checksum for the volume,
+    /// `ProxmoxError::Validation` if it reports one with a different algorithm, or another
+    /// [`ProxmoxError`] if the request fails.
+    pub async fn verify_storage_content(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        storage: &str,
+        volid: &str,
+        expected: &Checksum,
+    ) -> ProxmoxResult<bool> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let volume = self.storage_volume(&node, storage, volid).await?;
+        let actual = volume.checksum()?.ok_or_else(|| {
+            ProxmoxError::Unexpected(format!(
+                "Storage '{}' does not report a checksum for '{}'",
+                storage, volid
+            ))
+        })?;
+        if actual.algorithm() != expected.algorithm() {
+            return Err(ProxmoxError::Validation {
+                source: ValidationError::Field {
+                    field: "checksum".to_string(),
+                    message: format!(
+                        "Storage reports a {} checksum for '{}', expected {}",
+                        actual.algorithm(),
+                        volid,
+                        expected.algorithm()
+                    ),
+                },
+                backtrace: Backtrace::capture(),
+            });
+        }
+        Ok(actual.digest() == expected.digest())
+    }
+
+    /// Backs up a guest with `vzdump`.
+    ///
+    /// This method calls `/nodes/{node}/vzdump`. Pass the returned task ID to
+    /// [`wait_for_task`](Self::wait_for_task) to wait for the archive to be written.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the guest resides.
+    /// * `vmid` - The guest identifier.
+    /// * `params` - Storage, mode, compression and notes (see [`BackupParams`]).
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn backup_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        params: &BackupParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/vzdump", node);
+        self.api_client
+            .post(&path, &params.to_body(vmid.get()))
+            .await
+    }
+
+    /// Lists the backup archives available on a storage.
+    ///
+    /// This method calls `/nodes/{node}/storage/{storage}/content` with `content=backup`.
+    ///
+    /// # Arguments
+    /// * `node` - The node the storage is attached to.
+    /// * `storage` - The storage identifier (e.g., "local").
+    /// * `vmid` - If set, only backups of this guest are returned. The filter is sent to the
+    ///   server and also applied to the response, since some storages ignore it.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn list_backups(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        storage: &str,
+        vmid: Option<VmId>,
+    ) -> ProxmoxResult<Vec<BackupFile>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let mut path = format!("nodes/{}/storage/{}/content?content=backup", node, storage);
+        if let Some(vmid) = vmid {
+            path.push_str(&format!("&vmid={}", vmid));
+        }
+        let mut backups: Vec<BackupFile> = self.api_client.get(&path).await?;
+        if let Some(vmid) = vmid {
+            backups.retain(|backup| backup.vmid == Some(vmid.get()));
+        }
+        Ok(backups)
+    }
+
+    /// Restores a guest from a backup archive.
+    ///
+    /// Issues a VM or container creation request with the archive set, which is how
+    /// Proxmox performs restores (`qmrestore` / `pct restore`).
+    ///
+    /// # Arguments
+    /// * `node` - The node where the guest is restored.
+    /// * `params` - The archive, target identifier and optional overrides (see [`RestoreParams`]).
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails.
+    pub async fn restore_backup(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &RestoreParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let path = match params.guest_type {
+            GuestType::Qemu => format!("nodes/{}/qemu", node),
+            GuestType::Lxc => format!("nodes/{}/lxc", node),
+        };
+        self.api_client.post_form(&path, &params.to_body()).await
+    }
+
+    /// Restores a VM from a backup archive (the `qmrestore` equivalent).
+    ///
+    /// Unlike [`restore_backup`](Self::restore_backup), the archive is checked to be a backup
+    /// volume ID before anything is sent.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM is restored.
+    /// * `params` - The archive, target identifier and optional overrides; `guest_type` must be
+    ///   [`GuestType::Qemu`].
+    ///
+    /// Returns a task ID.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Validation` if the archive is not a backup volume ID or the
+    /// parameters describe a container, or [`ProxmoxError`] if the request fails.
+    pub async fn restore_vm(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        params: &RestoreParams,
+    ) -> ProxmoxResult<String> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        if params.guest_type != GuestType::Qemu {
+            return Err(ProxmoxError::Validation {
+                source: ValidationError::Field {
+                    field: "guest_type".to_string(),
+                    message: "restore_vm restores VMs only; use restore_backup for containers"
+                        .to_string(),
+                },
+                backtrace: Backtrace::capture(),
+            });
+        }
+        params.validate_archive()?;
+        self.restore_backup(&node, params).await
+    }
+
+    /// Lists the guest's network interfaces and addresses through the QEMU guest agent.
+    ///
+    /// Useful for discovering the addresses a VM obtained after boot.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    ///
+    /// # Errors
+    /// Returns `ProxmoxError::Connection("guest agent not responding")` if the agent is not
+    /// running or does not answer, or [`ProxmoxError`] if the request otherwise fails.
+    pub async fn vm_agent_interfaces(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+    ) -> ProxmoxResult<Vec<AgentInterface>> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/agent/network-get-interfaces", node, vmid);
+        let response: AgentResponse<Vec<AgentInterface>> = self
+            .api_client
+            .get(&path)
+            .await
+            .map_err(map_agent_unavailable)?;
+        Ok(response.result)
+    }
+
+    /// Runs a command inside the guest through the QEMU guest agent.
+    ///
+    /// The command is started asynchronously; use [`agent_exec_status`](Self::agent_exec_status)
+    /// with the returned PID to retrieve its output, or [`agent_exec_wait`](Self::agent_exec_wait)
+    /// to do both in one call.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `command` - The program followed by its arguments.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails (e.g., the guest agent is not running).
+    pub async fn agent_exec(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        command: &[String],
+    ) -> ProxmoxResult<AgentExecPid> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/agent/exec", node, vmid);
+        self.api_client
+            .post(&path, &serde_json::json!({ "command": command }))
+            .await
+    }
+
+    /// Retrieves the status of a process started with [`agent_exec`](Self::agent_exec).
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError`] if the request fails or the response cannot be parsed.
+    pub async fn agent_exec_status(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        pid: u64,
+    ) -> ProxmoxResult<AgentExecStatus> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let path = format!("nodes/{}/qemu/{}/agent/exec-status?pid={}", node, vmid, pid);
+        self.api_client.get(&path).await
+    }
+
+    /// Runs a command inside the guest and waits for it to exit.
+    ///
+    /// Polls `exec-status` until the agent reports the process as exited and returns its
+    /// exit code together with the decoded standard output and error.
+    ///
+    /// # Arguments
+    /// * `node` - The node where the VM resides.
+    /// * `vmid` - The VM identifier.
+    /// * `command` - The program followed by its arguments.
+    /// * `timeout` - Maximum time to wait for the process to exit.
+    ///
+    /// # Errors
+    /// Returns [`ProxmoxError::Timeout`] if the process is still running after `timeout`,
+    /// or any error from the underlying requests.
+    pub async fn agent_exec_wait(
+        &self,
+        node: impl TryInto<NodeName, Error: Into<ProxmoxError>>,
+        vmid: impl TryInto<VmId, Error: Into<ProxmoxError>>,
+        command: &[String],
+        timeout: Duration,
+    ) -> ProxmoxResult<AgentExecResult> {
+        let node: NodeName = node.try_into().map_err(Into::into)?;
+        let vmid: VmId = vmid.try_into().map_err(Into::into)?;
+        let pid = self.agent_exec(&node, vmid, command).await?.pid;
+        let poll = async {
+            loop {
+                let status = self.agent_exec_status(&node, vmid, pid).await?;
+                if status.exited {
+                    return Ok(AgentExecResult::from_status(pid, status));
+                }
+                tokio::time::sleep(AGENT_POLL_INTERVAL).await;
+            }
+        };
+        tokio::time::timeout(timeout, poll).await.map_err(|_| {
+            ProxmoxError::Timeout(format!(
+                "Guest command (pid {}) did not exit within {:?}",
+                pid, timeout
+            ))
+        })?
+    }
+}
+
+/// Messages Proxmox uses when the guest agent cannot be reached.
+const AGENT_UNAVAILABLE_MARKERS: [&str; 3] = [
+    "guest agent is not running",
+    "qmp command 'guest-",
+    "got timeout",
+];
+
+/// Replaces an API error saying the guest agent is unreachable with a uniform message.
+fn map_agent_unavailable(error: ProxmoxError) -> ProxmoxError {
+    match error {
+        ProxmoxError::Api {
+            status: 500,
+            message,
+        } if AGENT_UNAVAILABLE_MARKERS
+            .iter()
+            .any(|marker| message.contains(marker)) =>
+        {
+            ProxmoxError::Connection("guest agent not responding".to_string())
+        }
+        other => other,
+    }
+}
+
+/// Returns `true` if an API error says the requested guest does not exist.
+///
+/// Proxmox answers with 404 on some versions and with a 500 "Configuration file ... does not
+/// exist" on others.
+fn is_missing_vm(error: &ProxmoxError) -> bool {
+    match error {
+        ProxmoxError::Api { status, message } => {
+            *status == 404 || message.contains("does not exist")
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if the server rejected a VM identifier because it is already in use.
+fn is_vmid_collision(error: &ProxmoxError) -> bool {
+    matches!(error, ProxmoxError::Api { message, .. } if message.contains("already exists"))
+}
+
+/// Error for a VM identifier search that ran past [`MAX_VMID`].
+fn no_free_vmid(min: u32) -> ProxmoxError {
+    ProxmoxError::Unexpected(format!("No free VM identifier at or above {}", min))
+}
+
+/// Percent-encodes a value for use as a single API path segment (e.g., a volume ID).
+fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                encoded.push(char::from(byte))
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "blocking")]
+    mod blocking_tests;
+    mod integration;
+    mod pool_tests;
+    mod resources;
+    use super::*;
+    use crate::core::domain::value_object::fresh_ticket;
+    use std::time::Duration;
+
+    #[test]
+    fn test_builder_default_secure() {
+        let builder = ProxmoxClientBuilder::default();
+        assert!(builder.secure);
+        assert!(!builder.accept_invalid_certs);
     }
 
     #[tokio::test]
@@ -799,7 +3046,7 @@ mod tests {
             .credentials("user", "pass", "pam");
         let err = builder.build().await.unwrap_err();
         assert!(
-            matches!(err, ProxmoxError::Validation { source: ValidationError::Field { field, .. }, .. } if field == "host")
+            matches!(err, ProxmoxError::Validation { source: ValidationError::MissingFields(ref fields), .. } if fields == &["host"])
         );
     }
 
@@ -810,8 +3057,40 @@ mod tests {
             .port(8006);
         let err = builder.build().await.unwrap_err();
         assert!(
-            matches!(err, ProxmoxError::Validation { source: ValidationError::Field { field, .. }, .. } if field == "username")
+            matches!(err, ProxmoxError::Validation { source: ValidationError::MissingFields(ref fields), .. } if fields == &["username", "password", "realm"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder_reports_all_missing_fields() {
+        let err = ProxmoxClientBuilder::default().build().await.unwrap_err();
+        let ProxmoxError::Validation {
+            source: ValidationError::MissingFields(fields),
+            ..
+        } = &err
+        else {
+            panic!("expected missing fields, got {:?}", err);
+        };
+        assert_eq!(fields, &["host", "username", "password", "realm"]);
+        assert_eq!(
+            err.to_string(),
+            "Validation error: Missing required fields: host, username, password, realm"
         );
+
+        // Present fields are still validated once nothing is missing.
+        let err = ProxmoxClientBuilder::default()
+            .host("bad_host!")
+            .credentials("user", "password123", "pam")
+            .build()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProxmoxError::Validation {
+                source: ValidationError::Format(_),
+                ..
+            }
+        ));
     }
 
     #[tokio::test]
@@ -864,29 +3143,159 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_client_login_no_auth() {
-        let client = ProxmoxClientBuilder::default()
-            .host("example.com")
+    async fn test_client_login_no_auth() {
+        let client = ProxmoxClientBuilder::default()
+            .host("example.com")
+            .port(8006)
+            .credentials("user", "password123", "pam")
+            .build()
+            .await
+            .unwrap();
+        assert!(!client.is_authenticated().await);
+    }
+
+    #[tokio::test]
+    async fn test_builder_enable_methods() {
+        let builder = ProxmoxClientBuilder::default()
+            .host("example.com")
+            .port(8006)
+            .credentials("root", "password", "pam") // length 8, weak password
+            .enable_password_strength(3)
+            .enable_dns_resolution()
+            .block_reserved_usernames();
+        // Should fail because password weak and username reserved
+        let err = builder.build().await.unwrap_err();
+        assert!(matches!(err, ProxmoxError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_builder_verify_on_build_unreachable() {
+        let err = ProxmoxClientBuilder::default()
+            .host("127.0.0.1")
+            .port(1)
+            .credentials("user", "password123", "pam")
+            .secure(false)
+            .verify_on_build(true)
+            .build()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProxmoxError::Connection(_)));
+    }
+
+    fn env_lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_builder_from_lookup_populates_fields() {
+        let builder = ProxmoxClientBuilder::from_lookup(env_lookup(&[
+            ("PROXMOX_HOST", "pve.example.com"),
+            ("PROXMOX_PORT", "8006"),
+            ("PROXMOX_USERNAME", "automation"),
+            ("PROXMOX_PASSWORD", "Str0ng!Pass123"),
+            ("PROXMOX_REALM", "pve"),
+            ("PROXMOX_SECURE", "false"),
+        ]))
+        .unwrap();
+        assert_eq!(builder.host.as_deref(), Some("pve.example.com"));
+        assert_eq!(builder.port, Some(8006));
+        assert_eq!(builder.username.as_deref(), Some("automation"));
+        assert_eq!(builder.password.as_deref(), Some("Str0ng!Pass123"));
+        assert_eq!(builder.realm.as_deref(), Some("pve"));
+        assert!(!builder.secure);
+        assert!(builder.accept_invalid_certs);
+    }
+
+    #[test]
+    fn test_builder_from_lookup_lists_missing_vars() {
+        let err = ProxmoxClientBuilder::from_lookup(env_lookup(&[
+            ("PROXMOX_HOST", "pve.example.com"),
+            ("PROXMOX_PORT", "8006"),
+            ("PROXMOX_REALM", ""),
+        ]))
+        .unwrap_err();
+        let ProxmoxError::Validation {
+            source: ValidationError::Field { message, .. },
+            ..
+        } = err
+        else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(
+            message,
+            "Missing required environment variables: PROXMOX_USERNAME, PROXMOX_PASSWORD, PROXMOX_REALM"
+        );
+    }
+
+    #[test]
+    fn test_builder_from_lookup_invalid_values() {
+        let base = [
+            ("PROXMOX_HOST", "pve.example.com"),
+            ("PROXMOX_USERNAME", "automation"),
+            ("PROXMOX_PASSWORD", "Str0ng!Pass123"),
+            ("PROXMOX_REALM", "pam"),
+        ];
+        let mut bad_port = base.to_vec();
+        bad_port.push(("PROXMOX_PORT", "eighty"));
+        assert!(ProxmoxClientBuilder::from_lookup(env_lookup(&bad_port)).is_err());
+
+        let mut bad_secure = base.to_vec();
+        bad_secure.extend([("PROXMOX_PORT", "8006"), ("PROXMOX_SECURE", "maybe")]);
+        assert!(ProxmoxClientBuilder::from_lookup(env_lookup(&bad_secure)).is_err());
+    }
+
+    #[test]
+    fn test_builder_from_lookup_defaults_to_secure() {
+        let builder = ProxmoxClientBuilder::from_lookup(env_lookup(&[
+            ("PROXMOX_HOST", "env.example.com"),
+            ("PROXMOX_PORT", "8443"),
+            ("PROXMOX_USERNAME", "envuser"),
+            ("PROXMOX_PASSWORD", "Str0ng!Pass123"),
+            ("PROXMOX_REALM", "pam"),
+        ]))
+        .unwrap();
+        assert_eq!(builder.port, Some(8443));
+        assert!(builder.secure);
+    }
+
+    #[tokio::test]
+    async fn test_logout_clears_auth() {
+        let client = ProxmoxClient::builder()
+            .host("127.0.0.1")
             .port(8006)
-            .credentials("user", "password123", "pam")
+            .credentials("user", "Str0ng!Pass123", "pam")
+            .secure(false)
             .build()
             .await
             .unwrap();
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("user@pam", "sig"));
+        let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, Some(csrf)))
+            .await;
+        assert!(client.is_authenticated().await);
+
+        client.logout().await;
         assert!(!client.is_authenticated().await);
+        assert!(client.auth_token().await.is_none());
+        assert!(client.csrf_token().await.is_none());
     }
 
     #[tokio::test]
-    async fn test_builder_enable_methods() {
-        let builder = ProxmoxClientBuilder::default()
-            .host("example.com")
-            .port(8006)
-            .credentials("root", "password", "pam") // length 8, weak password
-            .enable_password_strength(3)
-            .enable_dns_resolution()
-            .block_reserved_usernames();
-        // Should fail because password weak and username reserved
-        let err = builder.build().await.unwrap_err();
-        assert!(matches!(err, ProxmoxError::Validation { .. }));
+    async fn test_builder_without_verify_skips_probe() {
+        let client = ProxmoxClientBuilder::default()
+            .host("127.0.0.1")
+            .port(1)
+            .credentials("user", "password123", "pam")
+            .secure(false)
+            .build()
+            .await;
+        assert!(client.is_ok());
     }
 
     #[test]
@@ -932,6 +3341,682 @@ mod tests {
         assert!(!client.is_csrf_expired().await);
     }
 
+    #[tokio::test]
+    async fn test_builder_ticket_lifetime() {
+        use crate::core::domain::model::proxmox_auth::ProxmoxAuth;
+        use crate::core::domain::value_object::ProxmoxTicket;
+
+        let client = ProxmoxClientBuilder::default()
+            .host("example.com")
+            .port(8006)
+            .credentials("user", "password", "pam")
+            .ticket_lifetime(Duration::from_secs(30 * 60))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(client.config.ticket_lifetime, Duration::from_secs(1800));
+
+        let issued_ago = |minutes: u64| {
+            let issued = std::time::SystemTime::now() - Duration::from_secs(minutes * 60);
+            let secs = issued
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            ProxmoxAuth::new(
+                ProxmoxTicket::new_unchecked(format!("PVE:user@pam:{:X}::sig", secs)),
+                None,
+            )
+        };
+
+        client.api_client.set_auth(issued_ago(20)).await;
+        assert!(!client.is_ticket_expired().await);
+        assert!(client.is_authenticated().await);
+
+        client.api_client.set_auth(issued_ago(45)).await;
+        assert!(client.is_ticket_expired().await);
+        assert!(!client.is_authenticated().await);
+    }
+
+    #[tokio::test]
+    async fn test_configure_http_applies_to_requests() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .and(header("user-agent", "inventory-bot/2.1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        let client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .configure_http(|http| http.user_agent("inventory-bot/2.1"))
+            .build()
+            .await
+            .unwrap();
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, Some(csrf)))
+            .await;
+
+        assert!(client.nodes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_on_login_and_requests() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .and(header("user-agent", "fleet-manager/3.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("automation@pve", "sig"),
+                    "CSRFPreventionToken": "4EEC61E2:lwk7od06fa1+DcPUwBTXCcndyAY/3mKxQp5vR8sNjWuBtL9fZg=="
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .and(header("user-agent", "fleet-manager/3.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        let mut client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .user_agent("fleet-manager/3.0")
+            .build()
+            .await
+            .unwrap();
+        client.login().await.unwrap();
+        assert!(client.nodes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_login_reuses_api_http_client() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .and(header("x-client-instance", "shared"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("automation@pve", "sig"),
+                    "CSRFPreventionToken": "4EEC61E2:lwk7od06fa1+DcPUwBTXCcndyAY/3mKxQp5vR8sNjWuBtL9fZg=="
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .and(header("x-client-instance", "shared"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // A default header configured on the one HTTP client shows up on both requests.
+        let address = mock_server.address();
+        let mut client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .configure_http(|http| {
+                let mut headers = reqwest::header::HeaderMap::new();
+                headers.insert(
+                    "x-client-instance",
+                    reqwest::header::HeaderValue::from_static("shared"),
+                );
+                http.default_headers(headers)
+            })
+            .build()
+            .await
+            .unwrap();
+        client.login().await.unwrap();
+        assert!(client.nodes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_middleware_injects_header_and_sees_response() {
+        use crate::middleware::{Middleware, RequestParts, ResponseParts, async_trait};
+        use std::sync::Mutex;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        #[derive(Default)]
+        struct Recorder {
+            seen: Mutex<Vec<(String, u16)>>,
+        }
+
+        #[async_trait]
+        impl Middleware for Recorder {
+            async fn on_request(&self, request: &mut RequestParts) {
+                request
+                    .headers
+                    .insert("x-correlation-id", "deploy-42".parse().unwrap());
+            }
+
+            async fn on_response(&self, response: &ResponseParts) {
+                self.seen
+                    .lock()
+                    .unwrap()
+                    .push((response.url.path().to_string(), response.status.as_u16()));
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .and(header("x-correlation-id", "deploy-42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let recorder = Arc::new(Recorder::default());
+        let address = mock_server.address();
+        let client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .with_middleware(recorder.clone())
+            .build()
+            .await
+            .unwrap();
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, Some(csrf)))
+            .await;
+
+        assert!(client.nodes().await.unwrap().is_empty());
+        assert_eq!(
+            *recorder.seen.lock().unwrap(),
+            vec![("/api2/json/nodes".to_string(), 200)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cloudflare_access_headers_on_requests() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .and(header("cf-access-client-id", "0123abcd.access"))
+            .and(header("cf-access-client-secret", "s3cr3t"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        let client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .cloudflare_access("0123abcd.access", "s3cr3t")
+            .build()
+            .await
+            .unwrap();
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, Some(csrf)))
+            .await;
+
+        assert!(client.nodes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_default_user_agent() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/version"))
+            .and(header(
+                "user-agent",
+                concat!("leeca_proxmox/", env!("CARGO_PKG_VERSION")),
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .verify_on_build(true)
+            .build()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_session_restore_without_credentials() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        let session = serde_json::to_string(&ProxmoxAuth::new(ticket, Some(csrf))).unwrap();
+
+        let address = mock_server.address();
+        let client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .secure(false)
+            .with_session(session.as_bytes())
+            .await
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(
+            client.api_client.connection().username().as_str(),
+            "automation"
+        );
+        assert_eq!(client.api_client.connection().realm().as_str(), "pve");
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+
+        assert!(client.nodes().await.unwrap().is_empty());
+        match client.nodes().await {
+            Err(ProxmoxError::Authentication(message)) => {
+                assert!(message.contains("No credentials available"));
+            }
+            other => panic!("expected an authentication error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_with_invalid_ticket_user_is_an_error() {
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("bad user!@pve", "sig"));
+        let session = serde_json::to_string(&ProxmoxAuth::new(ticket, None)).unwrap();
+
+        let result = ProxmoxClient::builder()
+            .host("pve.example.com")
+            .secure(false)
+            .with_session(session.as_bytes())
+            .await
+            .unwrap()
+            .build()
+            .await;
+        assert!(matches!(
+            result,
+            Err(ProxmoxError::Validation {
+                source: ValidationError::Format(ref message),
+                ..
+            }) if message.starts_with("Username")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_session_allows_reads_and_refuses_mutations() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("monitor@pve", "sig"));
+        let session = serde_json::to_string(&ProxmoxAuth::new(ticket, None)).unwrap();
+
+        let address = mock_server.address();
+        let client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .secure(false)
+            .with_session(session.as_bytes())
+            .await
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert!(client.is_read_only().await);
+
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        assert!(client.nodes().await.unwrap().is_empty());
+        match client.start_vm("pve1", 100).await {
+            Err(ProxmoxError::Authentication(message)) => assert_eq!(message, "read-only session"),
+            other => panic!("expected a read-only error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_authentication() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("automation@pve", "sig"),
+                    "CSRFPreventionToken": "4EEC61E2:token"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        let mut client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .build()
+            .await
+            .unwrap();
+        let other = client.clone();
+        assert!(!other.is_authenticated().await);
+
+        client.login().await.unwrap();
+        assert!(other.is_authenticated().await);
+
+        let tasks = [client, other].map(|c| tokio::spawn(async move { c.nodes().await }));
+        for task in tasks {
+            assert!(task.await.unwrap().unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_from_connection() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api2/json/access/ticket"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "ticket": fresh_ticket("automation@pve", "sig"),
+                    "CSRFPreventionToken": "4EEC61E2:token"
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        let connection = ProxmoxConnection::new(
+            ProxmoxHost::new(address.ip().to_string()).unwrap(),
+            ProxmoxPort::new(address.port()).unwrap(),
+            ProxmoxUsername::new("automation").unwrap(),
+            ProxmoxPassword::new("Str0ng!Pass123").unwrap(),
+            ProxmoxRealm::new("pve").unwrap(),
+            false,
+            false,
+            ProxmoxUrl::new(format!("{}/", mock_server.uri())).unwrap(),
+        );
+        let mut client =
+            ProxmoxClient::from_connection(connection, ValidationConfig::default()).unwrap();
+        client.login().await.unwrap();
+        assert!(client.nodes().await.unwrap().is_empty());
+
+        assert!(ProxmoxPort::new(0).is_err());
+        assert!(ProxmoxHost::new("bad host").is_err());
+        assert!(ProxmoxUrl::new("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_without_credentials_or_session_fails() {
+        let result = ProxmoxClient::builder()
+            .host("127.0.0.1")
+            .port(8006)
+            .secure(false)
+            .build()
+            .await;
+        assert!(matches!(result, Err(ProxmoxError::Validation { .. })));
+    }
+
+    fn session_test_client(host: &str, port: u16) -> ProxmoxClient {
+        let connection = ProxmoxConnection::new(
+            ProxmoxHost::new_unchecked(host.to_string()),
+            ProxmoxPort::new_unchecked(port),
+            ProxmoxUsername::new_unchecked("automation".to_string()),
+            ProxmoxPassword::new_unchecked("pass".to_string()),
+            ProxmoxRealm::new_unchecked("pve".to_string()),
+            true,
+            false,
+            ProxmoxUrl::new_unchecked(format!("https://{}:{}/", host, port)),
+        );
+        ProxmoxClient {
+            api_client: ApiClient::new(connection, ValidationConfig::default()).unwrap(),
+            config: ValidationConfig::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_file_includes_connection_metadata() {
+        let client = session_test_client("pve-a.example.com", 8006);
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, None))
+            .await;
+
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        client.save_session_to_file(temp_file.path()).await.unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(temp_file.path()).unwrap()).unwrap();
+        assert_eq!(saved["host"], "pve-a.example.com");
+        assert_eq!(saved["port"], 8006);
+        assert_eq!(saved["secure"], true);
+        assert_eq!(saved["username"], "automation");
+        assert_eq!(saved["realm"], "pve");
+
+        // Matching host and port load fine.
+        let restored = ProxmoxClient::builder()
+            .host("pve-a.example.com")
+            .port(8006)
+            .with_session(std::fs::File::open(temp_file.path()).unwrap())
+            .await
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert!(restored.is_authenticated().await);
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_session_round_trip() {
+        let client = session_test_client("pve-a.example.com", 8006);
+        let ticket_value = fresh_ticket("automation@pve", "sig");
+        let ticket = ProxmoxTicket::new_unchecked(ticket_value.clone());
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, None))
+            .await;
+
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        client
+            .save_session_encrypted(temp_file.path(), "correct horse battery staple")
+            .await
+            .unwrap();
+        let saved = std::fs::read_to_string(temp_file.path()).unwrap();
+        assert!(!saved.contains("PVE:automation@pve"));
+
+        let restored = ProxmoxClient::builder()
+            .host("pve-a.example.com")
+            .port(8006)
+            .with_encrypted_session(saved.as_bytes(), "correct horse battery staple")
+            .await
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert!(restored.is_authenticated().await);
+        assert_eq!(
+            restored.api_client.auth().await.unwrap().ticket().as_str(),
+            ticket_value
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_session_wrong_passphrase() {
+        let client = session_test_client("pve-a.example.com", 8006);
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, None))
+            .await;
+
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        client
+            .save_session_encrypted(temp_file.path(), "correct horse battery staple")
+            .await
+            .unwrap();
+
+        let result = ProxmoxClient::builder()
+            .host("pve-a.example.com")
+            .port(8006)
+            .with_encrypted_session(
+                std::fs::File::open(temp_file.path()).unwrap(),
+                "wrong passphrase",
+            )
+            .await;
+        assert!(matches!(result, Err(ProxmoxError::SessionDecryption(_))));
+    }
+
+    #[tokio::test]
+    async fn test_session_mismatched_host_rejected() {
+        let client = session_test_client("pve-a.example.com", 8006);
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, None))
+            .await;
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        client.save_session_to_file(temp_file.path()).await.unwrap();
+
+        let result = ProxmoxClient::builder()
+            .host("pve-b.example.com")
+            .port(8006)
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .with_session(std::fs::File::open(temp_file.path()).unwrap())
+            .await;
+        assert!(matches!(
+            result,
+            Err(ProxmoxError::Validation {
+                source: ValidationError::Field { ref field, .. },
+                ..
+            }) if field == "host"
+        ));
+
+        let mut other = session_test_client("pve-a.example.com", 8443);
+        assert!(matches!(
+            other.load_session_from_file(temp_file.path()).await,
+            Err(ProxmoxError::Validation { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_session_save_load() {
         use crate::core::domain::model::proxmox_auth::ProxmoxAuth;
@@ -988,4 +4073,50 @@ mod tests {
             new_client.auth_token().await.unwrap().as_str()
         );
     }
+
+    #[tokio::test]
+    async fn test_access_permissions_and_whoami() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, query_param},
+        };
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api2/json/access/permissions"))
+            .and(query_param("path", "/vms/100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "/vms/100": { "VM.Audit": 1, "VM.PowerMgmt": 0 }
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let address = mock_server.address();
+        let client = ProxmoxClient::builder()
+            .host(address.ip().to_string())
+            .port(address.port())
+            .credentials("automation", "Str0ng!Pass123", "pve")
+            .secure(false)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(client.whoami().await, None);
+
+        let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("automation@pve", "sig"));
+        let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+        client
+            .api_client
+            .set_auth(ProxmoxAuth::new(ticket, Some(csrf)))
+            .await;
+        assert_eq!(client.whoami().await.as_deref(), Some("automation@pve"));
+
+        let permissions = client.access_permissions(Some("/vms/100")).await.unwrap();
+        let vm = &permissions["/vms/100"];
+        assert_eq!(vm["VM.Audit"], 1);
+        assert_eq!(vm["VM.PowerMgmt"], 0);
+        assert!(!vm.contains_key("VM.Allocate"));
+    }
 }
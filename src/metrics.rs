@@ -0,0 +1,251 @@
+//! Request metrics for dashboards and alerting.
+//!
+//! Register an implementation of [`Metrics`] with
+//! [`ProxmoxClientBuilder::with_metrics`](crate::ProxmoxClientBuilder::with_metrics). It is
+//! called once per HTTP attempt, so a request retried after a ticket refresh is recorded
+//! twice. Login requests are not recorded.
+//!
+//! With the `metrics` feature, [`PrometheusMetrics`] records into a Prometheus registry.
+//!
+//! # Example
+//! ```no_run
+//! use leeca_proxmox::metrics::Metrics;
+//! use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+//! use reqwest::{Method, StatusCode};
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::time::Duration;
+//!
+//! #[derive(Default)]
+//! struct ErrorCounter(AtomicU64);
+//!
+//! impl Metrics for ErrorCounter {
+//!     fn record_request(
+//!         &self,
+//!         _method: &Method,
+//!         _path: &str,
+//!         status: Option<StatusCode>,
+//!         _duration: Duration,
+//!     ) {
+//!         if status.is_none_or(|s| !s.is_success()) {
+//!             self.0.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     }
+//! }
+//!
+//! # async fn run() -> ProxmoxResult<()> {
+//! let client = ProxmoxClient::builder()
+//!     .host("192.168.1.182")
+//!     .port(8006)
+//!     .credentials("leeca", "password", "pam")
+//!     .with_metrics(Arc::new(ErrorCounter::default()))
+//!     .build()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::{Method, StatusCode};
+use std::fmt;
+use std::time::Duration;
+
+/// A sink for per-request measurements.
+pub trait Metrics: Send + Sync {
+    /// Called after each HTTP attempt.
+    ///
+    /// `path` is the API path without the `/api2/json/` prefix or query string (e.g.,
+    /// "nodes/pve1/qemu/100/status/current"). `status` is `None` if no response arrived
+    /// (e.g., a connection error), and `duration` runs until the response headers arrived.
+    fn record_request(
+        &self,
+        _method: &Method,
+        _path: &str,
+        _status: Option<StatusCode>,
+        _duration: Duration,
+    ) {
+    }
+}
+
+impl fmt::Debug for dyn Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Metrics(..)")
+    }
+}
+
+/// Returns the part of a request URL path after `/api2/json/` (or `/api2/extjs/`).
+pub(crate) fn api_path(url_path: &str) -> &str {
+    url_path
+        .split_once("/api2/json/")
+        .or_else(|| url_path.split_once("/api2/extjs/"))
+        .map_or(url_path, |(_, path)| path)
+}
+
+/// Collections whose next path segment is an identifier, and the placeholder used for it.
+const ROUTE_PARAMETERS: [(&str, &str); 12] = [
+    ("nodes", "{node}"),
+    ("qemu", "{vmid}"),
+    ("lxc", "{vmid}"),
+    ("tasks", "{upid}"),
+    ("storage", "{storage}"),
+    ("content", "{volume}"),
+    ("services", "{service}"),
+    ("network", "{iface}"),
+    ("snapshot", "{snapname}"),
+    ("pools", "{poolid}"),
+    ("users", "{userid}"),
+    ("groups", "{groupid}"),
+];
+
+/// Replaces the identifiers in an API path with placeholders, giving its route template
+/// (e.g., "nodes/pve1/tasks/UPID:pve1:.../status" becomes "nodes/{node}/tasks/{upid}/status").
+///
+/// Use it to label measurements by endpoint: raw paths contain node names, guest IDs and
+/// task IDs, so a label per raw path grows with every guest and every task.
+#[must_use]
+pub fn route_template(path: &str) -> String {
+    let mut template = Vec::new();
+    let mut placeholder = None;
+    for segment in path.trim_matches('/').split('/') {
+        match placeholder.take() {
+            Some(parameter) => template.push(parameter),
+            None => {
+                placeholder = ROUTE_PARAMETERS
+                    .iter()
+                    .find(|(collection, _)| *collection == segment)
+                    .map(|(_, parameter)| *parameter);
+                template.push(segment);
+            }
+        }
+    }
+    template.join("/")
+}
+
+#[cfg(feature = "metrics")]
+pub use prometheus;
+
+/// [`Metrics`] backed by Prometheus collectors.
+///
+/// Records `proxmox_requests_total` (labels `method`, `path`, `status`) and
+/// `proxmox_request_duration_seconds` (labels `method`, `path`). Failed attempts without a
+/// response use the status `"error"`. The `path` label is the [`route_template`] of the
+/// request, so the number of series depends on the endpoints used, not on the number of
+/// guests, nodes or tasks.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone)]
+pub struct PrometheusMetrics {
+    requests: prometheus::IntCounterVec,
+    duration: prometheus::HistogramVec,
+}
+
+#[cfg(feature = "metrics")]
+impl PrometheusMetrics {
+    /// Creates the collectors and registers them with `registry`.
+    ///
+    /// # Errors
+    /// Returns a [`prometheus::Error`] if collectors with the same names are already
+    /// registered.
+    pub fn new(registry: &prometheus::Registry) -> prometheus::Result<Self> {
+        let requests = prometheus::IntCounterVec::new(
+            prometheus::Opts::new("proxmox_requests_total", "Proxmox API requests sent"),
+            &["method", "path", "status"],
+        )?;
+        let duration = prometheus::HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "proxmox_request_duration_seconds",
+                "Time until the Proxmox API response headers arrived",
+            ),
+            &["method", "path"],
+        )?;
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(duration.clone()))?;
+        Ok(Self { requests, duration })
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Metrics for PrometheusMetrics {
+    fn record_request(
+        &self,
+        method: &Method,
+        path: &str,
+        status: Option<StatusCode>,
+        duration: Duration,
+    ) {
+        let status = status.as_ref().map_or("error", StatusCode::as_str);
+        let route = route_template(path);
+        self.requests
+            .with_label_values(&[method.as_str(), &route, status])
+            .inc();
+        self.duration
+            .with_label_values(&[method.as_str(), &route])
+            .observe(duration.as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_template_replaces_identifiers() {
+        assert_eq!(
+            route_template("nodes/pve1/qemu/100/status/current"),
+            "nodes/{node}/qemu/{vmid}/status/current"
+        );
+        assert_eq!(
+            route_template(
+                "nodes/pve1/tasks/UPID:pve1:000A1B2C:0123ABCD:65A1B2C3:qmstart:100:root@pam:/status"
+            ),
+            "nodes/{node}/tasks/{upid}/status"
+        );
+        assert_eq!(
+            route_template("nodes/pve1/storage/local/content/local:iso%2Fdebian.iso"),
+            "nodes/{node}/storage/{storage}/content/{volume}"
+        );
+        assert_eq!(route_template("cluster/resources"), "cluster/resources");
+        assert_eq!(route_template("nodes"), "nodes");
+        assert_eq!(route_template("version"), "version");
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_prometheus_metrics_records_counts_and_latency() {
+        let registry = prometheus::Registry::new();
+        let metrics = PrometheusMetrics::new(&registry).unwrap();
+        metrics.record_request(
+            &Method::GET,
+            "version",
+            Some(StatusCode::OK),
+            Duration::from_millis(20),
+        );
+        metrics.record_request(&Method::GET, "version", None, Duration::from_millis(5));
+
+        let ok = metrics
+            .requests
+            .with_label_values(&["GET", "version", "200"])
+            .get();
+        let failed = metrics
+            .requests
+            .with_label_values(&["GET", "version", "error"])
+            .get();
+        assert_eq!((ok, failed), (1, 1));
+        assert_eq!(
+            metrics
+                .duration
+                .with_label_values(&["GET", "version"])
+                .get_sample_count(),
+            2
+        );
+        assert!(PrometheusMetrics::new(&registry).is_err());
+
+        for vmid in [100, 101] {
+            let path = format!("nodes/pve1/qemu/{}/status/current", vmid);
+            metrics.record_request(&Method::GET, &path, Some(StatusCode::OK), Duration::ZERO);
+        }
+        let by_route = metrics
+            .requests
+            .with_label_values(&["GET", "nodes/{node}/qemu/{vmid}/status/current", "200"])
+            .get();
+        assert_eq!(by_route, 2);
+    }
+}
@@ -0,0 +1,118 @@
+//! Hooks that observe and adjust every API request.
+//!
+//! Register implementations of [`Middleware`] with
+//! [`ProxmoxClientBuilder::with_middleware`](crate::ProxmoxClientBuilder::with_middleware).
+//! They run in registration order before a request is sent and after its response arrives,
+//! including the retry after a ticket refresh. The login request itself is not passed through
+//! them.
+//!
+//! # Example
+//! ```no_run
+//! use leeca_proxmox::middleware::{Middleware, RequestParts, ResponseParts, async_trait};
+//! use leeca_proxmox::{ProxmoxClient, ProxmoxResult};
+//! use std::sync::Arc;
+//!
+//! struct CorrelationId;
+//!
+//! #[async_trait]
+//! impl Middleware for CorrelationId {
+//!     async fn on_request(&self, request: &mut RequestParts) {
+//!         request
+//!             .headers
+//!             .insert("x-correlation-id", "deploy-42".parse().unwrap());
+//!     }
+//!
+//!     async fn on_response(&self, response: &ResponseParts) {
+//!         println!("{} {} -> {} in {:?}", response.method, response.url, response.status, response.elapsed);
+//!     }
+//! }
+//!
+//! # async fn run() -> ProxmoxResult<()> {
+//! let client = ProxmoxClient::builder()
+//!     .host("192.168.1.182")
+//!     .port(8006)
+//!     .credentials("leeca", "password", "pam")
+//!     .with_middleware(Arc::new(CorrelationId))
+//!     .build()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub use async_trait::async_trait;
+use reqwest::{Method, StatusCode, Url, header::HeaderMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A request about to be sent. Changes made by [`Middleware::on_request`] are applied to it.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RequestParts {
+    /// The HTTP method.
+    pub method: Method,
+    /// The full request URL.
+    pub url: Url,
+    /// The request headers, including the authentication cookie and CSRF token.
+    pub headers: HeaderMap,
+}
+
+/// A response as received, before its body is read.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ResponseParts {
+    /// The HTTP method of the request.
+    pub method: Method,
+    /// The full request URL.
+    pub url: Url,
+    /// The response status.
+    pub status: StatusCode,
+    /// The response headers.
+    pub headers: HeaderMap,
+    /// Time from sending the request to receiving the response headers.
+    pub elapsed: Duration,
+}
+
+/// A hook around every API request.
+///
+/// Both methods default to doing nothing, so implement only the side you need.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Called before the request is sent; may modify it (e.g., to add headers).
+    async fn on_request(&self, _request: &mut RequestParts) {}
+
+    /// Called after the response headers arrive.
+    async fn on_response(&self, _response: &ResponseParts) {}
+}
+
+/// The registered middleware, in order.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+    pub(crate) fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.0.push(middleware);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) async fn on_request(&self, request: &mut RequestParts) {
+        for middleware in &self.0 {
+            middleware.on_request(request).await;
+        }
+    }
+
+    pub(crate) async fn on_response(&self, response: &ResponseParts) {
+        for middleware in &self.0 {
+            middleware.on_response(response).await;
+        }
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MiddlewareChain({} registered)", self.0.len())
+    }
+}
@@ -0,0 +1,101 @@
+//! Named collection of [`ProxmoxClient`]s for managing several clusters at once.
+//!
+//! # Example
+//! ```no_run
+//! use leeca_proxmox::{ProxmoxClient, ProxmoxResult, pool::ProxmoxClientPool};
+//!
+//! #[tokio::main]
+//! async fn main() -> ProxmoxResult<()> {
+//!     let mut pool = ProxmoxClientPool::new();
+//!     for (name, host) in [("lab", "10.0.0.10"), ("prod", "10.1.0.10")] {
+//!         let mut client = ProxmoxClient::builder()
+//!             .host(host)
+//!             .port(8006)
+//!             .credentials("leeca", "password", "pam")
+//!             .build()
+//!             .await?;
+//!         client.login().await?;
+//!         pool.add(name, client);
+//!     }
+//!
+//!     for (name, version) in pool.broadcast(|client| client.version()).await {
+//!         println!("{}: {:?}", name, version.map(|v| v.version));
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{ProxmoxClient, ProxmoxResult};
+use futures::future::join_all;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+/// A set of clients keyed by a caller-chosen cluster name.
+///
+/// Each client keeps its own authentication, so clusters are logged in to independently.
+/// Iteration and [`broadcast`](Self::broadcast) results are ordered by name.
+#[derive(Debug, Default)]
+pub struct ProxmoxClientPool {
+    clients: BTreeMap<String, ProxmoxClient>,
+}
+
+impl ProxmoxClientPool {
+    /// Creates an empty pool.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `client` under `name`, returning the client previously registered under it.
+    pub fn add(&mut self, name: impl Into<String>, client: ProxmoxClient) -> Option<ProxmoxClient> {
+        self.clients.insert(name.into(), client)
+    }
+
+    /// Returns the client registered under `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&ProxmoxClient> {
+        self.clients.get(name)
+    }
+
+    /// Returns the client registered under `name` mutably (e.g., to call
+    /// [`ProxmoxClient::login`]).
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut ProxmoxClient> {
+        self.clients.get_mut(name)
+    }
+
+    /// Removes and returns the client registered under `name`.
+    pub fn remove(&mut self, name: &str) -> Option<ProxmoxClient> {
+        self.clients.remove(name)
+    }
+
+    /// Returns the registered names in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.clients.keys().map(String::as_str)
+    }
+
+    /// Returns the number of registered clients.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns `true` if no clients are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Runs `f` against every client concurrently and collects the results by name.
+    ///
+    /// A failure on one cluster does not affect the others; each result is reported
+    /// separately.
+    pub async fn broadcast<'a, F, Fut, T>(&'a self, f: F) -> Vec<(String, ProxmoxResult<T>)>
+    where
+        F: Fn(&'a ProxmoxClient) -> Fut,
+        Fut: Future<Output = ProxmoxResult<T>>,
+    {
+        let names = self.clients.keys().cloned();
+        let results = join_all(self.clients.values().map(f)).await;
+        names.zip(results).collect()
+    }
+}
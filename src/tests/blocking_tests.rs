@@ -0,0 +1,113 @@
+use crate::core::domain::value_object::fresh_ticket;
+use crate::{
+    NodeOnlineStatus, ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort,
+    ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, ValidationConfig, blocking::BlockingProxmoxClient,
+    core::infrastructure::api_client::ApiClient,
+};
+use tokio::runtime::Runtime;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_connection(server_url: &str) -> ProxmoxConnection {
+    let host = ProxmoxHost::new_unchecked(server_url.trim_start_matches("http://").to_string());
+    let port = ProxmoxPort::new_unchecked(8006);
+    let username = ProxmoxUsername::new_unchecked("testuser".to_string());
+    let password = ProxmoxPassword::new_unchecked("testpass".to_string());
+    let realm = ProxmoxRealm::new_unchecked("pam".to_string());
+    let url = ProxmoxUrl::new_unchecked(server_url.to_string() + "/");
+    ProxmoxConnection::new(host, port, username, password, realm, false, true, url)
+}
+
+// The mock server needs an async runtime of its own; the blocking client must be driven
+// from outside of it.
+fn create_blocking_client(rt: &Runtime, mock_server: &MockServer) -> BlockingProxmoxClient {
+    let connection = create_test_connection(&mock_server.uri());
+    let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+
+    use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+    let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+    let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
+    rt.block_on(client.set_auth(auth));
+
+    BlockingProxmoxClient::from_client(ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_blocking_nodes_list() {
+    let rt = Runtime::new().unwrap();
+    let mock_server = rt.block_on(MockServer::start());
+    rt.block_on(
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "node": "pve1", "status": "online", "maxcpu": 8 },
+                    { "node": "pve2", "status": "offline" }
+                ]
+            })))
+            .mount(&mock_server),
+    );
+
+    let client = create_blocking_client(&rt, &mock_server);
+    let nodes = client.nodes().unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0].node, "pve1");
+    assert_eq!(nodes[0].maxcpu, Some(8));
+    assert_eq!(nodes[1].status, NodeOnlineStatus::Offline);
+}
+
+#[test]
+fn test_blocking_vms_list() {
+    let rt = Runtime::new().unwrap();
+    let mock_server = rt.block_on(MockServer::start());
+    rt.block_on(
+        Mock::given(method("GET"))
+            .and(path("/api2/json/nodes/pve1/qemu"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {
+                        "vmid": 100,
+                        "name": "ubuntu-vm",
+                        "status": "running",
+                        "node": "pve1",
+                        "id": "qemu/100"
+                    }
+                ]
+            })))
+            .mount(&mock_server),
+    );
+
+    let client = create_blocking_client(&rt, &mock_server);
+    let vms = client.vms("pve1").unwrap();
+    assert_eq!(vms.len(), 1);
+    assert_eq!(vms[0].vmid, 100);
+    assert_eq!(vms[0].name, "ubuntu-vm");
+}
+
+#[test]
+fn test_blocking_start_vm() {
+    let rt = Runtime::new().unwrap();
+    let mock_server = rt.block_on(MockServer::start());
+    rt.block_on(
+        Mock::given(method("POST"))
+            .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
+            })))
+            .mount(&mock_server),
+    );
+
+    let client = create_blocking_client(&rt, &mock_server);
+    let task_id = client.start_vm("pve1", 100).unwrap();
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
+    );
+}
@@ -0,0 +1,74 @@
+use crate::core::domain::value_object::fresh_ticket;
+use crate::{
+    ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
+    ProxmoxUrl, ProxmoxUsername, ValidationConfig, core::infrastructure::api_client::ApiClient,
+    pool::ProxmoxClientPool,
+};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+fn create_test_connection(server_url: &str) -> ProxmoxConnection {
+    let host = ProxmoxHost::new_unchecked(server_url.trim_start_matches("http://").to_string());
+    let port = ProxmoxPort::new_unchecked(8006);
+    let username = ProxmoxUsername::new_unchecked("testuser".to_string());
+    let password = ProxmoxPassword::new_unchecked("testpass".to_string());
+    let realm = ProxmoxRealm::new_unchecked("pam".to_string());
+    let url = ProxmoxUrl::new_unchecked(server_url.to_string() + "/");
+    ProxmoxConnection::new(host, port, username, password, realm, false, true, url)
+}
+
+async fn create_authenticated_client(mock_server: &MockServer) -> ProxmoxClient {
+    let connection = create_test_connection(&mock_server.uri());
+    let client = ApiClient::new(connection, ValidationConfig::default()).unwrap();
+
+    use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+    let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+    let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
+    client.set_auth(auth).await;
+
+    ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    }
+}
+
+async fn mock_version(mock_server: &MockServer, version: &str) {
+    Mock::given(method("GET"))
+        .and(path("/api2/json/version"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "version": version, "release": "8.2", "repoid": "abcdef12" }
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_pool_broadcast_collects_every_client() {
+    let lab_server = MockServer::start().await;
+    let prod_server = MockServer::start().await;
+    mock_version(&lab_server, "8.2.4").await;
+    mock_version(&prod_server, "7.4.17").await;
+
+    let mut pool = ProxmoxClientPool::new();
+    pool.add("prod", create_authenticated_client(&prod_server).await);
+    pool.add("lab", create_authenticated_client(&lab_server).await);
+    assert_eq!(pool.len(), 2);
+    assert!(pool.get("lab").is_some());
+    assert!(pool.get("staging").is_none());
+
+    let results = pool.broadcast(|client| client.version()).await;
+    let versions: Vec<(String, String)> = results
+        .into_iter()
+        .map(|(name, result)| (name, result.unwrap().version))
+        .collect();
+    assert_eq!(
+        versions,
+        vec![
+            ("lab".to_string(), "8.2.4".to_string()),
+            ("prod".to_string(), "7.4.17".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,343 @@
+use crate::core::domain::value_object::fresh_ticket;
+use crate::{
+    BackupCompression, BackupFile, BackupMode, BackupParams, Checksum, ChecksumAlgorithm,
+    GuestType, ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort,
+    ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, RestoreParams, UploadContentType, ValidationConfig,
+    VmId, core::infrastructure::api_client::ApiClient,
+};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path, query_param},
+};
+
+fn create_test_connection(server_url: &str) -> ProxmoxConnection {
+    let host = ProxmoxHost::new_unchecked(server_url.trim_start_matches("http://").to_string());
+    let port = ProxmoxPort::new_unchecked(8006);
+    let username = ProxmoxUsername::new_unchecked("testuser".to_string());
+    let password = ProxmoxPassword::new_unchecked("testpass".to_string());
+    let realm = ProxmoxRealm::new_unchecked("pam".to_string());
+    let url = ProxmoxUrl::new_unchecked(server_url.to_string() + "/");
+    ProxmoxConnection::new(host, port, username, password, realm, false, true, url)
+}
+
+async fn create_authenticated_client(mock_server: &MockServer) -> ApiClient {
+    let connection = create_test_connection(&mock_server.uri());
+    let config = ValidationConfig::default();
+    let client = ApiClient::new(connection, config).unwrap();
+
+    use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+    let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+    let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
+    client.set_auth(auth).await;
+    client
+}
+
+#[tokio::test]
+async fn test_list_backups_for_vmid() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/storage/local/content"))
+        .and(query_param("content", "backup"))
+        .and(query_param("vmid", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "volid": "local:backup/vzdump-qemu-100-2024_01_01-00_00_00.vma.zst",
+                    "content": "backup",
+                    "vmid": 100,
+                    "ctime": 1704067200,
+                    "size": 1073741824_i64,
+                    "format": "vma.zst",
+                    "notes": "nightly",
+                    "protected": 1
+                },
+                {
+                    "volid": "pbs:backup/vm/100/2024-01-02T00:00:00Z",
+                    "content": "backup",
+                    "vmid": 100,
+                    "ctime": 1704153600,
+                    "size": 2147483648_i64,
+                    "format": "pbs-vm",
+                    "verification": {
+                        "state": "ok",
+                        "upid": "UPID:pbs:00000001:00000001:00000001:verify"
+                    }
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let backups = proxmox_client
+        .list_backups("pve1", "local", Some(VmId::new(100).unwrap()))
+        .await
+        .unwrap();
+    assert_eq!(backups.len(), 2);
+
+    let first = &backups[0];
+    assert_eq!(
+        first.volid,
+        "local:backup/vzdump-qemu-100-2024_01_01-00_00_00.vma.zst"
+    );
+    assert_eq!(first.vmid, Some(100));
+    assert_eq!(first.ctime, Some(1704067200));
+    assert_eq!(first.size, Some(1073741824));
+    assert_eq!(first.format.as_deref(), Some("vma.zst"));
+    assert_eq!(first.notes.as_deref(), Some("nightly"));
+    assert_eq!(first.protected, Some(1));
+    assert!(first.verification.is_none());
+
+    let second = &backups[1];
+    assert_eq!(second.format.as_deref(), Some("pbs-vm"));
+    let verification = second.verification.as_ref().unwrap();
+    assert_eq!(verification.state, "ok");
+    assert!(verification.upid.is_some());
+}
+
+#[tokio::test]
+async fn test_list_backups_drops_other_guests() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/storage/nfs/content"))
+        .and(query_param("content", "backup"))
+        .and(query_param("vmid", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {"volid": "nfs:backup/vzdump-qemu-100-2024_01_01-00_00_00.vma.zst", "vmid": 100},
+                {"volid": "nfs:backup/vzdump-qemu-101-2024_01_01-00_00_00.vma.zst", "vmid": 101}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let backups: Vec<BackupFile> = proxmox_client
+        .list_backups("pve1", "nfs", Some(VmId::new(100).unwrap()))
+        .await
+        .unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].vmid, Some(100));
+}
+
+#[tokio::test]
+async fn test_backup_vm_posts_vzdump() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/vzdump"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "vmid": 100,
+            "mode": "snapshot",
+            "compress": "zstd"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:vzdump:100:testuser@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = BackupParams::new()
+        .storage("local")
+        .mode(BackupMode::Snapshot)
+        .compress(BackupCompression::Zstd);
+    let task_id = proxmox_client
+        .backup_vm("pve1", 100, &params)
+        .await
+        .unwrap();
+    assert_eq!(
+        task_id,
+        "UPID:pve1:00000001:00000001:00000001:vzdump:100:testuser@pam:"
+    );
+}
+
+#[tokio::test]
+async fn test_restore_backup_posts_archive() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(wiremock::matchers::body_string(
+            "vmid=150&archive=local%3Abackup%2Fvzdump-qemu-100-2024_01_01-00_00_00.vma.zst\
+             &storage=local-lvm&force=1",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmrestore"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = RestoreParams::new(
+        GuestType::Qemu,
+        150,
+        "local:backup/vzdump-qemu-100-2024_01_01-00_00_00.vma.zst",
+    )
+    .storage("local-lvm")
+    .force(true);
+    let task_id = proxmox_client
+        .restore_backup("pve1", &params)
+        .await
+        .unwrap();
+    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:qmrestore");
+}
+
+#[tokio::test]
+async fn test_restore_vm_posts_archive() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(wiremock::matchers::body_string(
+            "vmid=151&archive=pbs%3Abackup%2Fvm%2F100%2F2024-01-02T00%3A00%3A00Z&storage=local-lvm",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000002:00000002:00000002:qmrestore"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = RestoreParams::new(
+        GuestType::Qemu,
+        151,
+        "pbs:backup/vm/100/2024-01-02T00:00:00Z",
+    )
+    .storage("local-lvm");
+    let task_id = proxmox_client.restore_vm("pve1", &params).await.unwrap();
+    assert_eq!(task_id, "UPID:pve1:00000002:00000002:00000002:qmrestore");
+
+    let invalid = RestoreParams::new(GuestType::Qemu, 151, "vzdump-qemu-100.vma.zst");
+    assert!(proxmox_client.restore_vm("pve1", &invalid).await.is_err());
+}
+
+#[tokio::test]
+async fn test_verify_storage_content_checksum() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api2/json/nodes/pve1/storage/local/content/local:iso%2Fdebian-12.iso",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "path": "/var/lib/vz/template/iso/debian-12.iso",
+                "size": 657457152,
+                "used": 657457152,
+                "format": "iso",
+                "checksum": "9F181AE12B25840A507A6D2E9C6A05D7A32E4B3B6B4E3C5E2C4B9A3A1D0E8F7C",
+                "checksum-algorithm": "sha256"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let matching = Checksum::new(
+        ChecksumAlgorithm::Sha256,
+        "9f181ae12b25840a507a6d2e9c6a05d7a32e4b3b6b4e3c5e2c4b9a3a1d0e8f7c",
+    )
+    .unwrap();
+    assert!(
+        proxmox_client
+            .verify_storage_content("pve1", "local", "local:iso/debian-12.iso", &matching)
+            .await
+            .unwrap()
+    );
+
+    let mismatching = Checksum::new(
+        ChecksumAlgorithm::Sha256,
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    )
+    .unwrap();
+    assert!(
+        !proxmox_client
+            .verify_storage_content("pve1", "local", "local:iso/debian-12.iso", &mismatching)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_upload_iso_to_storage() {
+    use wiremock::matchers::{body_string_contains, header_regex};
+
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/storage/local/upload"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=",
+        ))
+        .and(body_string_contains("name=\"content\"\r\n\r\niso\r\n"))
+        .and(body_string_contains(
+            "name=\"filename\"; filename=\"tiny.iso\"",
+        ))
+        .and(body_string_contains("CD001 tiny image"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:imgcopy::root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let data: &'static [u8] = b"CD001 tiny image";
+    let upid = proxmox_client
+        .upload_to_storage(
+            "pve1",
+            "local",
+            UploadContentType::Iso,
+            "tiny.iso",
+            data,
+            data.len() as u64,
+        )
+        .await
+        .unwrap();
+    assert!(upid.contains("imgcopy"));
+}
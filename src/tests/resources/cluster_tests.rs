@@ -1,12 +1,14 @@
+use crate::core::domain::value_object::fresh_ticket;
 use crate::{
-    ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
-    ProxmoxUrl, ProxmoxUsername, ValidationConfig,
-    core::domain::model::cluster_resource::ClusterResource,
+    HaState, MAX_VMID, ProxmoxClient, ProxmoxConnection, ProxmoxError, ProxmoxHost,
+    ProxmoxPassword, ProxmoxPort, ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, ResourceFilter,
+    ResourceKind, ValidationConfig, core::domain::model::cluster_resource::ClusterResource,
+    core::domain::model::cluster_status::ClusterStatusEntry,
     core::infrastructure::api_client::ApiClient,
 };
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
-    matchers::{method, path},
+    matchers::{method, path, query_param},
 };
 
 fn create_test_connection(server_url: &str) -> ProxmoxConnection {
@@ -26,7 +28,7 @@ async fn create_authenticated_client(mock_server: &MockServer) -> ApiClient {
 
     // Set a dummy auth (we don't need real login for these tests)
     use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
-    let ticket = ProxmoxTicket::new_unchecked("PVE:testuser@pam:4EEC61E2::sig".to_string());
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
     let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
     let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
     client.set_auth(auth).await;
@@ -204,7 +206,7 @@ async fn test_cluster_resources_unauthorized_triggers_refresh() {
         .and(path("/api2/json/access/ticket"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
             "data": {
-                "ticket": "PVE:testuser@pam:4EEC61E2::refreshed",
+                "ticket": fresh_ticket("testuser@pam", "refreshed"),
                 "CSRFPreventionToken": "4EEC61E2:newtoken"
             }
         })))
@@ -243,3 +245,408 @@ async fn test_cluster_resources_unauthorized_triggers_refresh() {
         _ => panic!("Expected Qemu resource"),
     }
 }
+
+#[tokio::test]
+async fn test_next_vmid_confirmed_skips_collision() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .and(wiremock::matchers::query_param_is_missing("vmid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "105"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // Another provisioner grabbed 105 between the two calls.
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .and(wiremock::matchers::query_param("vmid", "105"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "errors": { "vmid": "VM 105 already exists" },
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .and(wiremock::matchers::query_param("vmid", "106"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "106"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    assert_eq!(proxmox_client.next_vmid_confirmed().await.unwrap(), 106);
+}
+
+#[tokio::test]
+async fn test_next_vmid_from_max_does_not_overflow() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/resources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "errors": { "vmid": "VM 999999999 already exists" },
+            "data": null
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let result = proxmox_client.next_vmid_from(u32::MAX).await;
+    assert!(matches!(result, Err(ProxmoxError::Unexpected(_))));
+    let result = proxmox_client.next_vmid_from(MAX_VMID).await;
+    assert!(matches!(result, Err(ProxmoxError::Unexpected(_))));
+}
+
+#[tokio::test]
+async fn test_next_vmid_from_clamps_to_min_vmid() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/resources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": []
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .and(query_param("vmid", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": 100
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    assert_eq!(proxmox_client.next_vmid_from(0).await.unwrap(), 100);
+}
+
+#[tokio::test]
+async fn test_standalone_node_cluster_endpoints() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/resources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "type": "node",
+                    "id": "node/pve1",
+                    "node": "pve1",
+                    "status": "online",
+                    "maxcpu": 4
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "type": "node",
+                    "id": "node/pve1",
+                    "name": "pve1",
+                    "ip": "192.168.1.10",
+                    "local": 1,
+                    "online": 1,
+                    "nodeid": 0,
+                    "level": ""
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let resources = proxmox_client.cluster_resources().await.unwrap();
+    assert_eq!(resources.len(), 1);
+    assert!(matches!(resources[0], ClusterResource::Node(_)));
+
+    let status = proxmox_client.cluster_status().await.unwrap();
+    assert_eq!(status.len(), 1);
+    let ClusterStatusEntry::Node(node) = &status[0] else {
+        panic!("expected a node entry");
+    };
+    assert_eq!(node.name, "pve1");
+    assert_eq!(node.local, Some(1));
+
+    assert!(!proxmox_client.is_cluster().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_clustered_status_reports_cluster() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                { "type": "cluster", "id": "cluster", "name": "lab", "nodes": 2, "quorate": 1, "version": 4 },
+                { "type": "node", "id": "node/pve1", "name": "pve1", "nodeid": 1, "online": 1, "local": 1 },
+                { "type": "node", "id": "node/pve2", "name": "pve2", "nodeid": 2, "online": 0, "local": 0 }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let status = proxmox_client.cluster_status().await.unwrap();
+    assert_eq!(status.len(), 3);
+    assert!(matches!(&status[0], ClusterStatusEntry::Cluster(info) if info.quorate == Some(1)));
+    assert!(proxmox_client.is_cluster().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_next_vmid_parses_data() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "104"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    assert_eq!(proxmox_client.next_vmid().await.unwrap(), 104);
+}
+
+#[tokio::test]
+async fn test_next_vmid_from_skips_used_ids() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/resources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                { "id": "qemu/200", "type": "qemu", "node": "pve1", "status": "running", "vmid": 200 },
+                { "id": "lxc/201", "type": "lxc", "node": "pve1", "status": "running", "vmid": 201 },
+                { "id": "qemu/203", "type": "qemu", "node": "pve1", "status": "running", "vmid": 203 }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .and(wiremock::matchers::query_param("vmid", "202"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": 202
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    assert_eq!(proxmox_client.next_vmid_from(200).await.unwrap(), 202);
+}
+
+#[tokio::test]
+async fn test_cluster_resources_filtered_by_type() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/resources"))
+        .and(query_param("type", "vm"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {"type": "qemu", "id": "qemu/100", "node": "pve1", "status": "running", "vmid": 100},
+                {"type": "lxc", "id": "lxc/200", "node": "pve1", "status": "running", "vmid": 200}
+            ]
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let guests = proxmox_client
+        .cluster_resources_filtered(&ResourceFilter::new().kind(ResourceKind::Vm))
+        .await
+        .unwrap();
+    assert_eq!(guests.len(), 2);
+
+    let vms = proxmox_client
+        .cluster_resources_filtered(&ResourceFilter::new().kind(ResourceKind::Qemu))
+        .await
+        .unwrap();
+    assert_eq!(vms.len(), 1);
+    assert!(matches!(&vms[0], ClusterResource::Qemu(vm) if vm.vmid == 100));
+}
+
+#[tokio::test]
+async fn test_cluster_resources_filtered_by_node() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/resources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {"type": "qemu", "id": "qemu/100", "node": "pve1", "status": "running", "vmid": 100},
+                {"type": "qemu", "id": "qemu/101", "node": "pve2", "status": "stopped", "vmid": 101},
+                {"type": "node", "id": "node/pve2", "node": "pve2", "status": "online"},
+                {"type": "pool", "id": "/pool/production", "pool": "production"}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let resources = proxmox_client
+        .cluster_resources_filtered(&ResourceFilter::new().node("pve2"))
+        .await
+        .unwrap();
+    assert_eq!(resources.len(), 2);
+    assert!(resources.iter().all(|r| r.node() == Some("pve2")));
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), None);
+}
+
+#[tokio::test]
+async fn test_ha_resources_list_and_set_state() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/ha/resources"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "sid": "vm:100",
+                    "type": "vm",
+                    "state": "started",
+                    "group": "prod",
+                    "max_restart": 1,
+                    "max_relocate": 1,
+                    "comment": "database",
+                    "digest": "0123abcd"
+                },
+                {"sid": "ct:200", "type": "ct", "state": "disabled"}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/cluster/ha/resources/vm:100"))
+        .and(wiremock::matchers::body_string("state=stopped"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let resources = proxmox_client.ha_resources().await.unwrap();
+    assert_eq!(resources.len(), 2);
+    assert_eq!(resources[0].sid, "vm:100");
+    assert_eq!(resources[0].state, Some(HaState::Started));
+    assert_eq!(resources[0].group.as_deref(), Some("prod"));
+    assert_eq!(resources[0].max_restart, Some(1));
+    assert_eq!(resources[0].max_relocate, Some(1));
+    assert_eq!(resources[0].comment.as_deref(), Some("database"));
+    assert_eq!(resources[1].state, Some(HaState::Disabled));
+    assert!(resources[1].group.is_none());
+
+    proxmox_client
+        .set_ha_state("vm:100", HaState::Stopped)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_set_ha_state_encodes_sid() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path(
+            "/api2/json/cluster/ha/resources/vm:100%2F..%2F..%2Fnodes",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    proxmox_client
+        .set_ha_state("vm:100/../../nodes", HaState::Started)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,179 @@
+use crate::core::domain::value_object::fresh_ticket;
+use crate::{
+    CreateLxcParams, ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort,
+    ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, TaskOutcome, UpdateLxcConfig, ValidationConfig,
+    core::infrastructure::api_client::ApiClient,
+};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{header, method, path},
+};
+
+fn create_test_connection(server_url: &str) -> ProxmoxConnection {
+    let host = ProxmoxHost::new_unchecked(server_url.trim_start_matches("http://").to_string());
+    let port = ProxmoxPort::new_unchecked(8006);
+    let username = ProxmoxUsername::new_unchecked("testuser".to_string());
+    let password = ProxmoxPassword::new_unchecked("testpass".to_string());
+    let realm = ProxmoxRealm::new_unchecked("pam".to_string());
+    let url = ProxmoxUrl::new_unchecked(server_url.to_string() + "/");
+    ProxmoxConnection::new(host, port, username, password, realm, false, true, url)
+}
+
+async fn create_authenticated_client(mock_server: &MockServer) -> ApiClient {
+    let connection = create_test_connection(&mock_server.uri());
+    let config = ValidationConfig::default();
+    let client = ApiClient::new(connection, config).unwrap();
+
+    use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+    let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+    let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
+    client.set_auth(auth).await;
+    client
+}
+
+/// Parses a form-encoded request body into key/value pairs.
+fn form_pairs(req: &wiremock::Request) -> Vec<(String, String)> {
+    url::form_urlencoded::parse(&req.body)
+        .into_owned()
+        .collect()
+}
+
+#[tokio::test]
+async fn test_create_container() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/lxc"))
+        .and(header("content-type", "application/x-www-form-urlencoded"))
+        .and(|req: &wiremock::Request| {
+            let pairs = form_pairs(req);
+            let has = |key: &str, value: &str| pairs.iter().any(|(k, v)| k == key && v == value);
+            has("vmid", "200")
+                && has(
+                    "ostemplate",
+                    "local:vztmpl/debian-12-standard_12.7-1_amd64.tar.zst",
+                )
+                && has("hostname", "web-ct")
+                && has("rootfs", "local-lvm:8")
+                && has("ssh-public-keys", "ssh-ed25519 AAAA admin@example")
+                && has("unprivileged", "1")
+        })
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:vzcreate:200:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params =
+        CreateLxcParams::builder(200, "local:vztmpl/debian-12-standard_12.7-1_amd64.tar.zst")
+            .hostname("web-ct")
+            .memory(1024)
+            .rootfs("local-lvm", 8)
+            .net0("name=eth0,bridge=vmbr0,ip=dhcp")
+            .ssh_public_keys("ssh-ed25519 AAAA admin@example")
+            .unprivileged(true)
+            .build()
+            .unwrap();
+    let upid = proxmox_client
+        .create_container("pve1", &params)
+        .await
+        .unwrap();
+    assert!(upid.contains("vzcreate:200"));
+}
+
+#[tokio::test]
+async fn test_container_config() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/lxc/200/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "hostname": "web-ct",
+                "memory": 1024,
+                "swap": 512,
+                "cores": 2,
+                "rootfs": "local-lvm:vm-200-disk-0,size=8G",
+                "net0": "name=eth0,bridge=vmbr0,hwaddr=BC:24:11:00:00:01,ip=dhcp,type=veth",
+                "net1": "name=eth1,bridge=vmbr1,ip=10.0.0.5/24,type=veth",
+                "mp0": "local-lvm:vm-200-disk-1,mp=/srv,size=16G",
+                "ostype": "debian",
+                "arch": "amd64",
+                "features": "nesting=1",
+                "tags": "web;prod",
+                "unprivileged": 1,
+                "digest": "0123456789abcdef"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let config = proxmox_client.container_config("pve1", 200).await.unwrap();
+    assert_eq!(config.hostname.as_deref(), Some("web-ct"));
+    assert_eq!(config.memory, Some(1024));
+    assert_eq!(config.swap, Some(512));
+    assert_eq!(config.cores, Some(2));
+    assert_eq!(
+        config.rootfs.as_deref(),
+        Some("local-lvm:vm-200-disk-0,size=8G")
+    );
+    assert_eq!(config.ostype.as_deref(), Some("debian"));
+    assert_eq!(config.arch.as_deref(), Some("amd64"));
+    assert_eq!(config.features.as_deref(), Some("nesting=1"));
+    assert_eq!(config.tags.as_deref(), Some("web;prod"));
+    assert_eq!(config.unprivileged, Some(1));
+    let networks = config.networks();
+    assert_eq!(networks.len(), 2);
+    assert!(networks[&1].starts_with("name=eth1"));
+    assert_eq!(
+        config.mount_points()[&0],
+        "local-lvm:vm-200-disk-1,mp=/srv,size=16G"
+    );
+}
+
+#[tokio::test]
+async fn test_update_container_config_memory() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/lxc/200/config"))
+        .and(|req: &wiremock::Request| {
+            form_pairs(req) == [("memory".to_string(), "2048".to_string())]
+        })
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let update = UpdateLxcConfig {
+        memory: Some(2048),
+        ..UpdateLxcConfig::new()
+    };
+    let outcome = proxmox_client
+        .update_container_config("pve1", 200, &update)
+        .await
+        .unwrap();
+    assert_eq!(outcome, TaskOutcome::Sync);
+}
@@ -1,3 +1,6 @@
+mod backup_tests;
 mod cluster_tests;
+mod lxc_tests;
 mod node_tests;
+mod task_tests;
 mod vm_tests;
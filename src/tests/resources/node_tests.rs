@@ -1,6 +1,8 @@
+use crate::core::domain::value_object::fresh_ticket;
 use crate::{
-    ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
-    ProxmoxUrl, ProxmoxUsername, ValidationConfig, core::infrastructure::api_client::ApiClient,
+    NodeOnlineStatus, NodePowerAction, ProxmoxClient, ProxmoxConnection, ProxmoxError, ProxmoxHost,
+    ProxmoxPassword, ProxmoxPort, ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, ServiceAction,
+    ValidationConfig, ValidationError, core::infrastructure::api_client::ApiClient,
 };
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
@@ -23,7 +25,7 @@ async fn create_authenticated_client(mock_server: &MockServer) -> ApiClient {
     let client = ApiClient::new(connection, config).unwrap();
 
     use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
-    let ticket = ProxmoxTicket::new_unchecked("PVE:testuser@pam:4EEC61E2::sig".to_string());
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
     let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
     let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
     client.set_auth(auth).await;
@@ -80,7 +82,7 @@ async fn test_nodes_list_success() {
     // Check first node
     let node1 = &nodes[0];
     assert_eq!(node1.node, "pve1");
-    assert_eq!(node1.status, "online");
+    assert_eq!(node1.status, NodeOnlineStatus::Online);
     assert_eq!(node1.cpu, Some(0.15));
     assert_eq!(node1.maxcpu, Some(8));
     assert_eq!(node1.mem, Some(8589934592));
@@ -90,11 +92,12 @@ async fn test_nodes_list_success() {
     assert_eq!(node1.uptime, Some(1234567));
     assert_eq!(node1.id.as_deref(), Some("node/pve1"));
     assert_eq!(node1.ssl_fingerprint.as_deref(), Some("AA:BB:CC:DD:EE:FF"));
+    assert!(node1.fingerprint().unwrap().matches("aa:bb:cc:dd:ee:ff"));
 
     // Check second node
     let node2 = &nodes[1];
     assert_eq!(node2.node, "pve2");
-    assert_eq!(node2.status, "online");
+    assert_eq!(node2.status, NodeOnlineStatus::Online);
     assert_eq!(node2.cpu, Some(0.08));
     assert_eq!(node2.maxcpu, Some(16));
     assert_eq!(node2.mem, Some(4294967296));
@@ -179,10 +182,11 @@ async fn test_node_status_success() {
     assert_eq!(status.loadavg, Some([1.2, 0.8, 0.5]));
     assert_eq!(status.wait, Some(0.03));
     assert_eq!(
-        status.cpuinfo.as_deref(),
+        status.cpuinfo.as_ref().map(|cpu| cpu.model.as_str()),
         Some("Intel(R) Xeon(R) CPU E5-2680 v4 @ 2.40GHz")
     );
     assert_eq!(status.pve_version.as_deref(), Some("7.3-1"));
+    assert_eq!(status.parsed_version().unwrap().unwrap(), (7, 3, 1));
 }
 
 #[tokio::test]
@@ -305,7 +309,7 @@ async fn test_node_dns_unauthorized_triggers_refresh() {
         .and(path("/api2/json/access/ticket"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
             "data": {
-                "ticket": "PVE:testuser@pam:4EEC61E2::refreshed",
+                "ticket": fresh_ticket("testuser@pam", "refreshed"),
                 "CSRFPreventionToken": "4EEC61E2:newtoken"
             }
         })))
@@ -333,3 +337,484 @@ async fn test_node_dns_unauthorized_triggers_refresh() {
     assert_eq!(dns.domain, "example.com");
     assert_eq!(dns.servers, vec!["8.8.8.8".to_string()]);
 }
+
+#[tokio::test]
+async fn test_node_services_list() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/services"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "service": "pveproxy",
+                    "name": "pveproxy",
+                    "desc": "PVE API Proxy Server",
+                    "state": "running",
+                    "active-state": "active"
+                },
+                {
+                    "service": "pvestatd",
+                    "name": "pvestatd",
+                    "desc": "PVE Status Daemon",
+                    "state": "stopped",
+                    "active-state": "failed"
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let services = proxmox_client.node_services("pve1").await.unwrap();
+    assert_eq!(services.len(), 2);
+    assert_eq!(services[0].service, "pveproxy");
+    assert_eq!(services[0].active_state.as_deref(), Some("active"));
+    assert_eq!(services[1].state.as_deref(), Some("stopped"));
+}
+
+#[tokio::test]
+async fn test_node_service_restart() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/services/pvestatd/restart"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:srvrestart:pvestatd:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let task_id = proxmox_client
+        .node_service_action("pve1", "pvestatd", ServiceAction::Restart)
+        .await
+        .unwrap();
+    assert!(task_id.contains("srvrestart"));
+}
+
+#[tokio::test]
+async fn test_node_service_action_rejects_bad_service_name() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let err = proxmox_client
+        .node_service_action(
+            "pve1",
+            "pveproxy/../../qemu/100/status",
+            ServiceAction::Stop,
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "service")
+    );
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_node_networks_bridge_and_bond() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/network"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "iface": "vmbr0",
+                    "type": "bridge",
+                    "method": "static",
+                    "address": "192.168.1.10",
+                    "netmask": "255.255.255.0",
+                    "gateway": "192.168.1.1",
+                    "bridge_ports": "bond0",
+                    "autostart": 1,
+                    "active": 1
+                },
+                {
+                    "iface": "bond0",
+                    "type": "bond",
+                    "method": "manual",
+                    "slaves": "eno1 eno2",
+                    "bond_mode": "802.3ad",
+                    "autostart": 1,
+                    "active": 1
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let interfaces = proxmox_client.node_networks("pve1").await.unwrap();
+    assert_eq!(interfaces.len(), 2);
+
+    let bridge = &interfaces[0];
+    assert_eq!(bridge.interface_type, "bridge");
+    assert_eq!(bridge.bridge_ports.as_deref(), Some("bond0"));
+    assert_eq!(bridge.gateway.as_deref(), Some("192.168.1.1"));
+
+    let bond = &interfaces[1];
+    assert_eq!(bond.interface_type, "bond");
+    assert_eq!(bond.slaves.as_deref(), Some("eno1 eno2"));
+    assert_eq!(bond.bond_mode.as_deref(), Some("802.3ad"));
+    assert_eq!(bond.address, None);
+}
+
+#[tokio::test]
+async fn test_reload_node_network() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/network"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:srvreload:networking:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let task_id = proxmox_client.reload_node_network("pve1").await.unwrap();
+    assert!(task_id.contains("srvreload"));
+}
+
+#[tokio::test]
+async fn test_version_parsed() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/version"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "version": "8.1.4",
+                "release": "8.1",
+                "repoid": "ec5affc9e41f1d79"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let info = proxmox_client.version().await.unwrap();
+    assert_eq!(info.release.as_deref(), Some("8.1"));
+    assert!(info.parsed().unwrap() >= (8, 0));
+}
+
+#[tokio::test]
+async fn test_ping() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/version"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "version": "8.1.4", "release": "8.1", "repoid": "ec5affc9e41f1d79" }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/version"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("pveproxy is restarting"))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    proxmox_client.ping().await.unwrap();
+    let err = proxmox_client.ping().await.unwrap_err();
+    assert_eq!(err.status_code(), Some(500));
+}
+
+#[tokio::test]
+async fn test_node_apt_updates() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/apt/update"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "Package": "pve-manager",
+                    "OldVersion": "8.3.0",
+                    "Version": "8.3.2",
+                    "Title": "Proxmox Virtual Environment Management Tools",
+                    "Priority": "optional",
+                    "Section": "admin",
+                    "Arch": "amd64"
+                },
+                {
+                    "Package": "proxmox-kernel-6.8.12-5-pve-signed",
+                    "Version": "6.8.12-5",
+                    "Title": "Proxmox Kernel Image (signed)",
+                    "Priority": "optional"
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let updates = proxmox_client.node_apt_updates("pve1").await.unwrap();
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0].package, "pve-manager");
+    assert_eq!(updates[0].old_version.as_deref(), Some("8.3.0"));
+    assert_eq!(updates[0].version, "8.3.2");
+    assert_eq!(updates[0].priority.as_deref(), Some("optional"));
+    assert_eq!(updates[0].extra["Arch"], "amd64");
+    assert_eq!(updates[1].old_version, None);
+    assert_eq!(
+        updates[1].title.as_deref(),
+        Some("Proxmox Kernel Image (signed)")
+    );
+}
+
+#[tokio::test]
+async fn test_node_needs_reboot_after_kernel_update() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "cpu": 0.05,
+                "memory": { "total": 1024, "used": 512, "free": 512 },
+                "uptime": 86400,
+                "kversion": "Linux 6.8.12-4-pve #1 SMP PREEMPT_DYNAMIC PMX 6.8.12-4",
+                "current-kernel": {
+                    "release": "6.8.12-4-pve",
+                    "sysname": "Linux",
+                    "machine": "x86_64"
+                }
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/apt/versions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "Package": "proxmox-ve",
+                    "Version": "8.3.0",
+                    "CurrentState": "Installed",
+                    "RunningKernel": "6.8.12-4-pve",
+                    "ManagerVersion": "8.3.0"
+                },
+                {
+                    "Package": "proxmox-kernel-6.8",
+                    "Version": "6.8.12-5",
+                    "CurrentState": "Installed"
+                },
+                {
+                    "Package": "proxmox-kernel-6.8.12-4-pve-signed",
+                    "Version": "6.8.12-4",
+                    "CurrentState": "Installed"
+                },
+                {
+                    "Package": "proxmox-kernel-6.8.12-5-pve-signed",
+                    "Version": "6.8.12-5",
+                    "CurrentState": "Installed"
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    assert!(proxmox_client.node_needs_reboot("pve1").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_node_time() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/time"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "time": 1_700_000_000,
+                "localtime": 1_700_003_600,
+                "timezone": "Europe/Madrid"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let time = proxmox_client.node_time("pve1").await.unwrap();
+    assert_eq!(time.time, 1_700_000_000);
+    assert_eq!(time.timezone, "Europe/Madrid");
+    assert_eq!(time.utc_offset_secs(), 3600);
+}
+
+#[tokio::test]
+async fn test_set_node_timezone_sends_timezone() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/time"))
+        .and(wiremock::matchers::body_json(serde_json::json!({
+            "timezone": "America/New_York"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    proxmox_client
+        .set_node_timezone("pve1", "America/New_York")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_node_status_full_payload() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "cpu": 0.05,
+                "wait": 0.001,
+                "uptime": 86400,
+                "loadavg": [0.42, 0.38, 0.31],
+                "memory": { "total": 67_108_864_000_i64, "used": 12_884_901_888_i64, "free": 54_223_962_112_i64 },
+                "swap": { "total": 8_589_934_592_i64, "used": 0, "free": 8_589_934_592_i64 },
+                "rootfs": {
+                    "total": 100_861_726_720_i64,
+                    "used": 14_577_602_560_i64,
+                    "free": 86_284_124_160_i64,
+                    "avail": 81_112_051_712_i64
+                },
+                "ksm": { "shared": 1_073_741_824 },
+                "cpuinfo": {
+                    "model": "AMD EPYC 7302P 16-Core Processor",
+                    "cpus": 32,
+                    "cores": 16,
+                    "sockets": 1,
+                    "mhz": "2999.997",
+                    "hvm": "1",
+                    "flags": "fpu vme de pse"
+                },
+                "current-kernel": {
+                    "sysname": "Linux",
+                    "release": "6.8.12-4-pve",
+                    "machine": "x86_64",
+                    "version": "#1 SMP PREEMPT_DYNAMIC PMX 6.8.12-4 (2024-11-06T15:04Z)"
+                },
+                "pveversion": "pve-manager/8.3.0/c1689ccb1065a83b"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let status = proxmox_client.node_status("pve1").await.unwrap();
+    let rootfs = status.rootfs.as_ref().unwrap();
+    assert_eq!(rootfs.total, 100_861_726_720);
+    assert_eq!(rootfs.avail, Some(81_112_051_712));
+    assert_eq!(status.ksm.as_ref().unwrap().shared, 1_073_741_824);
+    let cpu = status.cpuinfo.as_ref().unwrap();
+    assert_eq!(cpu.model, "AMD EPYC 7302P 16-Core Processor");
+    assert_eq!(cpu.cpus, Some(32));
+    assert_eq!(cpu.sockets, Some(1));
+    assert_eq!(cpu.mhz, Some(2999.997));
+    assert_eq!(status.running_kernel_release(), Some("6.8.12-4-pve"));
+}
+
+#[tokio::test]
+async fn test_node_power_requires_confirmation() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/status"))
+        .and(wiremock::matchers::body_string("command=reboot"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let refused = proxmox_client
+        .node_power("pve1", NodePowerAction::Reboot, false)
+        .await;
+    assert!(matches!(refused, Err(ProxmoxError::Validation { .. })));
+
+    proxmox_client
+        .node_power("pve1", NodePowerAction::Reboot, true)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,217 @@
+use crate::core::domain::value_object::fresh_ticket;
+use crate::{
+    ProxmoxClient, ProxmoxConnection, ProxmoxError, ProxmoxHost, ProxmoxPassword, ProxmoxPort,
+    ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, TaskId, TaskListItem, TaskListOptions,
+    ValidationConfig, ValidationError, core::infrastructure::api_client::ApiClient,
+};
+use futures::TryStreamExt;
+use std::time::Duration;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path, query_param},
+};
+
+fn create_test_connection(server_url: &str) -> ProxmoxConnection {
+    let host = ProxmoxHost::new_unchecked(server_url.trim_start_matches("http://").to_string());
+    let port = ProxmoxPort::new_unchecked(8006);
+    let username = ProxmoxUsername::new_unchecked("testuser".to_string());
+    let password = ProxmoxPassword::new_unchecked("testpass".to_string());
+    let realm = ProxmoxRealm::new_unchecked("pam".to_string());
+    let url = ProxmoxUrl::new_unchecked(server_url.to_string() + "/");
+    ProxmoxConnection::new(host, port, username, password, realm, false, true, url)
+}
+
+async fn create_authenticated_client(mock_server: &MockServer) -> ApiClient {
+    let connection = create_test_connection(&mock_server.uri());
+    let config = ValidationConfig::default();
+    let client = ApiClient::new(connection, config).unwrap();
+
+    use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+    let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+    let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
+    client.set_auth(auth).await;
+    client
+}
+
+const UPID: &str = "UPID:pve2:000A1B2C:0012D687:65A1B2C3:qmigrate:100:root@pam:";
+
+#[tokio::test]
+async fn test_stop_task_by_id_uses_upid_node() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path(format!("/api2/json/nodes/pve2/tasks/{}", UPID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let task = TaskId::parse(UPID).unwrap();
+    proxmox_client.stop_task_id(&task).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_stop_task_invalid_upid() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let result = proxmox_client.stop_task("pve1", "not-a-upid").await;
+    assert!(matches!(
+        result,
+        Err(ProxmoxError::Validation {
+            source: ValidationError::Format(_),
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_wait_for_task_with_progress_reports_each_poll() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+    let upid = "UPID:pve1:00000001:00000001:00000001:vzdump:100:root@pam:";
+
+    let status_path = format!("/api2/json/nodes/pve1/tasks/{}/status", upid);
+    Mock::given(method("GET"))
+        .and(path(status_path.clone()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "upid": upid, "node": "pve1", "status": "running", "type": "vzdump" }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(status_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "upid": upid,
+                "node": "pve1",
+                "status": "stopped",
+                "exitstatus": "OK",
+                "type": "vzdump"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let log_path = format!("/api2/json/nodes/pve1/tasks/{}/log", upid);
+    Mock::given(method("GET"))
+        .and(path(log_path.clone()))
+        .and(query_param("start", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                { "n": 1, "t": "INFO: starting new backup job: vzdump 100" },
+                { "n": 2, "t": "INFO:  40% (4.0 GiB of 10.0 GiB) in 10s" }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(log_path))
+        .and(query_param("start", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                { "n": 3, "t": "INFO: 100% (10.0 GiB of 10.0 GiB) in 25s" },
+                { "n": 4, "t": "INFO: Finished Backup of VM 100" }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let mut seen = Vec::new();
+    let status = proxmox_client
+        .wait_for_task_with_progress("pve1", upid, Duration::from_secs(10), |status| {
+            seen.push((status.status.clone(), status.progress));
+        })
+        .await
+        .unwrap();
+
+    assert!(status.is_success());
+    assert_eq!(status.progress, Some(100.0));
+    assert_eq!(
+        seen,
+        vec![
+            ("running".to_string(), Some(40.0)),
+            ("stopped".to_string(), Some(100.0)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_node_tasks_stream_pages_until_short_page() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    let task = |n: u32| {
+        serde_json::json!({
+            "upid": format!("UPID:pve1:0000000{}:00000001:00000001:vzdump:100:root@pam:", n),
+            "node": "pve1",
+            "type": "vzdump",
+            "id": "100",
+            "user": "root@pam",
+            "starttime": 1_700_000_000 + n,
+            "endtime": 1_700_000_060 + n,
+            "status": "OK"
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/tasks"))
+        .and(query_param("start", "0"))
+        .and(query_param("limit", "2"))
+        .and(query_param("vmid", "100"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "data": [task(1), task(2)] })),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/tasks"))
+        .and(query_param("start", "2"))
+        .and(query_param("limit", "2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": [task(3)] })),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let options = TaskListOptions::new().limit(2).vmid(100);
+    let tasks: Vec<TaskListItem> = proxmox_client
+        .node_tasks_stream("pve1", &options)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let starts: Vec<u64> = tasks.iter().map(|t| t.starttime).collect();
+    assert_eq!(starts, vec![1_700_000_001, 1_700_000_002, 1_700_000_003]);
+    assert_eq!(tasks[0].task_type, "vzdump");
+    assert_eq!(tasks[2].status.as_deref(), Some("OK"));
+}
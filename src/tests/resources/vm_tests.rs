@@ -1,7 +1,9 @@
+use crate::core::domain::value_object::fresh_ticket;
 use crate::{
-    ProxmoxClient, ProxmoxConnection, ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm,
-    ProxmoxUrl, ProxmoxUsername, ValidationConfig, core::domain::model::vm::*,
-    core::infrastructure::api_client::ApiClient,
+    IpAddressType, NodeName, PowerStatus, ProxmoxClient, ProxmoxConnection, ProxmoxError,
+    ProxmoxHost, ProxmoxPassword, ProxmoxPort, ProxmoxRealm, ProxmoxUrl, ProxmoxUsername, TaskId,
+    TaskListOptions, TaskOutcome, ValidationConfig, ValidationError, VmId,
+    core::domain::model::vm::*, core::infrastructure::api_client::ApiClient,
 };
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
@@ -24,13 +26,48 @@ async fn create_authenticated_client(mock_server: &MockServer) -> ApiClient {
     let client = ApiClient::new(connection, config).unwrap();
 
     use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
-    let ticket = ProxmoxTicket::new_unchecked("PVE:testuser@pam:4EEC61E2::sig".to_string());
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
     let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
     let auth = crate::ProxmoxAuth::new(ticket, Some(csrf));
     client.set_auth(auth).await;
     client
 }
 
+/// Parses a form-encoded request body into key/value pairs.
+fn form_pairs(req: &wiremock::Request) -> Vec<(String, String)> {
+    url::form_urlencoded::parse(&req.body)
+        .into_owned()
+        .collect()
+}
+
+/// Matches a form-encoded body containing every pair in `expected`.
+fn form_contains(
+    expected: &'static [(&'static str, &'static str)],
+) -> impl Fn(&wiremock::Request) -> bool + Send + Sync {
+    move |req| {
+        let pairs = form_pairs(req);
+        expected
+            .iter()
+            .all(|(k, v)| pairs.iter().any(|(pk, pv)| pk == k && pv == v))
+    }
+}
+
+/// Matches a form-encoded body consisting of exactly the pairs in `expected`.
+fn form_eq(
+    expected: &'static [(&'static str, &'static str)],
+) -> impl Fn(&wiremock::Request) -> bool + Send + Sync {
+    move |req| {
+        let mut pairs = form_pairs(req);
+        pairs.sort();
+        let mut expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        expected.sort();
+        pairs == expected
+    }
+}
+
 #[tokio::test]
 async fn test_vms_list_success() {
     let mock_server = MockServer::start().await;
@@ -81,7 +118,7 @@ async fn test_vms_list_success() {
     let vm1 = &vms[0];
     assert_eq!(vm1.vmid, 100);
     assert_eq!(vm1.name, "ubuntu-vm");
-    assert_eq!(vm1.status, "running");
+    assert_eq!(vm1.status, PowerStatus::Running);
     assert_eq!(vm1.cpu, Some(0.23));
     assert_eq!(vm1.maxcpu, Some(4));
     assert_eq!(vm1.mem, Some(4294967296));
@@ -96,7 +133,7 @@ async fn test_vms_list_success() {
     let vm2 = &vms[1];
     assert_eq!(vm2.vmid, 101);
     assert_eq!(vm2.name, "windows-vm");
-    assert_eq!(vm2.status, "stopped");
+    assert_eq!(vm2.status, PowerStatus::Stopped);
     assert_eq!(vm2.cpu, None);
     assert_eq!(vm2.maxcpu, Some(8));
     assert_eq!(vm2.mem, None);
@@ -131,6 +168,164 @@ async fn test_vms_list_empty() {
     assert!(vms.is_empty());
 }
 
+#[tokio::test]
+async fn test_vms_list_marks_templates() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "vmid": 100,
+                    "name": "web",
+                    "status": "running",
+                    "node": "pve1",
+                    "id": "qemu/100"
+                },
+                {
+                    "vmid": 9000,
+                    "name": "debian-12-golden",
+                    "status": "stopped",
+                    "node": "pve1",
+                    "id": "qemu/9000",
+                    "template": 1
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let vms = proxmox_client.vms("pve1").await.unwrap();
+    assert_eq!(vms[0].template, None);
+    assert!(!vms[0].is_template());
+    assert_eq!(vms[1].template, Some(true));
+    assert!(vms[1].is_template());
+}
+
+#[tokio::test]
+async fn test_migrate_vm_preconditions_with_local_disks() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/migrate"))
+        .and(wiremock::matchers::query_param("target", "pve2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "running": 1,
+                "allowed_nodes": ["pve2"],
+                "not_allowed_nodes": {
+                    "pve3": { "unavailable_storages": ["local-zfs"] }
+                },
+                "local_disks": [
+                    {
+                        "volid": "local-zfs:vm-100-disk-0",
+                        "size": 34359738368_i64,
+                        "drivename": "scsi0",
+                        "is_unused": 0
+                    },
+                    {
+                        "volid": "local-zfs:vm-100-disk-1",
+                        "size": 1073741824,
+                        "is_unused": 1
+                    }
+                ],
+                "local_resources": [],
+                "mapped-resources": []
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let checks = proxmox_client
+        .migrate_vm_preconditions("pve1", 100, "pve2")
+        .await
+        .unwrap();
+    assert!(checks.running);
+    assert!(checks.allows("pve2"));
+    assert!(!checks.allows("pve3"));
+    assert_eq!(
+        checks.not_allowed_nodes["pve3"].unavailable_storages,
+        ["local-zfs"]
+    );
+    assert_eq!(checks.local_disks.len(), 2);
+    assert_eq!(checks.local_disks[0].drivename.as_deref(), Some("scsi0"));
+    assert_eq!(checks.local_disks[0].size, Some(34_359_738_368));
+    assert_eq!(checks.local_disks[1].is_unused, Some(true));
+    assert!(checks.extra.contains_key("mapped-resources"));
+}
+
+#[tokio::test]
+async fn test_vm_pending_config() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/pending"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                { "key": "memory", "value": 2048, "pending": 4096 },
+                { "key": "name", "value": "web-01" },
+                { "key": "hostpci0", "value": "0000:01:00.0", "delete": 1 }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let items = proxmox_client.vm_pending_config("pve1", 100).await.unwrap();
+    assert_eq!(items.len(), 3);
+    let memory = &items[0];
+    assert_eq!(memory.key, "memory");
+    assert_eq!(memory.value, Some(serde_json::json!(2048)));
+    assert_eq!(memory.pending, Some(serde_json::json!(4096)));
+    assert!(memory.requires_restart());
+    let name = &items[1];
+    assert_eq!(name.pending, None);
+    assert!(!name.requires_restart());
+    assert!(items[2].requires_restart());
+}
+
+#[tokio::test]
+async fn test_template_vm() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/9000/template"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00001234:00005678:65A1B2C3:qmtemplate:9000:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let outcome = proxmox_client.template_vm("pve1", 9000).await.unwrap();
+    let task = outcome.task_id().unwrap();
+    assert!(task.as_str().contains(":qmtemplate:9000:"));
+}
+
 #[tokio::test]
 async fn test_vm_status_success() {
     let mock_server = MockServer::start().await;
@@ -170,7 +365,7 @@ async fn test_vm_status_success() {
     };
 
     let status = proxmox_client.vm_status("pve1", 100).await.unwrap();
-    assert_eq!(status.status, "running");
+    assert_eq!(status.status, PowerStatus::Running);
     assert_eq!(status.name, "ubuntu-vm");
     assert_eq!(status.cpu, Some(0.15));
     assert_eq!(status.mem, Some(4294967296));
@@ -209,7 +404,7 @@ async fn test_vm_status_minimal() {
     };
 
     let status = proxmox_client.vm_status("pve1", 100).await.unwrap();
-    assert_eq!(status.status, "stopped");
+    assert_eq!(status.status, PowerStatus::Stopped);
     assert_eq!(status.name, "test-vm");
     assert_eq!(status.cpu, None);
     assert_eq!(status.mem, None);
@@ -225,7 +420,7 @@ async fn test_start_vm_success() {
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:start"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
@@ -236,7 +431,10 @@ async fn test_start_vm_success() {
     };
 
     let task_id = proxmox_client.start_vm("pve1", 100).await.unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:start");
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
+    );
 }
 
 #[tokio::test]
@@ -247,7 +445,7 @@ async fn test_stop_vm_success() {
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu/100/status/stop"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:stop"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmstop:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
@@ -258,7 +456,10 @@ async fn test_stop_vm_success() {
     };
 
     let task_id = proxmox_client.stop_vm("pve1", 100).await.unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:stop");
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmstop:100:root@pam:"
+    );
 }
 
 #[tokio::test]
@@ -269,7 +470,7 @@ async fn test_shutdown_vm_success() {
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu/100/status/shutdown"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:shutdown"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmshutdown:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
@@ -280,7 +481,10 @@ async fn test_shutdown_vm_success() {
     };
 
     let task_id = proxmox_client.shutdown_vm("pve1", 100).await.unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:shutdown");
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmshutdown:100:root@pam:"
+    );
 }
 
 #[tokio::test]
@@ -291,7 +495,7 @@ async fn test_reboot_vm_success() {
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu/100/status/reboot"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:reboot"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmreboot:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
@@ -302,7 +506,10 @@ async fn test_reboot_vm_success() {
     };
 
     let task_id = proxmox_client.reboot_vm("pve1", 100).await.unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:reboot");
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmreboot:100:root@pam:"
+    );
 }
 
 #[tokio::test]
@@ -313,7 +520,7 @@ async fn test_reset_vm_success() {
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu/100/status/reset"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:reset"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmreset:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
@@ -324,7 +531,10 @@ async fn test_reset_vm_success() {
     };
 
     let task_id = proxmox_client.reset_vm("pve1", 100).await.unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:reset");
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmreset:100:root@pam:"
+    );
 }
 
 #[tokio::test]
@@ -346,7 +556,10 @@ async fn test_delete_vm_with_purge() {
         config: ValidationConfig::default(),
     };
 
-    let task_id = proxmox_client.delete_vm("pve1", 100, true).await.unwrap();
+    let task_id = proxmox_client
+        .delete_vm_simple("pve1", 100, true)
+        .await
+        .unwrap();
     assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:delete");
 }
 
@@ -369,8 +582,133 @@ async fn test_delete_vm_without_purge() {
         config: ValidationConfig::default(),
     };
 
-    let task_id = proxmox_client.delete_vm("pve1", 100, false).await.unwrap();
+    let task_id = proxmox_client
+        .delete_vm_simple("pve1", 100, false)
+        .await
+        .unwrap();
+    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:delete");
+}
+
+#[tokio::test]
+async fn test_delete_vm_with_options() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api2/json/nodes/pve1/qemu/100"))
+        .and(|req: &wiremock::Request| {
+            req.url.query() == Some("purge=1&destroy-unreferenced-disks=1")
+        })
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:delete"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let options = DeleteVmOptions::new()
+        .purge(true)
+        .destroy_unreferenced_disks(true);
+    let task_id = proxmox_client
+        .delete_vm("pve1", 100, &options)
+        .await
+        .unwrap();
     assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:delete");
+    assert_eq!(
+        options.skiplock(true).to_query(),
+        "purge=1&destroy-unreferenced-disks=1&skiplock=1"
+    );
+}
+
+#[tokio::test]
+async fn test_create_vm_sends_form_encoded_body() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(wiremock::matchers::header(
+            "content-type",
+            "application/x-www-form-urlencoded",
+        ))
+        .and(|req: &wiremock::Request| {
+            String::from_utf8_lossy(&req.body).contains("name=test-vm&memory=2048")
+        })
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmcreate:100:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = CreateVmParams::builder(100, "test-vm")
+        .memory(2048)
+        .net("virtio,bridge=vmbr0")
+        .build()
+        .unwrap();
+    proxmox_client.create_vm("pve1", &params).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_next_vmid_is_not_cached() {
+    let mock_server = MockServer::start().await;
+    let connection = create_test_connection(&mock_server.uri());
+    let config = ValidationConfig {
+        cache: Some(crate::CacheConfig {
+            ttl: std::time::Duration::from_secs(60),
+        }),
+        ..ValidationConfig::default()
+    };
+    let client = ApiClient::new(connection, config.clone()).unwrap();
+    use crate::core::domain::value_object::{ProxmoxCSRFToken, ProxmoxTicket};
+    let ticket = ProxmoxTicket::new_unchecked(fresh_ticket("testuser@pam", "sig"));
+    let csrf = ProxmoxCSRFToken::new_unchecked("4EEC61E2:token".to_string());
+    client
+        .set_auth(crate::ProxmoxAuth::new(ticket, Some(csrf)))
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "100"
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "101"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmcreate:100:root@pam:"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config,
+    };
+
+    let vmid = proxmox_client.next_vmid().await.unwrap();
+    assert_eq!(vmid, 100);
+    let params = CreateVmParams::builder(vmid, "test-vm").build().unwrap();
+    proxmox_client.create_vm("pve1", &params).await.unwrap();
+    assert_eq!(proxmox_client.next_vmid().await.unwrap(), 101);
 }
 
 #[tokio::test]
@@ -380,6 +718,11 @@ async fn test_create_vm_success() {
 
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(form_contains(&[
+            ("name", "test-vm"),
+            ("machine", "q35"),
+            ("scsi0", "local-lvm:32"),
+        ]))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
             "data": "UPID:pve1:00000001:00000001:00000001:create"
         })))
@@ -394,22 +737,24 @@ async fn test_create_vm_success() {
         cores: Some(2),
         threads: None,
         cpu: Some("host".to_string()),
-        ostype: Some("l26".to_string()),
+        ostype: Some(OsType::L26),
         kvm: Some(1),
         numa: None,
         net: Some("virtio,bridge=vmbr0".to_string()),
-        scsihw: Some("virtio-scsi-pci".to_string()),
+        scsihw: Some(ScsiControllerType::VirtioScsiPci),
         boot: Some("order=scsi0;net0".to_string()),
         start: Some(1),
         tags: Some("test".to_string()),
         description: Some("Created by leeca".to_string()),
         protection: None,
         tablet: Some(1),
-        vga: Some("virtio".to_string()),
+        vga: Some(VgaType::Virtio),
         bios: None,
+        machine: Some("q35".to_string()),
         efidisk: None,
         tpmstate: None,
         agent: Some(1),
+        disks: vec![DiskSpec::new("scsi", "local-lvm", 32)],
     };
 
     let proxmox_client = ProxmoxClient {
@@ -430,7 +775,6 @@ async fn test_vm_config_success() {
         .and(path("/api2/json/nodes/pve1/qemu/100/config"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
             "data": {
-                "vmid": 100,
                 "name": "ubuntu-vm",
                 "description": "Production VM",
                 "memory": 4096,
@@ -466,7 +810,7 @@ async fn test_vm_config_success() {
 
     let config = proxmox_client.vm_config("pve1", 100).await.unwrap();
     assert_eq!(config.vmid, 100);
-    assert_eq!(config.name, "ubuntu-vm");
+    assert_eq!(config.name.as_deref(), Some("ubuntu-vm"));
     assert_eq!(config.description.as_deref(), Some("Production VM"));
     assert_eq!(config.memory, Some(4096));
     assert_eq!(config.balloon, Some(1024));
@@ -499,35 +843,19 @@ async fn test_update_vm_config_success() {
     Mock::given(method("PUT"))
         .and(path("/api2/json/nodes/pve1/qemu/100/config"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:update"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmconfig:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
 
-    let params = CreateVmParams {
-        vmid: 100,
-        name: "ubuntu-vm".to_string(),
+    let params = UpdateVmConfig {
         memory: Some(8192),
         sockets: Some(2),
         cores: Some(4),
-        threads: None,
-        cpu: None,
-        ostype: None,
-        kvm: None,
-        numa: None,
-        net: None,
-        scsihw: None,
-        boot: None,
-        start: None,
         tags: Some("updated".to_string()),
         description: Some("Updated description".to_string()),
         protection: Some(1),
-        tablet: None,
-        vga: None,
-        bios: None,
-        efidisk: None,
-        tpmstate: None,
-        agent: None,
+        ..UpdateVmConfig::new()
     };
 
     let proxmox_client = ProxmoxClient {
@@ -535,11 +863,43 @@ async fn test_update_vm_config_success() {
         config: ValidationConfig::default(),
     };
 
-    let task_id = proxmox_client
+    let outcome = proxmox_client
+        .update_vm_config("pve1", 100, &params)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome.task_id().map(TaskId::as_str),
+        Some("UPID:pve1:00000001:00000001:00000001:qmconfig:100:root@pam:")
+    );
+}
+
+#[tokio::test]
+async fn test_update_vm_config_null_data_is_sync() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = UpdateVmConfig {
+        cores: Some(2),
+        ..UpdateVmConfig::new()
+    };
+    let outcome = proxmox_client
         .update_vm_config("pve1", 100, &params)
         .await
         .unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:update");
+    assert_eq!(outcome, TaskOutcome::Sync);
 }
 
 #[tokio::test]
@@ -560,7 +920,7 @@ async fn test_vm_actions_unauthorized_triggers_refresh() {
         .and(path("/api2/json/access/ticket"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
             "data": {
-                "ticket": "PVE:testuser@pam:4EEC61E2::refreshed",
+                "ticket": fresh_ticket("testuser@pam", "refreshed"),
                 "CSRFPreventionToken": "4EEC61E2:newtoken"
             }
         })))
@@ -571,7 +931,7 @@ async fn test_vm_actions_unauthorized_triggers_refresh() {
     Mock::given(method("POST"))
         .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "data": "UPID:pve1:00000001:00000001:00000001:start"
+            "data": "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
         })))
         .mount(&mock_server)
         .await;
@@ -582,5 +942,940 @@ async fn test_vm_actions_unauthorized_triggers_refresh() {
     };
 
     let task_id = proxmox_client.start_vm("pve1", 100).await.unwrap();
-    assert_eq!(task_id, "UPID:pve1:00000001:00000001:00000001:start");
+    assert_eq!(
+        task_id.task_id().unwrap().as_str(),
+        "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
+    );
+}
+
+#[tokio::test]
+async fn test_vm_agent_interfaces() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api2/json/nodes/pve1/qemu/100/agent/network-get-interfaces",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "result": [
+                    {
+                        "name": "eth0",
+                        "hardware-address": "bc:24:11:00:00:01",
+                        "ip-addresses": [
+                            {"ip-address": "192.168.1.50", "ip-address-type": "ipv4", "prefix": 24},
+                            {"ip-address": "fe80::be24:11ff:fe00:1", "ip-address-type": "ipv6", "prefix": 64}
+                        ],
+                        "statistics": {"rx-bytes": 1024, "tx-bytes": 2048}
+                    }
+                ]
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/api2/json/nodes/pve1/qemu/100/agent/network-get-interfaces",
+        ))
+        .respond_with(ResponseTemplate::new(500).set_body_string("QEMU guest agent is not running"))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let interfaces = proxmox_client
+        .vm_agent_interfaces("pve1", 100)
+        .await
+        .unwrap();
+    assert_eq!(interfaces.len(), 1);
+    let eth0 = &interfaces[0];
+    assert_eq!(eth0.name, "eth0");
+    assert_eq!(eth0.hardware_address.as_deref(), Some("bc:24:11:00:00:01"));
+    assert_eq!(eth0.ip_addresses.len(), 2);
+    assert_eq!(eth0.ip_addresses[0].ip_address, "192.168.1.50");
+    assert_eq!(eth0.ip_addresses[0].ip_address_type, IpAddressType::Ipv4);
+    assert_eq!(eth0.ip_addresses[0].prefix, 24);
+    assert_eq!(eth0.ip_addresses[1].ip_address_type, IpAddressType::Ipv6);
+    assert_eq!(eth0.ip_addresses[1].prefix, 64);
+    assert!(eth0.extra.contains_key("statistics"));
+
+    let err = proxmox_client
+        .vm_agent_interfaces("pve1", 100)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Connection(ref msg) if msg == "guest agent not responding")
+    );
+}
+
+#[tokio::test]
+async fn test_agent_exec_wait_decodes_output() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/agent/exec"))
+        .and(wiremock::matchers::body_json(serde_json::json!({
+            "command": ["cat", "/etc/hostname"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "pid": 42 }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // First poll: still running
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/agent/exec-status"))
+        .and(wiremock::matchers::query_param("pid", "42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "exited": 0 }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    // Second poll: exited with base64-encoded output
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/agent/exec-status"))
+        .and(wiremock::matchers::query_param("pid", "42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "exited": 1,
+                "exitcode": 0,
+                "out-data": "dWJ1bnR1LXZtCg==",
+                "err-data": ""
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let command = vec!["cat".to_string(), "/etc/hostname".to_string()];
+    let result = proxmox_client
+        .agent_exec_wait("pve1", 100, &command, std::time::Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(result.pid, 42);
+    assert!(result.success());
+    assert_eq!(result.stdout, "ubuntu-vm\n");
+    assert_eq!(result.stderr, "");
+    assert!(!result.truncated);
+}
+
+#[tokio::test]
+async fn test_agent_exec_wait_timeout() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/agent/exec"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "pid": 7 }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/agent/exec-status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "exited": false }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let command = vec!["sleep".to_string(), "60".to_string()];
+    let result = proxmox_client
+        .agent_exec_wait("pve1", 100, &command, std::time::Duration::from_millis(200))
+        .await;
+    assert!(matches!(result, Err(crate::ProxmoxError::Timeout(_))));
+}
+
+#[tokio::test]
+async fn test_vm_clone_sends_bwlimit() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/clone"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "newid": 201,
+            "full": 1,
+            "bwlimit": 102400
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmclone:100:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = CloneVmParams {
+        name: Some("clone-of-100".to_string()),
+        full: Some(1),
+        bwlimit: Some(102_400),
+        ..CloneVmParams::new(201)
+    };
+    let task_id = proxmox_client.vm_clone("pve1", 100, &params).await.unwrap();
+    assert!(task_id.contains("qmclone"));
+}
+
+#[tokio::test]
+async fn test_vm_vncproxy_parses_ticket() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/vncproxy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "cert": "-----BEGIN CERTIFICATE-----\nMIIF...\n-----END CERTIFICATE-----\n",
+                "port": "5900",
+                "ticket": "PVEVNC:65A1B2C3::sig",
+                "upid": "UPID:pve1:00000001:00000001:65A1B2C3:vncproxy:100:root@pam:",
+                "user": "root@pam"
+            }
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let proxy = proxmox_client.vm_vncproxy("pve1", 100).await.unwrap();
+    assert_eq!(proxy.port, 5900);
+    assert_eq!(proxy.ticket, "PVEVNC:65A1B2C3::sig");
+    assert_eq!(proxy.user.as_deref(), Some("root@pam"));
+    assert!(proxy.upid.unwrap().contains("vncproxy"));
+
+    // A second call must hit the server again rather than reusing the ticket.
+    proxmox_client.vm_vncproxy("pve1", 100).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_vm_spiceproxy_parses_connection() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/spiceproxy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "type": "spice",
+                "host": "pvespiceproxy:65a1b2c3:100:pve1::sig",
+                "proxy": "http://pve1:3128",
+                "password": "secret",
+                "tls-port": 61000,
+                "host-subject": "OU=PVE Cluster Node,O=Proxmox Virtual Environment,CN=pve1",
+                "delete-this-file": 1,
+                "title": "VM 100 - test-vm"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let spice = proxmox_client.vm_spiceproxy("pve1", 100).await.unwrap();
+    assert_eq!(spice.connection_type, "spice");
+    assert_eq!(spice.tls_port, 61000);
+    assert_eq!(spice.proxy, "http://pve1:3128");
+    assert_eq!(spice.delete_this_file, Some(1));
+}
+
+#[tokio::test]
+async fn test_move_disk_and_wait_failure_unlocks_vm() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+    let upid = "UPID:pve1:00000001:00000001:00000001:qmmove:100:root@pam:";
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/move_disk"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "disk": "scsi0",
+            "storage": "ceph"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": upid })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/api2/json/nodes/pve1/tasks/{}/status", upid)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "upid": upid,
+                "node": "pve1",
+                "status": "stopped",
+                "exitstatus": "storage migration failed: out of space",
+                "type": "qmmove",
+                "id": "100",
+                "user": "root@pam"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "name": "test-vm", "lock": "disk" }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .and(form_eq(&[("delete", "lock"), ("skiplock", "1")]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": null })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let err = proxmox_client
+        .move_disk_and_wait(
+            "pve1",
+            100,
+            &MoveDiskParams::new("scsi0", "ceph"),
+            std::time::Duration::from_secs(5),
+        )
+        .await
+        .unwrap_err();
+    match err {
+        crate::ProxmoxError::TaskFailed(message) => {
+            assert!(message.contains("out of space"));
+            assert!(message.contains("released leftover 'disk' lock"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_stop_all_vms_reports_partial_failure() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                { "vmid": 100, "name": "web", "status": "running", "node": "pve1", "id": "qemu/100" },
+                { "vmid": 101, "name": "db", "status": "running", "node": "pve1", "id": "qemu/101" },
+                { "vmid": 102, "name": "cache", "status": "running", "node": "pve1", "id": "qemu/102" },
+                { "vmid": 103, "name": "old", "status": "stopped", "node": "pve1", "id": "qemu/103" }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    for vmid in [100, 102] {
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "/api2/json/nodes/pve1/qemu/{}/status/stop",
+                vmid
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": format!("UPID:pve1:00000001:00000001:00000001:qmstop:{}:root@pam:", vmid)
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/101/status/stop"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("VM 101 is locked"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let results = proxmox_client.stop_all_vms("pve1").await.unwrap();
+    let vmids: Vec<u32> = results.iter().map(|(vmid, _)| *vmid).collect();
+    assert_eq!(vmids, vec![100, 101, 102]);
+    assert_eq!(
+        results[0].1.as_ref().unwrap().task_id().unwrap().id(),
+        "100"
+    );
+    assert!(results[1].1.is_err());
+    assert_eq!(
+        results[2].1.as_ref().unwrap().task_id().unwrap().id(),
+        "102"
+    );
+}
+
+#[tokio::test]
+async fn test_start_vm_and_wait_polls_until_stopped() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+    let upid = "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:";
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": upid })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let status_path = format!("/api2/json/nodes/pve1/tasks/{}/status", upid);
+    Mock::given(method("GET"))
+        .and(path(status_path.clone()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "upid": upid, "node": "pve1", "status": "running", "type": "qmstart" }
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(status_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "upid": upid,
+                "node": "pve1",
+                "status": "stopped",
+                "exitstatus": "OK",
+                "type": "qmstart"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let status = proxmox_client
+        .start_vm_and_wait("pve1", 100)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(status.is_success());
+    assert_eq!(status.exitstatus.as_deref(), Some("OK"));
+}
+
+#[tokio::test]
+async fn test_start_vm_accepts_null_data() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": null })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let outcome = proxmox_client.start_vm("pve1", 100).await.unwrap();
+    assert_eq!(outcome, TaskOutcome::Sync);
+    let status = proxmox_client.start_vm_and_wait("pve1", 100).await.unwrap();
+    assert!(status.is_none());
+}
+
+#[tokio::test]
+async fn test_shutdown_vm_with_fallback_graceful() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/shutdown"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmshutdown:100:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "status": "running", "name": "web" }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "status": "stopped", "name": "web" }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/stop"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let outcome = proxmox_client
+        .shutdown_vm_with_fallback("pve1", 100, std::time::Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(outcome, ShutdownOutcome::Graceful);
+}
+
+#[tokio::test]
+async fn test_shutdown_vm_with_fallback_forces_stop() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+    let shutdown_upid = "UPID:pve1:00000001:00000001:00000001:qmshutdown:100:root@pam:";
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/shutdown"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": shutdown_upid })),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "status": "running", "name": "web" }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!(
+            "/api2/json/nodes/pve1/tasks/{}",
+            shutdown_upid
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": null })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/stop"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000002:00000002:00000002:qmstop:100:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let outcome = proxmox_client
+        .shutdown_vm_with_fallback("pve1", 100, std::time::Duration::from_millis(1500))
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome,
+        ShutdownOutcome::Forced(TaskOutcome::Async(
+            TaskId::parse("UPID:pve1:00000002:00000002:00000002:qmstop:100:root@pam:").unwrap()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_clone_vm_full_to_other_node_returns_target_config() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+    let upid = "UPID:pve1:00000001:00000001:00000001:qmclone:100:root@pam:";
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/clone"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "newid": 201,
+            "target": "pve2",
+            "full": 1
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": upid })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/api2/json/nodes/pve1/tasks/{}/status", upid)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "upid": upid,
+                "node": "pve1",
+                "status": "stopped",
+                "exitstatus": "OK",
+                "type": "qmclone"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve2/qemu/201/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "name": "web-clone",
+                "memory": 2048,
+                "scsi0": "ceph:vm-201-disk-0,size=32G"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = CloneVmParams {
+        name: Some("web-clone".to_string()),
+        target: Some("pve2".to_string()),
+        ..CloneVmParams::new(201)
+    };
+    let (vmid, config) = proxmox_client
+        .clone_vm_full("pve1", 100, &params, std::time::Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(vmid, 201);
+    assert_eq!(config.name.as_deref(), Some("web-clone"));
+    assert_eq!(config.disks()["scsi0"], "ceph:vm-201-disk-0,size=32G");
+}
+
+#[tokio::test]
+async fn test_update_vm_config_sets_and_deletes() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .and(form_eq(&[("memory", "4096"), ("delete", "net1")]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmconfig:100:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = UpdateVmConfig {
+        memory: Some(4096),
+        ..UpdateVmConfig::new()
+    }
+    .delete("net1");
+    proxmox_client
+        .update_vm_config("pve1", 100, &params)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_ensure_vm_creates_missing_vm() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/150/config"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(form_contains(&[("vmid", "150"), ("name", "provisioned")]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmcreate:150:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = CreateVmParams::builder(150, "provisioned").build().unwrap();
+    match proxmox_client.ensure_vm("pve1", &params).await.unwrap() {
+        EnsuredVm::Created(upid) => assert!(upid.contains("qmcreate")),
+        other => panic!("expected creation, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_create_vm_with_next_vmid_retries_on_collision() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    // The suggestion is stale on the retry too, so the client has to move past it itself.
+    Mock::given(method("GET"))
+        .and(path("/api2/json/cluster/nextid"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": 100
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(form_contains(&[("vmid", "100")]))
+        .respond_with(
+            ResponseTemplate::new(500).set_body_string("VM 100 already exists on node 'pve2'"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .and(form_contains(&[("vmid", "101"), ("name", "provisioned")]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmcreate:101:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = CreateVmParams::builder(999, "provisioned").build().unwrap();
+    let (vmid, upid) = proxmox_client
+        .create_vm_with_next_vmid("pve1", &params)
+        .await
+        .unwrap();
+    assert_eq!(vmid, 101);
+    assert!(upid.contains("qmcreate:101"));
+}
+
+#[tokio::test]
+async fn test_ensure_vm_returns_existing_config() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/150/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "memory": 2048 }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = CreateVmParams::builder(150, "provisioned").build().unwrap();
+    match proxmox_client.ensure_vm("pve1", &params).await.unwrap() {
+        EnsuredVm::Existing(config) => {
+            assert_eq!(config.vmid, 150);
+            assert_eq!(config.name, None);
+            assert_eq!(config.memory, Some(2048));
+        }
+        other => panic!("expected the existing VM, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_node_and_vmid_rejected_before_request() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let err = proxmox_client.vms("pve1/").await.unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "node")
+    );
+    let err = proxmox_client.start_vm("pve1", 42).await.unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "vmid")
+    );
+    let err = proxmox_client.unlock_vm("pve1/", 100).await.unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "node")
+    );
+    let err = proxmox_client
+        .vm_agent_interfaces("pve1", 42)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "vmid")
+    );
+    let mut tasks = Box::pin(proxmox_client.node_tasks_stream("pve1/", &TaskListOptions::new()));
+    let err = futures::StreamExt::next(&mut tasks)
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(
+        matches!(err, ProxmoxError::Validation { source: ValidationError::Field { ref field, .. }, .. } if field == "node")
+    );
+
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_vm_methods_accept_validated_newtypes() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/status/start"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": "UPID:pve1:00000001:00000001:00000001:qmstart:100:root@pam:"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let node = NodeName::new("pve1").unwrap();
+    let vmid = VmId::new(100).unwrap();
+    proxmox_client.start_vm(&node, &vmid).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_replace_vm_config_deletes_extra_server_keys() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "name": "web01",
+                "memory": 2048,
+                "cores": 2,
+                "net0": "virtio=BC:24:11:00:00:01,bridge=vmbr0",
+                "net1": "virtio=BC:24:11:00:00:02,bridge=vmbr1",
+                "unused0": "local-lvm:vm-100-disk-1",
+                "digest": "abc123"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .and(form_eq(&[
+            ("memory", "4096"),
+            ("delete", "net1,unused0"),
+            ("digest", "abc123"),
+        ]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let desired: VmConfig = serde_json::from_value(serde_json::json!({
+        "vmid": 100,
+        "name": "web01",
+        "memory": 4096,
+        "cores": 2,
+        "net0": "virtio=BC:24:11:00:00:01,bridge=vmbr0"
+    }))
+    .unwrap();
+    let outcome = proxmox_client
+        .replace_vm_config("pve1", 100, &desired)
+        .await
+        .unwrap();
+    assert_eq!(outcome, TaskOutcome::Sync);
+}
+
+#[tokio::test]
+async fn test_update_vm_config_digest_mismatch_is_conflict() {
+    let mock_server = MockServer::start().await;
+    let client = create_authenticated_client(&mock_server).await;
+
+    Mock::given(method("PUT"))
+        .and(path("/api2/json/nodes/pve1/qemu/100/config"))
+        .and(form_eq(&[("memory", "4096"), ("digest", "stale0digest")]))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "data": null,
+            "message": "detected modified configuration - file changed by other user? Try again.\n"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let proxmox_client = ProxmoxClient {
+        api_client: client,
+        config: ValidationConfig::default(),
+    };
+
+    let params = UpdateVmConfig {
+        memory: Some(4096),
+        ..UpdateVmConfig::new()
+    }
+    .if_digest("stale0digest");
+    let err = proxmox_client
+        .update_vm_config("pve1", 100, &params)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ProxmoxError::Conflict(_)), "{:?}", err);
 }